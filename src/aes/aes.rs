@@ -1,50 +1,106 @@
-use crate::utils::helpers::{Cipher, FieldElement, to_binary, to_decimal};
+use crate::utils::helpers::{Cipher, FieldElement};
 use aes::Aes128;
 use aes::cipher::generic_array::GenericArray;
 use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
 
-/// Cuts bit array element to u8 element array
+/// Cuts 128 bit field element to u8 element array (big endian)
 ///
 /// # Example
+/// ```ignore
+/// assert_eq!(to_u8_slices(&FieldElement::new(0x9402, 128))[14..], [148, 2])
 /// ```
-/// assert_eq!(to_u8_slices(vec![1,0,0,1,0,1,0,0, 0,0,0,0,0,0,1,0]), vec![148, 2])
-/// ```
-fn to_u8_slices(elem: &FieldElement) -> Vec<u8> {
-    let mut result: Vec<u8> = Vec::with_capacity(16);
-    for i in 0..16 {
-        result.push(to_decimal(&elem[(i * 8)..((i * 8) + 8)]) as u8);
-    }
-    result
+fn to_u8_slices(elem: &FieldElement) -> [u8; 16] {
+    elem.value().low_u128().to_be_bytes()
 }
 
-fn from_u8_slices(elem: &Vec<u8>) -> FieldElement {
-    let mut result = Vec::with_capacity(128);
-    for i in 0..16 {
-        result.append(&mut to_binary(elem[i] as u128, 8));
-    }
-    result
+fn from_u8_slices(elem: &[u8]) -> FieldElement {
+    FieldElement::new(u128::from_be_bytes(elem.try_into().expect("AES block must be 16 bytes")), 128)
 }
 
 pub struct AES {}
 
 impl Cipher for AES {
     fn encrypt(&self, plaintext: &FieldElement, key: &FieldElement) -> FieldElement {
-        let _key = to_u8_slices(&key);
-        let cipher = Aes128::new(GenericArray::from_slice(&*_key));
-        let mut binding = to_u8_slices(&plaintext);
-        let _plaintext = GenericArray::from_mut_slice(&mut binding[0..16]);
+        let _key = to_u8_slices(key);
+        let cipher = Aes128::new(GenericArray::from_slice(&_key));
+        let mut binding = to_u8_slices(plaintext);
+        let _plaintext = GenericArray::from_mut_slice(&mut binding);
         cipher.encrypt_block(_plaintext);
 
-        from_u8_slices(&_plaintext.to_vec())
+        from_u8_slices(_plaintext)
     }
 
     fn decrypt(&self, ciphertext: &FieldElement, key: &FieldElement) -> FieldElement {
-        let _key = to_u8_slices(&key);
-        let cipher = Aes128::new(GenericArray::from_slice(&*_key));
-        let mut binding = to_u8_slices(&ciphertext);
-        let _ciphertext = GenericArray::from_mut_slice(&mut binding[0..16]);
+        let _key = to_u8_slices(key);
+        let cipher = Aes128::new(GenericArray::from_slice(&_key));
+        let mut binding = to_u8_slices(ciphertext);
+        let _ciphertext = GenericArray::from_mut_slice(&mut binding);
         cipher.decrypt_block(_ciphertext);
 
-        from_u8_slices(&_ciphertext.to_vec())
+        from_u8_slices(_ciphertext)
+    }
+
+    fn block_size(&self) -> u32 {
+        128
+    }
+
+    fn rounds(&self) -> usize {
+        10
+    }
+
+    fn name(&self) -> &'static str {
+        "AES"
+    }
+
+    fn parameters(&self) -> String {
+        String::from("n = 128, 10 rounds")
     }
-}
\ No newline at end of file
+
+    /// Expands the key only once for all the blocks.
+    fn encrypt_many(&self, blocks: &mut [FieldElement], key: &FieldElement) {
+        let cipher = Aes128::new(GenericArray::from_slice(&to_u8_slices(key)));
+        for block in blocks {
+            let mut binding = to_u8_slices(block);
+            cipher.encrypt_block(GenericArray::from_mut_slice(&mut binding));
+            *block = from_u8_slices(&binding);
+        }
+    }
+
+    fn decrypt_many(&self, blocks: &mut [FieldElement], key: &FieldElement) {
+        let cipher = Aes128::new(GenericArray::from_slice(&to_u8_slices(key)));
+        for block in blocks {
+            let mut binding = to_u8_slices(block);
+            cipher.decrypt_block(GenericArray::from_mut_slice(&mut binding));
+            *block = from_u8_slices(&binding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aes::aes::AES;
+    use crate::utils::helpers::{Cipher, FieldElement, generate_random_bits};
+
+    #[test]
+    fn fips_197() {
+        // Appendix C.1
+        let key = FieldElement::new(0x000102030405060708090a0b0c0d0e0f, 128);
+        let plaintext = FieldElement::new(0x00112233445566778899aabbccddeeff, 128);
+        let ciphertext = FieldElement::new(0x69c4e0d86a7b0430d8cdb78070b4c55a, 128);
+        assert_eq!(AES{}.encrypt(&plaintext, &key), ciphertext);
+        assert_eq!(AES{}.decrypt(&ciphertext, &key), plaintext);
+    }
+
+    #[test]
+    fn encrypt_many() {
+        let key = generate_random_bits(128);
+        let plaintexts: Vec<FieldElement> = (0..10).map(|_| generate_random_bits(128)).collect();
+        let mut blocks = plaintexts.clone();
+        AES{}.encrypt_many(&mut blocks, &key);
+        for (plaintext, ciphertext) in plaintexts.iter().zip(&blocks) {
+            assert_eq!(AES{}.encrypt(plaintext, &key), *ciphertext);
+        }
+        AES{}.decrypt_many(&mut blocks, &key);
+        assert_eq!(blocks, plaintexts);
+    }
+}
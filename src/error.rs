@@ -0,0 +1,127 @@
+//! Errors returned by the field and cipher constructors and operations, so that invalid parameters can be reported
+//! instead of panicking.
+
+use std::fmt;
+use std::fmt::Formatter;
+use crate::utils::g2p_field::G2P_BLOCK_SIZES;
+use crate::utils::helpers::MAX_BLOCK_SIZE;
+use crate::utils::prime_field::MAX_PRIME_BITS;
+use crate::utils::uint::Uint;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// 2^n field arithmetic is only implemented for 2 <= n <= [`MAX_BLOCK_SIZE`], AES only for 128 bits.
+    UnsupportedBlockSize(u32),
+    /// MiMC and the half-trace need an odd block size.
+    EvenBlockSize(u32),
+    /// Feistel ciphers need an even block size.
+    OddBlockSize(u32),
+    /// Block size which cannot be split into the branches of a generalized Feistel network, at least 2 branches of at
+    /// least 2 bits are needed.
+    InvalidBranches { block_size: u32, branches: u32 },
+    /// GMiMC MRF pairs the branches, so their number must be even.
+    OddBranches(u32),
+    /// Sponge rate and capacity have to be non-zero and fill the permutation block.
+    InvalidRate { rate: u32, capacity: u32, block_size: u32 },
+    /// Miyaguchi-Preneel uses the chaining value as the key, thus the cipher key must be as large as the block.
+    KeySizeMismatch { key_size: u32, block_size: u32 },
+    /// Key of the key schedule is longer than [`Uint::BITS`](crate::utils::uint::Uint::BITS), size is in bits.
+    KeyTooLarge(usize),
+    /// Ciphertext of a padded mode which is not a whole number of padded blocks, length is in bits.
+    InvalidCiphertextLength { length: usize, block_size: u32 },
+    /// Last lcm(n, 8) bits of the decrypted message do not end with 1 followed by zeros after a whole byte, usually due
+    /// to a wrong key (but a wrong key is not always detected).
+    InvalidPadding,
+    /// Message whose length in bits does not fit into the length block of CBC-MAC.
+    MessageTooLong { length: usize, block_size: u32 },
+    /// Tag which does not authenticate the message, usually due to a wrong key or a modified message.
+    InvalidTag,
+    /// Format-preserving encryption of the empty range [0, 0).
+    EmptyDomain,
+    /// Range [0, N) of format-preserving encryption which does not fit the cipher blocks.
+    DomainTooLarge { domain: u128, block_size: u32 },
+    /// Value outside of the range [0, N) of format-preserving encryption.
+    OutOfDomain { value: Uint, domain: u128 },
+    /// Tweak given to a cipher which does not take it.
+    NotTweakable,
+    /// Reduction polynomial which does not define a field.
+    ReducibleModulus(Uint),
+    /// Prime field modulus which is not an odd prime.
+    NotPrime(Uint),
+    /// Prime field modulus with more than [`MAX_PRIME_BITS`] bits.
+    PrimeTooLarge(u32),
+    /// Exponent smaller than 2, x^0 and x^1 do not give a cipher.
+    InvalidExponent(u128),
+    /// x^e is not a permutation of the field, thus it cannot be decrypted.
+    NotPermutation(u128),
+    /// Round reduction removes all the rounds.
+    TooManyRoundsRemoved { rounds: usize, round_reduction: usize },
+    /// Cipher needs at least one round constant.
+    NoRoundConstants,
+    /// g2p backend is not generated for the field (see [`G2P_BLOCK_SIZES`]).
+    BackendUnavailable(u32),
+    /// g2p backend combined with constant-time arithmetic, g2p uses lookup tables.
+    BackendNotConstantTime,
+    /// Inverse of zero.
+    ZeroInverse,
+    /// Invalid textual representation of a field or a number.
+    Parse(String),
+    /// Input which could not be read, e.g. a missing file.
+    Io(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnsupportedBlockSize(block_size) => write!(f, "block size must be from 2 to {MAX_BLOCK_SIZE} (128 for AES), got {block_size}"),
+            Error::EvenBlockSize(block_size) => write!(f, "block size must be odd, got {block_size}"),
+            Error::OddBlockSize(block_size) => write!(f, "block size of a Feistel cipher must be even, got {block_size}"),
+            Error::InvalidBranches { block_size, branches } =>
+                write!(f, "block size {block_size} cannot be split into {branches} branches of at least 2 bits"),
+            Error::OddBranches(branches) => write!(f, "MRF needs an even number of branches, got {branches}"),
+            Error::InvalidRate { rate, capacity, block_size } =>
+                write!(f, "rate {rate} and capacity {capacity} must be non-zero and add up to the block size {block_size}"),
+            Error::KeyTooLarge(key_size) => write!(f, "key of {key_size} bits does not fit into {} bits, use smaller blocks", Uint::BITS),
+            Error::KeySizeMismatch { key_size, block_size } =>
+                write!(f, "cipher key of {key_size} bits does not match the block size {block_size}"),
+            Error::InvalidCiphertextLength { length, block_size } =>
+                write!(f, "ciphertext of {length} bits does not fit the padded {block_size}-bit blocks"),
+            Error::InvalidPadding => write!(f, "invalid padding of the decrypted message, wrong key or corrupted ciphertext"),
+            Error::MessageTooLong { length, block_size } => write!(f, "message of {length} bits is too long for the {block_size}-bit length block"),
+            Error::InvalidTag => write!(f, "tag does not authenticate the message, wrong key or modified message"),
+            Error::EmptyDomain => write!(f, "domain must contain at least one value"),
+            Error::DomainTooLarge { domain, block_size } => write!(f, "domain [0, {domain}) does not fit into {block_size}-bit blocks"),
+            Error::OutOfDomain { value, domain } => write!(f, "value {value} is not in the domain [0, {domain})"),
+            Error::NotTweakable => write!(f, "tweak is only supported by MiMCGe over 2^n fields"),
+            Error::ReducibleModulus(modulus) => write!(f, "polynomial {modulus:#x} is reducible"),
+            Error::NotPrime(modulus) => write!(f, "{modulus} is not an odd prime"),
+            Error::PrimeTooLarge(bits) => write!(f, "prime must have at most {MAX_PRIME_BITS} bits, got {bits}"),
+            Error::InvalidExponent(exponent) => write!(f, "exponent must be at least 2, got {exponent}"),
+            Error::NotPermutation(exponent) => write!(f, "x^{exponent} is not a permutation polynomial of the field"),
+            Error::TooManyRoundsRemoved { rounds, round_reduction } =>
+                write!(f, "round reduction {round_reduction} leaves no rounds, the cipher has {rounds}"),
+            Error::NoRoundConstants => write!(f, "at least one round constant is needed"),
+            Error::BackendUnavailable(_) =>
+                write!(f, "g2p backend is only available for block sizes {G2P_BLOCK_SIZES:?} with the default polynomials"),
+            Error::BackendNotConstantTime => write!(f, "g2p backend is not constant-time"),
+            Error::ZeroInverse => write!(f, "zero has no multiplicative inverse"),
+            Error::Parse(message) | Error::Io(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error.to_string())
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Parse(message)
+    }
+}
@@ -1,109 +1,296 @@
 use std::time::{Duration, Instant};
 use crate::aes::aes::AES;
+use crate::error::{Error, Result};
+use crate::hash::hash::{HashFunction, HashType, Padding};
+use crate::hash::miyaguchi_preneel::MiyaguchiPreneel;
+use crate::hash::sponge::Sponge;
+use crate::mac::cbc_mac::CbcMac;
+use crate::mac::cmac::Cmac;
+use crate::mac::mac::{Mac, MacType};
+use crate::mac::sponge_mac::SpongeMac;
 use crate::mimc::mimc::MiMC;
+use crate::mimc_general::gmimc::GMiMC;
+use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
 use crate::mimc_general::mimc_general::MiMCGe;
-use crate::utils::helpers::{Cipher, CipherType, FieldElement, generate_random_bits, to_binary};
+use crate::mimc_general::mimc_prime::MiMCGePrime;
+use crate::utils::helpers::{BinaryField, Cipher, CipherType, FieldElement, fixed_round_constants, generate_random_bits, generate_round_constants, TweakableCipher};
 
-pub fn choose_cipher(t: &CipherType, block_size: u32) -> Box<dyn Cipher> {
-    match t {
+/// Builds the cipher of the given type with random round constants, unless the type carries its own.
+///
+/// Returns an error if the parameters do not give a valid cipher, e.g. the exponent is not a permutation of the field.
+pub fn choose_cipher(t: &CipherType, block_size: u32) -> Result<Box<dyn Cipher>> {
+    choose_cipher_with(t, block_size, generate_round_constants)
+}
+
+/// Same as [`choose_cipher`], missing round constants are made by `generate_constants` (number of rounds, size of the
+/// constants), e.g. [`fixed_round_constants`](crate::utils::helpers::fixed_round_constants) for reproducible ciphers.
+pub fn choose_cipher_with(t: &CipherType, block_size: u32, generate_constants: fn(usize, u32) -> Vec<FieldElement>) -> Result<Box<dyn Cipher>> {
+    Ok(match t {
+        CipherType::AES if block_size != 128 => return Err(Error::UnsupportedBlockSize(block_size)),
         CipherType::AES => Box::new(AES{}),
-        CipherType::MiMC => Box::new(MiMC::new(block_size)),
-        CipherType::MiMCGe(e, rc, rr) => {
-            if !rc.is_empty() {
-                Box::new(MiMCGe::with_round_constants(
-                    *e,
-                    block_size,
-                    &rc.iter().map(|x| to_binary(*x, block_size)).collect::<Vec<FieldElement>>()
-                ))
+        CipherType::MiMC(field, method, key_schedule) => {
+            let field = match field {
+                Some(field) => *field,
+                None => BinaryField::new(block_size)?,
+            };
+            Box::new(MiMC::with_key_schedule(field, &generate_constants(MiMC::number_of_rounds(block_size), block_size), *method, *key_schedule)?)
+        }
+        CipherType::MiMCGe(..) => choose_tweakable_cipher_with(t, block_size, generate_constants)?,
+        CipherType::MiMCGePrime(e, rc, rr, field) => {
+            let round_constants = if !rc.is_empty() {
+                rc.iter().map(|x| FieldElement::from_uint(*x, block_size)).collect::<Vec<FieldElement>>()
             }
             else {
-                Box::new(MiMCGe::new(*e, block_size, *rr))
+                generate_constants(MiMCGePrime::number_of_rounds(*e, field, *rr)?, block_size)
+            };
+            Box::new(MiMCGePrime::with_round_constants(*e, *field, &round_constants)?)
+        }
+        CipherType::MiMCGeFeistel(e, rc, rr, field) => {
+            let half_size = MiMCGeFeistel::half_size(block_size)?;
+            let field = match field {
+                Some(field) => *field,
+                None => BinaryField::new(half_size)?,
+            };
+            let round_constants = if !rc.is_empty() {
+                rc.iter().map(|x| FieldElement::from_uint(*x, half_size)).collect::<Vec<FieldElement>>()
+            }
+            else {
+                generate_constants(MiMCGeFeistel::number_of_rounds(*e, block_size, *rr)?, half_size)
+            };
+            Box::new(MiMCGeFeistel::with_field(*e, field, &round_constants)?)
+        }
+        CipherType::GMiMC(variant, branches, e, rc, rr, field) => {
+            let branch_size = GMiMC::branch_size(*branches, block_size)?;
+            let field = match field {
+                Some(field) => *field,
+                None => BinaryField::new(branch_size)?,
+            };
+            let round_constants = if !rc.is_empty() {
+                rc.iter().map(|x| FieldElement::from_uint(*x, branch_size)).collect::<Vec<FieldElement>>()
+            }
+            else {
+                generate_constants(GMiMC::number_of_rounds(*e, *branches, block_size, *rr)?, branch_size)
+            };
+            Box::new(GMiMC::with_field(*variant, *e, *branches, field, &round_constants)?)
+        }
+    })
+}
+
+/// Builds the tweakable cipher of the given type with random round constants, unless the type carries its own. Only
+/// MiMCGe over 2^n fields is tweakable.
+pub fn choose_tweakable_cipher(t: &CipherType, block_size: u32) -> Result<Box<dyn TweakableCipher>> {
+    choose_tweakable_cipher_with(t, block_size, generate_round_constants)
+}
+
+/// Same as [`choose_tweakable_cipher`], missing round constants are made by `generate_constants`.
+pub fn choose_tweakable_cipher_with(t: &CipherType, block_size: u32, generate_constants: fn(usize, u32) -> Vec<FieldElement>) -> Result<Box<dyn TweakableCipher>> {
+    match t {
+        CipherType::MiMCGe(e, rc, rr, field, method, key_schedule) => {
+            let field = match field {
+                Some(field) => *field,
+                None => BinaryField::new(block_size)?,
+            };
+            let round_constants = if !rc.is_empty() {
+                rc.iter().map(|x| FieldElement::from_uint(*x, block_size)).collect::<Vec<FieldElement>>()
             }
+            else {
+                generate_constants(MiMCGe::number_of_rounds(*e, block_size, *rr)?, block_size)
+            };
+            Ok(Box::new(MiMCGe::with_key_schedule(*e, field, &round_constants, *method, *key_schedule)?))
         }
+        _ => Err(Error::NotTweakable),
     }
 }
 
-pub fn diffusion(cipher: &Box<dyn Cipher>, block_size: u32) -> usize {
+/// Size of the key of the cipher type in bits, e.g. for random keys of the experiments. The 2K key schedule of MiMC and
+/// MiMCGe needs two blocks.
+///
+/// Returns an error if the key is too large.
+pub fn key_size(t: &CipherType, block_size: u32) -> Result<u32> {
+    match t {
+        CipherType::MiMC(_, _, key_schedule) | CipherType::MiMCGe(.., key_schedule) => key_schedule.key_size(block_size),
+        _ => Ok(block_size),
+    }
+}
+
+/// Builds the hash function of the given type over the cipher. Round constants are [`fixed_round_constants`], so that
+/// the digests are the same in every run.
+pub fn choose_hash(t: &HashType, cipher_type: &CipherType, block_size: u32) -> Result<Box<dyn HashFunction>> {
+    let cipher = choose_cipher_with(cipher_type, block_size, fixed_round_constants)?;
+    Ok(match t {
+        HashType::Sponge(rate, capacity, digest_size, padding) => Box::new(sponge(cipher, *rate, *capacity, *digest_size, *padding)?),
+        HashType::MiyaguchiPreneel(padding) => Box::new(MiyaguchiPreneel::new(cipher, *padding)?),
+    })
+}
+
+/// Builds the MAC of the given type over the cipher. Round constants are [`fixed_round_constants`], so that the tags
+/// can be verified by another run.
+pub fn choose_mac(t: &MacType, cipher_type: &CipherType, block_size: u32) -> Result<Box<dyn Mac>> {
+    let cipher = choose_cipher_with(cipher_type, block_size, fixed_round_constants)?;
+    Ok(match t {
+        MacType::CbcMac => Box::new(CbcMac::new(cipher)),
+        MacType::Cmac => Box::new(Cmac::new(cipher)?),
+        MacType::Sponge(rate, capacity, tag_size) => Box::new(SpongeMac::new(sponge(cipher, *rate, *capacity, *tag_size, Padding::MultiRate)?)),
+    })
+}
+
+/// Sponge over the permutation, rate is half of the block if neither rate nor capacity is given and the digest is one
+/// block by default.
+fn sponge(permutation: Box<dyn Cipher>, rate: Option<u32>, capacity: Option<u32>, digest_size: Option<u32>, padding: Padding) -> Result<Sponge> {
+    let block_size = permutation.block_size();
+    let (rate, capacity) = match (rate, capacity) {
+        (Some(rate), Some(capacity)) => (rate, capacity),
+        (Some(rate), None) => (rate, block_size.saturating_sub(rate)),
+        (None, Some(capacity)) => (block_size.saturating_sub(capacity), capacity),
+        (None, None) => (block_size / 2, block_size - block_size / 2),
+    };
+    Sponge::new(permutation, rate, capacity, padding, digest_size.unwrap_or(block_size))
+}
+
+/// Number of ciphertext bits that flip when each plaintext bit is flipped in turn, summed over all plaintext bits. Key
+/// and plaintext are random.
+pub fn diffusion(cipher: &dyn Cipher) -> usize {
     let mut result = 0;
-    let key = generate_random_bits(block_size);
-    let plaintext = generate_random_bits(block_size);
+    let key = generate_random_bits(cipher.key_size());
+    let plaintext = generate_random_bits(cipher.block_size());
     let ciphertext = cipher.encrypt(&plaintext, &key);
 
-    for i in 0..(block_size as usize) {
-        let mut new_plaintext = plaintext.to_vec();
-        new_plaintext[i] ^= 1; // Flip ith bit
+    for i in 0..cipher.block_size() {
+        let new_plaintext = plaintext.flip_bit(i); // Flip ith bit
         let new_ciphertext = cipher.encrypt(&new_plaintext, &key);
         // Count how many bits have flipped
-        result += new_ciphertext.iter().enumerate().fold(0, |acc, (i, bit)| if ciphertext[i] ^ bit >= 1 { acc + 1 } else { acc });
+        result += (ciphertext ^ new_ciphertext).count_ones() as usize;
     }
 
     result
 }
 
-pub fn confusion(cipher: &Box<dyn Cipher>, block_size: u32) -> usize {
+/// Number of ciphertext bits that flip when each key bit is flipped in turn, summed over all key bits. Key and
+/// plaintext are random.
+pub fn confusion(cipher: &dyn Cipher) -> usize {
     let mut result = 0;
-    let key = generate_random_bits(block_size);
-    let plaintext = generate_random_bits(block_size);
+    let key = generate_random_bits(cipher.key_size());
+    let plaintext = generate_random_bits(cipher.block_size());
     let ciphertext = cipher.encrypt(&plaintext, &key);
 
-    for i in 0..(block_size as usize) {
-        let mut new_key = key.to_vec();
-        new_key[i] ^= 1; // Flip ith bit
+    for i in 0..cipher.key_size() {
+        let new_key = key.flip_bit(i); // Flip ith bit
         let new_ciphertext = cipher.encrypt(&plaintext, &new_key);
         // Count how many bits have flipped
-        result += new_ciphertext.iter().enumerate().fold(0, |acc, (i, bit)| if ciphertext[i] ^ bit >= 1 { acc + 1 } else { acc });
+        result += (ciphertext ^ new_ciphertext).count_ones() as usize;
     }
 
     result
 }
 
-fn encryption(plaintexts: Vec<FieldElement>, key: FieldElement, cipher: &Box<dyn Cipher>) -> Duration {
-    let start = Instant::now();
-    for plaintext in plaintexts {
-        cipher.encrypt(&plaintext, &key);
+/// Number of ciphertext bits that flip when each tweak bit is flipped in turn, summed over all tweak bits. Key, tweak
+/// and plaintext are random.
+pub fn tweak_diffusion(cipher: &dyn TweakableCipher) -> usize {
+    let mut result = 0;
+    let key = generate_random_bits(cipher.key_size());
+    let tweak = generate_random_bits(cipher.tweak_size());
+    let plaintext = generate_random_bits(cipher.block_size());
+    let ciphertext = cipher.encrypt_tweaked(&plaintext, &key, &tweak);
+
+    for i in 0..cipher.tweak_size() {
+        let new_tweak = tweak.flip_bit(i); // Flip ith bit
+        let new_ciphertext = cipher.encrypt_tweaked(&plaintext, &key, &new_tweak);
+        // Count how many bits have flipped
+        result += (ciphertext ^ new_ciphertext).count_ones() as usize;
     }
-    start.elapsed()
+
+    result
 }
 
-fn decryption(ciphertexts: Vec<FieldElement>, key: FieldElement, cipher: &Box<dyn Cipher>) -> Duration {
-    let start = Instant::now();
-    for ciphertext in ciphertexts {
-        cipher.decrypt(&ciphertext, &key);
-    }
-    start.elapsed()
+/// Summary of repeated [`diffusion`], [`confusion`] or [`tweak_diffusion`] measurements.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    /// Number of measurements.
+    pub samples: usize,
+    pub block_size: u32,
+    /// Number of flipped bits in one measurement, i.e. block, key or tweak size.
+    pub input_size: u32,
+    /// Sum of all measurements.
+    pub sum: f64,
+    pub mean: f64,
+    /// Mean of a random permutation, half of the input_size * block_size bits flip.
+    pub expected_mean: f64,
+    pub standard_deviation: f64,
 }
 
-pub fn decryption_encryption(decrypt: bool, test_size: usize, sample_size: usize, block_size: u32, cipher_type: CipherType) -> Duration {
-    let mut start = Duration::new(0, 0);
-    for _ in 0..test_size {
-        let cipher = choose_cipher(&cipher_type, block_size);
-        let mut plaintexts: Vec<FieldElement> = Vec::with_capacity(sample_size);
-        for _ in 0..sample_size {
-            plaintexts.push(generate_random_bits(block_size));
+impl Statistics {
+    /// Statistics of `test_size` runs of the `measure` function on the cipher, which flips each of `input_size` bits.
+    fn measure<C: Cipher + ?Sized>(test_size: usize, cipher: &C, input_size: u32, measure: fn(&C) -> usize) -> Self {
+        let block_size = cipher.block_size();
+        let mut sum = 0.0;
+        let mut ssq = 0.0;
+        for _ in 0..test_size {
+            let r = measure(cipher) as f64;
+            sum += r;
+            ssq += r.powi(2);
         }
-        if decrypt {
-            start += decryption(plaintexts, generate_random_bits(block_size), &cipher);
-        } else {
-            start += encryption(plaintexts, generate_random_bits(block_size), &cipher);
+        Statistics {
+            samples: test_size,
+            block_size,
+            input_size,
+            sum,
+            mean: sum / test_size as f64,
+            expected_mean: 0.5 * ((input_size * block_size) as f64),
+            standard_deviation: standard_deviation(ssq, sum, test_size),
         }
     }
-    start
+
+    /// Share of flipped bits in 1/10000, 5000 is expected.
+    pub fn score(&self) -> f64 {
+        self.sum / (self.samples * (self.input_size * self.block_size) as usize) as f64 * 10000.0
+    }
 }
 
+/// [`diffusion`] of the cipher repeated **test_size** times with the same cipher.
+pub fn diffusion_statistics(test_size: usize, block_size: u32, cipher_type: CipherType) -> Result<Statistics> {
+    let cipher = choose_cipher(&cipher_type, block_size)?;
+    Ok(Statistics::measure(test_size, cipher.as_ref(), cipher.block_size(), diffusion))
+}
+
+/// [`confusion`] of the cipher repeated **test_size** times with the same cipher.
+pub fn confusion_statistics(test_size: usize, block_size: u32, cipher_type: CipherType) -> Result<Statistics> {
+    let cipher = choose_cipher(&cipher_type, block_size)?;
+    Ok(Statistics::measure(test_size, cipher.as_ref(), cipher.key_size(), confusion))
+}
 
-fn bits_to_byte(bits: &[u8]) -> u8 {
-    (bits[0] << 7)
-        | (bits[1] << 6)
-        | (bits[2] << 5)
-        | (bits[3] << 4)
-        | (bits[4] << 3)
-        | (bits[5] << 2)
-        | (bits[6] << 1)
-        | bits[7]
+/// [`tweak_diffusion`] of the cipher repeated **test_size** times with the same cipher.
+pub fn tweak_statistics(test_size: usize, block_size: u32, cipher_type: CipherType) -> Result<Statistics> {
+    let cipher = choose_tweakable_cipher(&cipher_type, block_size)?;
+    Ok(Statistics::measure(test_size, cipher.as_ref(), cipher.tweak_size(), tweak_diffusion))
 }
 
-pub fn to_32_bit(a: FieldElement) -> [u8; 4] {
-    [bits_to_byte(&a[0..8]),bits_to_byte(&a[8..16]),bits_to_byte(&a[16..24]), bits_to_byte(&a[24..32])]
+/// Time to encrypt (or decrypt) `sample_size` random blocks with a random key.
+fn encryption_time(decrypt: bool, sample_size: usize, cipher: &dyn Cipher) -> Duration {
+    let mut blocks: Vec<FieldElement> = (0..sample_size).map(|_| generate_random_bits(cipher.block_size())).collect();
+    let key = generate_random_bits(cipher.key_size());
+    let start = Instant::now();
+    if decrypt {
+        cipher.decrypt_many(&mut blocks, &key);
+    } else {
+        cipher.encrypt_many(&mut blocks, &key);
+    }
+    start.elapsed()
+}
+
+/// Total time to decrypt (or encrypt) **test_size** x **sample_size** random blocks. The cipher and the key are created
+/// again for every one of the **test_size** batches and are not included in the time.
+pub fn decryption_encryption(decrypt: bool, test_size: usize, sample_size: usize, block_size: u32, cipher_type: CipherType) -> Result<Duration> {
+    let mut start = Duration::new(0, 0);
+    for _ in 0..test_size {
+        let cipher = choose_cipher(&cipher_type, block_size)?;
+        start += encryption_time(decrypt, sample_size, cipher.as_ref());
+    }
+    Ok(start)
+}
+
+/// Takes the highest 32 bits of the element as big endian bytes. Element must have at least 32 bits.
+pub fn to_32_bit(a: FieldElement, block_size: u32) -> [u8; 4] {
+    ((a.value() >> (block_size - 32)).low_u128() as u32).to_be_bytes()
 }
 
 /// Calculating the standard deviation
@@ -112,3 +299,60 @@ pub fn to_32_bit(a: FieldElement) -> [u8; 4] {
 pub fn standard_deviation(ssq: f64, sum: f64, n: usize) -> f64 {
     (ssq / n as f64 - (sum / n as f64).powi(2)).sqrt()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::experiments::helpers::{confusion_statistics, diffusion_statistics, key_size, tweak_statistics};
+    use crate::utils::helpers::{CipherType, DecryptionMethod, KeySchedule};
+
+    #[test]
+    fn statistics_33() {
+        let rc = vec![];
+        let diffusion = diffusion_statistics(20, 33, CipherType::MiMCGe(3, &rc, None, None, DecryptionMethod::default(), KeySchedule::default())).unwrap();
+        let confusion = confusion_statistics(20, 33, CipherType::MiMCGe(3, &rc, None, None, DecryptionMethod::default(), KeySchedule::default())).unwrap();
+        for statistics in [diffusion, confusion] {
+            assert_eq!(statistics.expected_mean, 544.5);
+            assert_eq!(statistics.mean, statistics.sum / 20.0);
+            // A bit flip should change about half of the ciphertext bits
+            assert_eq!((statistics.samples, statistics.block_size), (20, 33));
+            assert!((statistics.score() - 5000.0).abs() < 1000.0);
+        }
+    }
+
+    #[test]
+    fn confusion_statistics_key_schedule() {
+        // 34 key bits of two keys
+        let statistics = confusion_statistics(10, 17, CipherType::MiMC(None, DecryptionMethod::default(), KeySchedule::TwoKey)).unwrap();
+        assert_eq!((statistics.input_size, statistics.expected_mean), (34, 289.0));
+        assert!((statistics.score() - 5000.0).abs() < 1000.0);
+        let rc = vec![];
+        let statistics = confusion_statistics(10, 129, CipherType::MiMCGe(3, &rc, None, None, DecryptionMethod::default(), KeySchedule::Independent)).unwrap();
+        assert_eq!(statistics.input_size, 129);
+        assert!((statistics.score() - 5000.0).abs() < 1000.0);
+        assert_eq!(key_size(&CipherType::MiMCGe(3, &rc, Some(15), None, DecryptionMethod::default(), KeySchedule::TwoKey), 33), Ok(66));
+        assert_eq!(key_size(&CipherType::MiMCGe(3, &rc, None, None, DecryptionMethod::default(), KeySchedule::Independent), 511), Ok(511));
+        assert_eq!(key_size(&CipherType::MiMCGe(3, &rc, None, None, DecryptionMethod::default(), KeySchedule::TwoKey), 257), Err(Error::KeyTooLarge(514)));
+    }
+
+    #[test]
+    fn tweak_statistics_33() {
+        let rc = vec![];
+        let statistics = tweak_statistics(20, 33, CipherType::MiMCGe(5, &rc, None, None, DecryptionMethod::default(), KeySchedule::default())).unwrap();
+        assert_eq!((statistics.samples, statistics.block_size, statistics.expected_mean), (20, 33, 544.5));
+        assert!((statistics.score() - 5000.0).abs() < 1000.0);
+        assert_eq!(tweak_statistics(1, 33, CipherType::MiMC(None, DecryptionMethod::default(), KeySchedule::default())).err(), Some(Error::NotTweakable));
+    }
+
+    #[test]
+    fn invalid_cipher() {
+        let rc = vec![];
+        let cipher = |e| CipherType::MiMCGe(e, &rc, None, None, DecryptionMethod::default(), KeySchedule::default());
+        assert!(diffusion_statistics(1, 33, cipher(3)).is_ok());
+        assert_eq!(diffusion_statistics(1, 8, cipher(3)).err(), Some(Error::NotPermutation(3)));
+        assert_eq!(confusion_statistics(1, 512, cipher(3)).err(), Some(Error::UnsupportedBlockSize(512)));
+        assert_eq!(confusion_statistics(1, 33, CipherType::AES).err(), Some(Error::UnsupportedBlockSize(33)));
+        assert!(confusion_statistics(1, 33, CipherType::MiMC(None, DecryptionMethod::default(), KeySchedule::default())).is_ok());
+        assert_eq!(confusion_statistics(1, 32, CipherType::MiMC(None, DecryptionMethod::default(), KeySchedule::default())).err(), Some(Error::EvenBlockSize(32)));
+    }
+}
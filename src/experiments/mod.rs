@@ -1,2 +1,4 @@
+//! Experiments evaluating the ciphers. [`helpers`] return the measured values, [`tests`] print them.
+
+pub mod helpers;
 pub mod tests;
-mod helpers;
\ No newline at end of file
@@ -1,8 +1,16 @@
+use std::fs;
 use std::io;
 use std::io::Write;
+use std::path::Path;
 use std::time::Instant;
-use crate::experiments::helpers::{choose_cipher, confusion, decryption_encryption, diffusion, standard_deviation, to_32_bit};
-use crate::utils::helpers::{CipherType, FieldElement, to_binary, to_decimal};
+use crate::error::{Error, Result};
+use crate::experiments::helpers::{choose_cipher, choose_cipher_with, choose_hash, choose_mac, choose_tweakable_cipher, confusion_statistics, decryption_encryption, diffusion_statistics, to_32_bit, tweak_statistics};
+use crate::fpe::fpe::FormatPreserving;
+use crate::hash::hash::HashType;
+use crate::mac::mac::MacType;
+use crate::modes::modes::{decrypt_message, encrypt_message, Mode};
+use crate::utils::helpers::{CipherType, FieldElement, fixed_round_constants};
+use crate::utils::uint::Uint;
 
 /// # Diffusion test for cipher.
 ///
@@ -14,24 +22,14 @@ use crate::utils::helpers::{CipherType, FieldElement, to_binary, to_decimal};
 ///
 /// # Note
 /// Similar to confusion, changing plaintext bits instead of key being the only difference.
-pub fn test_diffusion(test_size: usize, block_size: u32, cipher_type: CipherType) {
+pub fn test_diffusion(test_size: usize, block_size: u32, cipher_type: CipherType) -> Result<()> {
     let start = Instant::now();
-    let cipher = choose_cipher(&cipher_type, block_size);
-
-    let mut sum = 0.0;
-    let mut ssq = 0.0;
-    for _ in 0..test_size {
-        let r = diffusion(&cipher, block_size) as f64;
-        sum += r;
-        ssq += r.powi(2);
-    }
-    let mean = sum / test_size as f64;
-    let std_deviation = standard_deviation(ssq, sum, test_size);
-    let expected_mean = 0.5 * (block_size.pow(2) as f64);
+    let statistics = diffusion_statistics(test_size, block_size, cipher_type)?;
 
     println!("Diffusion tested with {test_size} plaintexts");
-    println!("Final result {} in {:.2?}", sum / (test_size * block_size.pow(2) as usize) as f64 * 10000.0, start.elapsed());
-    println!("Expected mean: {} mean: {} standard deviation: {}", expected_mean, mean, std_deviation);
+    println!("Final result {} in {:.2?}", statistics.score(), start.elapsed());
+    println!("Expected mean: {} mean: {} standard deviation: {}", statistics.expected_mean, statistics.mean, statistics.standard_deviation);
+    Ok(())
 }
 
 /// # Confusion test for cipher.
@@ -44,24 +42,33 @@ pub fn test_diffusion(test_size: usize, block_size: u32, cipher_type: CipherType
 ///
 /// # Note
 /// Similar to diffusion, changing key bits instead of plaintext being the only difference.
-pub fn test_confusion(test_size: usize, block_size: u32, cipher_type: CipherType) {
+pub fn test_confusion(test_size: usize, block_size: u32, cipher_type: CipherType) -> Result<()> {
     let start = Instant::now();
-    let cipher = choose_cipher(&cipher_type, block_size);
-
-    let mut sum = 0.0;
-    let mut ssq = 0.0;
-    for _ in 0..test_size {
-        let r = confusion(&cipher, block_size) as f64;
-        sum += r;
-        ssq += r.powi(2);
-    }
-    let mean = sum / test_size as f64;
-    let std_deviation = standard_deviation(ssq, sum, test_size);
-    let expected_mean = 0.5 * (block_size.pow(2) as f64);
+    let statistics = confusion_statistics(test_size, block_size, cipher_type)?;
 
     println!("Confusion tested with {test_size} plaintexts");
-    println!("Final result {} in {:.2?}", sum / (test_size * block_size.pow(2) as usize) as f64 * 10000.0, start.elapsed());
-    println!("Calculated r {} expected mean: {} mean: {} standard deviation: {}", sum, expected_mean, mean, std_deviation);
+    println!("Final result {} in {:.2?}", statistics.score(), start.elapsed());
+    println!("Calculated r {} expected mean: {} mean: {} standard deviation: {}", statistics.sum, statistics.expected_mean, statistics.mean, statistics.standard_deviation);
+    Ok(())
+}
+
+/// # Tweak diffusion test for tweakable cipher.
+///
+/// Takes random key, tweak and plaintext, flips one bit of the tweak and checks how many ciphertext bits have flipped.
+/// Repeats this cycle for every bit in tweak.
+///
+/// This cycle, without initializing the cipher, is repeated **test_size** times.
+///
+/// # Note
+/// Similar to confusion, changing tweak bits instead of key being the only difference.
+pub fn test_tweak_diffusion(test_size: usize, block_size: u32, cipher_type: CipherType) -> Result<()> {
+    let start = Instant::now();
+    let statistics = tweak_statistics(test_size, block_size, cipher_type)?;
+
+    println!("Tweak diffusion tested with {test_size} plaintexts");
+    println!("Final result {} in {:.2?}", statistics.score(), start.elapsed());
+    println!("Expected mean: {} mean: {} standard deviation: {}", statistics.expected_mean, statistics.mean, statistics.standard_deviation);
+    Ok(())
 }
 
 /// # Encryption efficiency test for cipher
@@ -72,9 +79,10 @@ pub fn test_confusion(test_size: usize, block_size: u32, cipher_type: CipherType
 /// This cycle is repeated **test_size** times.
 ///
 /// Returns the time it takes to encrypt **test_size** x **sample_size** plaintexts.
-pub fn test_encryption_time(test_size: usize, sample_size: usize, block_size: u32, cipher_type: CipherType) {
-    let time = decryption_encryption(false, test_size, sample_size, block_size, cipher_type);
+pub fn test_encryption_time(test_size: usize, sample_size: usize, block_size: u32, cipher_type: CipherType) -> Result<()> {
+    let time = decryption_encryption(false, test_size, sample_size, block_size, cipher_type)?;
     println!("Total time to encrypt {test_size}x{sample_size} plaintexts {:.6?}", time);
+    Ok(())
 }
 
 /// # Decryption efficiency test for cipher
@@ -85,32 +93,51 @@ pub fn test_encryption_time(test_size: usize, sample_size: usize, block_size: u3
 /// This cycle is repeated **test_size** times.
 ///
 /// Returns the time it takes to decrypt **test_size** x **sample_size** ciphertexts.
-pub fn test_decryption_time(test_size: usize, sample_size: usize, block_size: u32, cipher_type: CipherType) {
-    let time = decryption_encryption(true, test_size, sample_size, block_size, cipher_type);
+pub fn test_decryption_time(test_size: usize, sample_size: usize, block_size: u32, cipher_type: CipherType) -> Result<()> {
+    let time = decryption_encryption(true, test_size, sample_size, block_size, cipher_type)?;
     println!("Total time to decrypt {test_size}x{sample_size} ciphertexts {:.6?}", time);
+    Ok(())
 }
 
 /// # Simple encryption and decryption test
 ///
-/// Check if the cipher correctly decrypts the encrypted message
-pub fn test_cipher(plaintext: FieldElement, block_size: u32, key: FieldElement, cipher_type: CipherType) {
-    let cipher = choose_cipher(&cipher_type, block_size);
-    let start = Instant::now();
-    let ciphertext = cipher.encrypt(&plaintext, &key);
-    let decrypted = cipher.decrypt(&ciphertext, &key);
-    println!("Plaintext:  {} {:?}\nCiphertext: {} {:?}\nDecrypted:  {} {:?}\nTime: {:.2?}", to_decimal(&plaintext), plaintext, to_decimal(&ciphertext), ciphertext, to_decimal(&decrypted), decrypted, start.elapsed());
+/// Check if the cipher correctly decrypts the encrypted message, with the tweak if given (only for tweakable ciphers).
+pub fn test_cipher(plaintext: FieldElement, block_size: u32, key: FieldElement, tweak: Option<FieldElement>, cipher_type: CipherType) -> Result<()> {
+    let start;
+    let (ciphertext, decrypted) = match tweak {
+        Some(tweak) => {
+            let cipher = choose_tweakable_cipher(&cipher_type, block_size)?;
+            start = Instant::now();
+            let ciphertext = cipher.encrypt_tweaked(&plaintext, &key, &tweak);
+            let decrypted = cipher.decrypt_tweaked(&ciphertext, &key, &tweak);
+            println!("Cipher:     {} ({})", cipher.name(), cipher.parameters());
+            println!("Tweak:      {} {:?}", tweak, tweak.to_bits(cipher.tweak_size()));
+            (ciphertext, decrypted)
+        }
+        None => {
+            let cipher = choose_cipher(&cipher_type, block_size)?;
+            start = Instant::now();
+            let ciphertext = cipher.encrypt(&plaintext, &key);
+            let decrypted = cipher.decrypt(&ciphertext, &key);
+            println!("Cipher:     {} ({})", cipher.name(), cipher.parameters());
+            (ciphertext, decrypted)
+        }
+    };
+    println!("Plaintext:  {} {:?}\nCiphertext: {} {:?}\nDecrypted:  {} {:?}\nTime: {:.2?}", plaintext, plaintext.to_bits(block_size), ciphertext, ciphertext.to_bits(block_size), decrypted, decrypted.to_bits(block_size), start.elapsed());
     assert_eq!(decrypted, plaintext);
+    Ok(())
 }
 
 /// Encrypts a sequential list of numbers from 0 up to specified *test_size*.
 ///
 /// These numbers are then printed to standard output as ASCII bit array (1s and 0s).
 /// Each line represents a different number.
-pub fn encrypt_seq(test_size: usize, block_size: u32, key: FieldElement, cipher_type: CipherType) {
-    let cipher = choose_cipher(&cipher_type, block_size);
+pub fn encrypt_seq(test_size: usize, block_size: u32, key: FieldElement, cipher_type: CipherType) -> Result<()> {
+    let cipher = choose_cipher(&cipher_type, block_size)?;
     for i in 0..test_size {
-        println!("{}", cipher.encrypt(&to_binary(i as u128, block_size), &key).into_iter().map(|x| x.to_string()).collect::<Vec<String>>().join(""));
+        println!("{}", cipher.encrypt(&FieldElement::new(i as u128, block_size), &key).to_bits(block_size).into_iter().map(|x| x.to_string()).collect::<Vec<String>>().join(""));
     }
+    Ok(())
 }
 
 /// Encrypts a sequential list of numbers from 0 up to  2^32 < 4531145293 < 2^33.
@@ -118,12 +145,100 @@ pub fn encrypt_seq(test_size: usize, block_size: u32, key: FieldElement, cipher_
 /// This outputs encrypted sequential numbers to the standard output in 32 bits. If the cipher encrypts
 /// numbers larger than 32 bits, then the low end is discarded. This function will never end and when
 /// the sequence reaches the end, the cycle repeats.
-pub fn encrypt_seq_stream(block_size: u32, key: FieldElement, cipher_type: CipherType) {
-    let cipher = choose_cipher(&cipher_type, block_size);
+pub fn encrypt_seq_stream(block_size: u32, key: FieldElement, cipher_type: CipherType) -> Result<()> {
+    let cipher = choose_cipher(&cipher_type, block_size)?;
 
     let mut i = 0u128;
     loop {
-        io::stdout().write_all(&to_32_bit(cipher.encrypt(&to_binary(i, 33), &key))).unwrap();
+        io::stdout().write_all(&to_32_bit(cipher.encrypt(&FieldElement::new(i, 33), &key), block_size)).unwrap();
         i = (i + 1) % 4531145293;
     }
 }
+
+/// # Hash of the message
+///
+/// Prints the hex digest followed by the input name as sha256sum does. Parameters of the hash and the time it took are
+/// printed to the standard error, so that the digest can be piped.
+pub fn hash_message(message: &[u8], name: &str, block_size: u32, cipher_type: CipherType, hash_type: HashType) -> Result<()> {
+    let hash = choose_hash(&hash_type, &cipher_type, block_size)?;
+    let start = Instant::now();
+    let digest = hash.hash(message);
+    let elapsed = start.elapsed();
+    println!("{}  {name}", digest.iter().map(|byte| format!("{byte:02x}")).collect::<String>());
+    eprintln!("{} ({}) hashed {} bytes in {:.2?}", hash.name(), hash.parameters(), message.len(), elapsed);
+    Ok(())
+}
+
+/// # Message authentication
+///
+/// Prints the tag of the message in hex, or checks the given hex `tag` and prints OK. Round constants are fixed, so that
+/// the tag can be verified by another run with the same key and cipher parameters. Parameters and time are printed to
+/// the standard error.
+///
+/// Returns an error if the tag does not authenticate the message.
+pub fn authenticate(message: &[u8], name: &str, block_size: u32, key: FieldElement, cipher_type: CipherType, mac_type: MacType, tag: Option<&str>) -> Result<()> {
+    let mac = choose_mac(&mac_type, &cipher_type, block_size)?;
+    let key = FieldElement::from_uint(key.value(), mac.key_size());
+    let start = Instant::now();
+    match tag {
+        Some(tag) => {
+            if !mac.verify(message, &key, &from_hex(tag)?)? {
+                return Err(Error::InvalidTag);
+            }
+            println!("{name}: OK");
+        }
+        None => println!("{}  {name}", mac.mac(message, &key)?.iter().map(|byte| format!("{byte:02x}")).collect::<String>()),
+    }
+    eprintln!("{} ({}) authenticated {} bytes in {:.2?}", mac.name(), mac.parameters(), message.len(), start.elapsed());
+    Ok(())
+}
+
+/// Bytes of the hex string, e.g. "0a1b".
+fn from_hex(value: &str) -> Result<Vec<u8>> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    if !digits.len().is_multiple_of(2) || !digits.is_ascii() {
+        return Err(Error::Parse(format!("invalid hex bytes {value}")));
+    }
+    (0..digits.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| Error::Parse(format!("invalid hex bytes {value}"))))
+        .collect()
+}
+
+/// # Encryption of the whole message
+///
+/// Encrypts (or decrypts) the message in the mode of operation and writes the result to the `output` file or the
+/// standard output. The initialization vector is stored before the ciphertext. Round constants are fixed, so that the
+/// message can be decrypted by another run with the same key and cipher parameters. Time is printed to the standard
+/// error.
+pub fn encrypt_data(decrypt: bool, data: &[u8], output: Option<&Path>, block_size: u32, key: FieldElement, cipher_type: CipherType, mode: Mode) -> Result<()> {
+    let cipher = choose_cipher_with(&cipher_type, block_size, fixed_round_constants)?;
+    let start = Instant::now();
+    let result = if decrypt {
+        decrypt_message(cipher.as_ref(), mode, &key, data)?
+    } else {
+        encrypt_message(cipher.as_ref(), mode, &key, data)
+    };
+    let elapsed = start.elapsed();
+    match output {
+        Some(path) => fs::write(path, &result)?,
+        None => io::stdout().write_all(&result)?,
+    }
+    eprintln!("{} {} bytes with {} ({}) in {mode:?} mode in {:.2?}", if decrypt { "Decrypted" } else { "Encrypted" }, data.len(),
+              cipher.name(), cipher.parameters(), elapsed);
+    Ok(())
+}
+
+/// # Format-preserving encryption of a number
+///
+/// Encrypts (or decrypts) the value of [0, **domain**) by MiMCGe over the smallest field of the domain and prints the
+/// result to the standard output. The cipher is printed to the standard error.
+pub fn format_preserving(decrypt: bool, value: Uint, domain: u128, key: FieldElement, exponent: u128, round_reduction: Option<usize>) -> Result<()> {
+    let fpe = FormatPreserving::with_exponent(exponent, domain, round_reduction)?;
+    if value.bits() > u128::BITS {
+        return Err(Error::OutOfDomain { value, domain });
+    }
+    let result = if decrypt { fpe.decrypt(value.low_u128(), &key)? } else { fpe.encrypt(value.low_u128(), &key)? };
+    eprintln!("Cipher:     {} ({}), domain [0, {domain})", fpe.cipher().name(), fpe.cipher().parameters());
+    println!("{result}");
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Format-preserving encryption of integers in the range [0, N) by cycle-walking: the value is encrypted again until
+//! the ciphertext falls into the range. As the cipher is a permutation of the n-bit values, so is the restriction to
+//! the range and the decryption walks the same cycle backwards. With the smallest n such that 2^n >= N, every value
+//! takes less than 2^n / N encryptions on average.
+
+use crate::error::{Error, Result};
+use crate::mimc_general::mimc_general::{is_permutation, MiMCGe};
+use crate::utils::helpers::{Cipher, FieldElement};
+use crate::utils::uint::Uint;
+
+/// Largest block size of the cipher, the values are u128.
+pub const MAX_FPE_BLOCK_SIZE: u32 = 128;
+
+pub struct FormatPreserving {
+    cipher: Box<dyn Cipher>,
+    domain: u128,
+}
+
+impl FormatPreserving {
+    /// Encryption of [0, `domain`) by the cipher.
+    ///
+    /// Returns an error if the domain is empty or the cipher blocks are too small for it (or larger than
+    /// [`MAX_FPE_BLOCK_SIZE`]).
+    pub fn new(cipher: Box<dyn Cipher>, domain: u128) -> Result<Self> {
+        if domain == 0 {
+            return Err(Error::EmptyDomain);
+        }
+        let block_size = cipher.block_size();
+        if block_size > MAX_FPE_BLOCK_SIZE || (block_size < MAX_FPE_BLOCK_SIZE && domain > 1 << block_size) {
+            return Err(Error::DomainTooLarge { domain, block_size });
+        }
+        Ok(FormatPreserving { cipher, domain })
+    }
+
+    /// Encryption of [0, `domain`) by MiMCGe with x^`exponent` over the field of [`FormatPreserving::block_size`]. Round
+    /// constants are fixed, so that the values can be decrypted by another instance with the same parameters.
+    pub fn with_exponent(exponent: u128, domain: u128, round_reduction: Option<usize>) -> Result<Self> {
+        let block_size = FormatPreserving::block_size(exponent, domain)?;
+        FormatPreserving::new(Box::new(MiMCGe::with_fixed_round_constants(exponent, block_size, round_reduction)?), domain)
+    }
+
+    /// Smallest n such that 2^n >= `domain` and x^`exponent` is a permutation of GF(2^n), e.g. odd n for x^3.
+    ///
+    /// Returns an error if the domain is empty, the exponent is smaller than 2 or there is no such n up to
+    /// [`MAX_FPE_BLOCK_SIZE`].
+    pub fn block_size(exponent: u128, domain: u128) -> Result<u32> {
+        if domain == 0 {
+            return Err(Error::EmptyDomain);
+        }
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        let bits = (u128::BITS - (domain - 1).leading_zeros()).max(2);
+        (bits..=MAX_FPE_BLOCK_SIZE)
+            .find(|&n| is_permutation(exponent, Uint::mask(n)))
+            .ok_or(Error::DomainTooLarge { domain, block_size: MAX_FPE_BLOCK_SIZE })
+    }
+
+    pub fn domain(&self) -> u128 {
+        self.domain
+    }
+
+    pub fn cipher(&self) -> &dyn Cipher {
+        self.cipher.as_ref()
+    }
+
+    fn walk(&self, value: u128, key: &FieldElement, step: impl Fn(&FieldElement, &FieldElement) -> FieldElement) -> Result<u128> {
+        if value >= self.domain {
+            return Err(Error::OutOfDomain { value: Uint::from(value), domain: self.domain });
+        }
+        let block_size = self.cipher.block_size();
+        let key = FieldElement::from_uint(key.value(), self.cipher.key_size());
+        let mut block = FieldElement::new(value, block_size);
+        loop {
+            block = step(&block, &key);
+            let value = block.value().low_u128();
+            if value < self.domain {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Encrypts the value of [0, N) to another value of [0, N).
+    ///
+    /// Returns an error if the value is not in the domain.
+    pub fn encrypt(&self, value: u128, key: &FieldElement) -> Result<u128> {
+        self.walk(value, key, |block, key| self.cipher.encrypt(block, key))
+    }
+
+    /// Decrypts the value of [0, N) encrypted by [`FormatPreserving::encrypt`].
+    pub fn decrypt(&self, value: u128, key: &FieldElement) -> Result<u128> {
+        self.walk(value, key, |block, key| self.cipher.decrypt(block, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::fpe::fpe::FormatPreserving;
+    use crate::mimc_general::mimc_general::MiMCGe;
+    use crate::utils::helpers::{FieldElement, generate_random_bits};
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn block_size() {
+        assert_eq!(FormatPreserving::block_size(3, 1000), Ok(11)); // 2^10 >= 1000, x^3 needs odd n
+        assert_eq!(FormatPreserving::block_size(5, 1000), Ok(10));
+        assert_eq!(FormatPreserving::block_size(3, 1024), Ok(11));
+        assert_eq!(FormatPreserving::block_size(3, 1025), Ok(11));
+        assert_eq!(FormatPreserving::block_size(3, 1), Ok(3));
+        assert_eq!(FormatPreserving::block_size(7, u128::MAX), Ok(128));
+        assert_eq!(FormatPreserving::block_size(3, u128::MAX), Err(Error::DomainTooLarge { domain: u128::MAX, block_size: 128 }));
+    }
+
+    #[test]
+    fn permutation_of_domain() {
+        let fpe = FormatPreserving::with_exponent(3, 1000, None).unwrap();
+        let key = generate_random_bits(11);
+        let mut ciphertexts: Vec<u128> = (0..1000).map(|value| fpe.encrypt(value, &key).unwrap()).collect();
+        for (value, &ciphertext) in ciphertexts.iter().enumerate() {
+            assert_eq!(fpe.decrypt(ciphertext, &key), Ok(value as u128));
+        }
+        ciphertexts.sort();
+        assert_eq!(ciphertexts, (0..1000).collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn reproducible() {
+        let key = FieldElement::new(0x1234, 17);
+        let first = FormatPreserving::with_exponent(5, 100_000, None).unwrap();
+        let second = FormatPreserving::with_exponent(5, 100_000, None).unwrap();
+        assert_eq!(first.encrypt(31_337, &key), second.encrypt(31_337, &key));
+    }
+
+    #[test]
+    fn large_domain() {
+        let domain = 10u128.pow(38);
+        let fpe = FormatPreserving::with_exponent(7, domain, None).unwrap();
+        let key = generate_random_bits(128);
+        let value = domain - 1;
+        let ciphertext = fpe.encrypt(value, &key).unwrap();
+        assert!(ciphertext < domain);
+        assert_eq!(fpe.decrypt(ciphertext, &key), Ok(value));
+    }
+
+    #[test]
+    fn key_high_bits_ignored() {
+        let fpe = FormatPreserving::with_exponent(3, 50, None).unwrap();
+        let key = generate_random_bits(7);
+        let long_key = FieldElement::from_uint(key.value() ^ (Uint::ONE << 40), 64);
+        assert_eq!(fpe.encrypt(7, &key), fpe.encrypt(7, &long_key));
+    }
+
+    #[test]
+    fn invalid_domain() {
+        assert_eq!(FormatPreserving::with_exponent(3, 0, None).err(), Some(Error::EmptyDomain));
+        let fpe = FormatPreserving::with_exponent(3, 10, None).unwrap();
+        assert_eq!(fpe.encrypt(10, &FieldElement::ZERO), Err(Error::OutOfDomain { value: Uint::from(10u128), domain: 10 }));
+        let cipher = MiMCGe::new(3, 5, None).unwrap();
+        assert_eq!(FormatPreserving::new(Box::new(cipher), 33).err(), Some(Error::DomainTooLarge { domain: 33, block_size: 5 }));
+    }
+}
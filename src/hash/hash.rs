@@ -0,0 +1,114 @@
+//! Hash functions built from the ciphers, [`Sponge`](crate::hash::sponge::Sponge) with the cipher as a permutation and
+//! [`MiyaguchiPreneel`](crate::hash::miyaguchi_preneel::MiyaguchiPreneel) compression function. Messages are bytes,
+//! which are absorbed as bit arrays with the most significant bit of the first byte first.
+
+/// Hash function of byte messages.
+pub trait HashFunction {
+    /// Digest of the message, packed into bytes from the most significant bit. If the digest size is not a multiple of
+    /// 8, the lowest bits of the last byte are zero.
+    fn hash(&self, message: &[u8]) -> Vec<u8>;
+
+    /// Digest size in bits.
+    fn digest_size(&self) -> u32;
+
+    fn name(&self) -> &'static str;
+
+    /// Human readable parameters, e.g. "rate 32, capacity 32, 64-bit digest, multi-rate padding".
+    fn parameters(&self) -> String;
+}
+
+/// Padding of the message bits to a multiple of the block size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Padding {
+    /// Zeros up to the block size, messages which differ only in trailing zeros collide. Empty message is one zero
+    /// block.
+    Zeros,
+    /// Single 1 followed by zeros (10*), always adds at least one bit.
+    OneZeros,
+    /// Multi-rate padding of Keccak (10*1), 1 followed by zeros and 1, always adds at least two bits.
+    #[default]
+    MultiRate,
+}
+
+impl Padding {
+    /// Pads the bits to a non-zero multiple of the `block_size`.
+    pub fn pad(self, bits: &[u8], block_size: u32) -> Vec<u8> {
+        let block_size = block_size as usize;
+        let mut result = bits.to_vec();
+        match self {
+            Padding::Zeros => {}
+            Padding::OneZeros => result.push(1),
+            Padding::MultiRate => {
+                result.push(1);
+                result.resize(result.len().div_ceil(block_size).max(1) * block_size, 0);
+                // The final 1 does not fit into the block with the first one
+                if result[result.len() - 1] == 1 {
+                    result.resize(result.len() + block_size, 0);
+                }
+                *result.last_mut().unwrap() = 1;
+            }
+        }
+        result.resize(result.len().div_ceil(block_size).max(1) * block_size, 0);
+        result
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Padding::Zeros => "zero",
+            Padding::OneZeros => "10*",
+            Padding::MultiRate => "multi-rate",
+        }
+    }
+}
+
+/// Hash construction chosen for the `hash` command. Sponge takes rate, capacity and digest size in bits, all of them
+/// optional (rate is half of the block if neither is given, digest is one block).
+pub enum HashType {
+    Sponge(Option<u32>, Option<u32>, Option<u32>, Padding),
+    MiyaguchiPreneel(Padding),
+}
+
+/// Bits of the bytes, the most significant bit first.
+pub fn bytes_to_bits(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1)).collect()
+}
+
+/// Packs the bits into bytes (same ordering as in [`bytes_to_bits`]), the last byte is filled with zeros.
+pub fn bits_to_bytes(bits: &[u8]) -> Vec<u8> {
+    bits.chunks(8).map(|chunk| chunk.iter().enumerate().fold(0, |byte, (i, &bit)| byte | (bit << (7 - i)))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::hash::{bits_to_bytes, bytes_to_bits, Padding};
+
+    #[test]
+    fn bits_and_bytes() {
+        assert_eq!(bytes_to_bits(&[0xa5, 0x01]), vec![1, 0, 1, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(bits_to_bytes(&bytes_to_bits(&[0xa5, 0x01])), vec![0xa5, 0x01]);
+        assert_eq!(bits_to_bytes(&[1, 1, 0, 1]), vec![0xd0]);
+    }
+
+    #[test]
+    fn zeros() {
+        assert_eq!(Padding::Zeros.pad(&[], 4), vec![0, 0, 0, 0]);
+        assert_eq!(Padding::Zeros.pad(&[1, 1], 4), vec![1, 1, 0, 0]);
+        assert_eq!(Padding::Zeros.pad(&[1, 1, 1, 1], 4), vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn one_zeros() {
+        assert_eq!(Padding::OneZeros.pad(&[], 4), vec![1, 0, 0, 0]);
+        assert_eq!(Padding::OneZeros.pad(&[0, 1, 1], 4), vec![0, 1, 1, 1]);
+        assert_eq!(Padding::OneZeros.pad(&[1, 1, 1, 1], 4), vec![1, 1, 1, 1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn multi_rate() {
+        assert_eq!(Padding::MultiRate.pad(&[], 4), vec![1, 0, 0, 1]);
+        assert_eq!(Padding::MultiRate.pad(&[0, 0], 4), vec![0, 0, 1, 1]);
+        assert_eq!(Padding::MultiRate.pad(&[0, 0, 0], 4), vec![0, 0, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(Padding::MultiRate.pad(&[0, 0, 0, 0], 4), vec![0, 0, 0, 0, 1, 0, 0, 1]);
+        assert_eq!(Padding::MultiRate.pad(&[], 1), vec![1, 1]);
+    }
+}
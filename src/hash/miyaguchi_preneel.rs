@@ -0,0 +1,103 @@
+use crate::error::{Error, Result};
+use crate::hash::hash::{bits_to_bytes, bytes_to_bits, HashFunction, Padding};
+use crate::utils::helpers::{Cipher, FieldElement};
+use crate::utils::uint::Uint;
+
+/// Miyaguchi-Preneel hash over a block cipher, usually [`MiMCGe`](crate::mimc_general::mimc_general::MiMCGe). Every
+/// message block m_i updates the chaining value H_i = E_{H_{i-1}}(m_i) + m_i + H_{i-1}, starting from H_0 = 0. The
+/// padded message is followed by its 64-bit length in bits over ceil(64 / n) blocks (Merkle-Damgard strengthening), so
+/// the length does not wrap for small blocks, and the digest is the last chaining value.
+pub struct MiyaguchiPreneel {
+    cipher: Box<dyn Cipher>,
+    padding: Padding,
+}
+
+impl MiyaguchiPreneel {
+    /// Returns an error if the key size of the cipher differs from its block size.
+    pub fn new(cipher: Box<dyn Cipher>, padding: Padding) -> Result<Self> {
+        if cipher.key_size() != cipher.block_size() {
+            return Err(Error::KeySizeMismatch { key_size: cipher.key_size(), block_size: cipher.block_size() });
+        }
+        Ok(MiyaguchiPreneel { cipher, padding })
+    }
+
+    /// Digest of the message given as bit array, see [`HashFunction::hash`].
+    pub fn hash_bits(&self, message: &[u8]) -> Vec<u8> {
+        let block_size = self.cipher.block_size();
+        let mut blocks = self.padding.pad(message, block_size);
+        let length_size = u64::BITS.div_ceil(block_size) * block_size;
+        blocks.extend(FieldElement::from_uint(Uint::from(message.len() as u128), length_size).to_bits(length_size));
+
+        let mut chaining_value = FieldElement::ZERO;
+        for block in blocks.chunks(block_size as usize) {
+            let block = FieldElement::from_bits(block);
+            chaining_value = self.cipher.encrypt(&block, &chaining_value) ^ block ^ chaining_value;
+        }
+        chaining_value.to_bits(block_size)
+    }
+}
+
+impl HashFunction for MiyaguchiPreneel {
+    fn hash(&self, message: &[u8]) -> Vec<u8> {
+        bits_to_bytes(&self.hash_bits(&bytes_to_bits(message)))
+    }
+
+    fn digest_size(&self) -> u32 {
+        self.cipher.block_size()
+    }
+
+    fn name(&self) -> &'static str {
+        "MiyaguchiPreneel"
+    }
+
+    fn parameters(&self) -> String {
+        format!("{}-bit digest, {} padding, {} ({})", self.cipher.block_size(), self.padding.name(), self.cipher.name(), self.cipher.parameters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::hash::hash::{HashFunction, Padding};
+    use crate::hash::miyaguchi_preneel::MiyaguchiPreneel;
+    use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
+    use crate::mimc_general::mimc_general::MiMCGe;
+    use crate::utils::helpers::{Cipher, FieldElement};
+
+    #[test]
+    fn length_blocks() {
+        // 17-bit message fills the only block with zero padding, its length takes 4 blocks (68 bits)
+        let (cipher, message) = (MiMCGe::with_fixed_round_constants(5, 17, None).unwrap(), FieldElement::new(0x1abcd, 17));
+        let mut expected = cipher.encrypt(&message, &FieldElement::ZERO) ^ message;
+        for length in [0, 0, 0, 17] {
+            let length = FieldElement::new(length, 17);
+            expected = cipher.encrypt(&length, &expected) ^ length ^ expected;
+        }
+        let hash = MiyaguchiPreneel::new(Box::new(cipher), Padding::Zeros).unwrap();
+        assert_eq!(hash.hash_bits(&message.to_bits(17)), expected.to_bits(17));
+    }
+
+    #[test]
+    fn length_does_not_wrap() {
+        // Lengths of 1 and 1 + 2^9 bits are the same modulo 2^9, thus a single 9-bit length block would wrap
+        let hash = MiyaguchiPreneel::new(Box::new(MiMCGe::with_fixed_round_constants(5, 9, None).unwrap()), Padding::Zeros).unwrap();
+        let (short, long) = (vec![1], [vec![1], vec![0; 1 << 9]].concat());
+        assert_ne!(hash.hash_bits(&short), hash.hash_bits(&long));
+        assert_eq!(hash.hash_bits(&long).len(), 9);
+    }
+
+    #[test]
+    fn length_block() {
+        // Zero padding alone would give the same blocks for both messages
+        let hash = MiyaguchiPreneel::new(Box::new(MiMCGe::with_fixed_round_constants(5, 33, None).unwrap()), Padding::Zeros).unwrap();
+        assert_ne!(hash.hash(&[1, 0]), hash.hash(&[1, 0, 0]));
+        assert_eq!(hash.hash(b"abc"), MiyaguchiPreneel::new(Box::new(MiMCGe::with_fixed_round_constants(5, 33, None).unwrap()), Padding::Zeros).unwrap().hash(b"abc"));
+        assert_eq!((hash.digest_size(), hash.hash(b"abc").len()), (33, 5));
+    }
+
+    #[test]
+    fn key_size() {
+        let cipher = MiMCGeFeistel::new(3, 34, None).unwrap();
+        assert_eq!(MiyaguchiPreneel::new(Box::new(cipher), Padding::MultiRate).err(), Some(Error::KeySizeMismatch { key_size: 17, block_size: 34 }));
+    }
+}
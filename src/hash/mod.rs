@@ -0,0 +1,3 @@
+pub mod hash;
+pub mod miyaguchi_preneel;
+pub mod sponge;
@@ -0,0 +1,135 @@
+use crate::error::{Error, Result};
+use crate::hash::hash::{bits_to_bytes, bytes_to_bits, HashFunction, Padding};
+use crate::utils::helpers::{Cipher, FieldElement};
+
+/// Sponge over a cipher with zero key as the permutation, e.g. MiMCSponge with
+/// [`MiMCGeFeistel`](crate::mimc_general::mimc_feistel::MiMCGeFeistel). The state of b bits is split into the outer
+/// `rate` bits (the most significant ones), which absorb the message and give the digest, and the inner `capacity`
+/// bits.
+///
+/// The permutation must work over GF(2^b), as the message is added by XOR.
+pub struct Sponge {
+    permutation: Box<dyn Cipher>,
+    rate: u32,
+    capacity: u32,
+    padding: Padding,
+    digest_size: u32,
+}
+
+impl Sponge {
+    /// Returns an error if the rate or capacity is zero or they do not add up to the block size of the permutation.
+    pub fn new(permutation: Box<dyn Cipher>, rate: u32, capacity: u32, padding: Padding, digest_size: u32) -> Result<Self> {
+        let block_size = permutation.block_size();
+        if rate == 0 || capacity == 0 || rate.checked_add(capacity) != Some(block_size) {
+            return Err(Error::InvalidRate { rate, capacity, block_size });
+        }
+        Ok(Sponge { permutation, rate, capacity, padding, digest_size })
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    fn permute(&self, state: &FieldElement) -> FieldElement {
+        self.permutation.encrypt(state, &FieldElement::ZERO)
+    }
+
+    /// Digest of the message given as bit array (e.g. when its length is not a multiple of 8), see
+    /// [`HashFunction::hash`].
+    pub fn hash_bits(&self, message: &[u8]) -> Vec<u8> {
+        let block_size = self.permutation.block_size();
+        let mut state = FieldElement::ZERO;
+        for block in self.padding.pad(message, self.rate).chunks(self.rate as usize) {
+            let block = FieldElement::from_uint(FieldElement::from_bits(block).value() << self.capacity, block_size);
+            state = self.permute(&(state ^ block));
+        }
+
+        let mut digest = Vec::with_capacity(self.digest_size as usize);
+        loop {
+            digest.extend(FieldElement::from_uint(state.value() >> self.capacity, self.rate).to_bits(self.rate));
+            if digest.len() >= self.digest_size as usize {
+                break;
+            }
+            state = self.permute(&state);
+        }
+        digest.truncate(self.digest_size as usize);
+        digest
+    }
+}
+
+impl HashFunction for Sponge {
+    fn hash(&self, message: &[u8]) -> Vec<u8> {
+        bits_to_bytes(&self.hash_bits(&bytes_to_bits(message)))
+    }
+
+    fn digest_size(&self) -> u32 {
+        self.digest_size
+    }
+
+    fn name(&self) -> &'static str {
+        "Sponge"
+    }
+
+    fn parameters(&self) -> String {
+        format!("rate {}, capacity {}, {}-bit digest, {} padding, {} ({})", self.rate, self.capacity, self.digest_size,
+                self.padding.name(), self.permutation.name(), self.permutation.parameters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::hash::hash::{HashFunction, Padding};
+    use crate::hash::sponge::Sponge;
+    use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
+    use crate::mimc_general::mimc_general::MiMCGe;
+    use crate::utils::helpers::{Cipher, FieldElement};
+
+    fn feistel_sponge(rate: u32, digest_size: u32) -> Sponge {
+        let permutation = MiMCGeFeistel::with_fixed_round_constants(5, 64, None).unwrap();
+        Sponge::new(Box::new(permutation), rate, 64 - rate, Padding::MultiRate, digest_size).unwrap()
+    }
+
+    #[test]
+    fn single_block() {
+        // 16-bit message with 10*1 padding fills exactly one block of the 18-bit rate
+        let permutation = MiMCGe::with_fixed_round_constants(3, 25, None).unwrap();
+        let expected = permutation.encrypt(&FieldElement::new(((0xabcd << 2) | 3) << 7, 25), &FieldElement::ZERO);
+        let sponge = Sponge::new(Box::new(permutation), 18, 7, Padding::MultiRate, 18).unwrap();
+        assert_eq!(sponge.hash_bits(&FieldElement::new(0xabcd, 16).to_bits(16)), FieldElement::new((expected.value() >> 7).low_u128(), 18).to_bits(18));
+    }
+
+    #[test]
+    fn deterministic() {
+        let message = b"MiMC sponge with a general exponent";
+        assert_eq!(feistel_sponge(32, 64).hash(message), feistel_sponge(32, 64).hash(message));
+        assert_ne!(feistel_sponge(32, 64).hash(message), feistel_sponge(32, 64).hash(b"MiMC sponge with a general exponenT"));
+        assert_ne!(feistel_sponge(32, 64).hash(b""), feistel_sponge(32, 64).hash(&[0]));
+    }
+
+    #[test]
+    fn squeeze() {
+        // Digest longer than the rate needs more permutations, the first block is the same
+        let long = feistel_sponge(16, 100).hash(b"abc");
+        assert_eq!(long.len(), 13);
+        assert_eq!(long[..2], feistel_sponge(16, 16).hash(b"abc")[..]);
+        assert_eq!(long[12] & 0x0f, 0);
+    }
+
+    #[test]
+    fn invalid_rate() {
+        let permutation = MiMCGe::new(3, 17, None).unwrap();
+        assert_eq!(Sponge::new(Box::new(permutation), 10, 10, Padding::MultiRate, 17).err(),
+                   Some(Error::InvalidRate { rate: 10, capacity: 10, block_size: 17 }));
+        let permutation = MiMCGe::new(3, 17, None).unwrap();
+        assert_eq!(Sponge::new(Box::new(permutation), 17, 0, Padding::MultiRate, 17).err(),
+                   Some(Error::InvalidRate { rate: 17, capacity: 0, block_size: 17 }));
+        let permutation = MiMCGe::new(3, 17, None).unwrap();
+        assert_eq!(Sponge::new(Box::new(permutation), u32::MAX, 18, Padding::MultiRate, 17).err(),
+                   Some(Error::InvalidRate { rate: u32::MAX, capacity: 18, block_size: 17 }));
+    }
+}
@@ -0,0 +1,63 @@
+#![allow(clippy::module_inception, clippy::upper_case_acronyms)]
+//! [MiMC](https://eprint.iacr.org/2016/492) cipher with a general power map *x^e* (MiMCGe) over binary fields GF(2^n)
+//! for n from 2 to 511 and over prime fields GF(p) for primes up to 256 bits, together with the experiments used to
+//! evaluate it (diffusion, confusion and encryption/decryption time).
+//!
+//! The most used types are re-exported from the crate root:
+//!
+//! - field elements ([`FieldElement`], [`Uint`]) and fields ([`BinaryField`], [`PrimeField`], [`FieldType`]),
+//! - the [`Cipher`] and [`TweakableCipher`] traits and their implementations ([`MiMC`], [`MiMCGe`], [`MiMCGePrime`], [`MiMCGeFeistel`], [`GMiMC`], [`AES`]),
+//! - [`KeySchedule`] deriving the round keys of [`MiMC`] and [`MiMCGe`],
+//! - [`CipherType`], which selects the cipher for the functions in [`experiments`],
+//! - hash functions ([`Sponge`], [`MiyaguchiPreneel`]) over the ciphers in [`hash`],
+//! - message authentication codes ([`CbcMac`], [`Cmac`], [`SpongeMac`]) over the ciphers in [`mac`],
+//! - modes of operation ([`Mode`]) encrypting messages of any length in [`modes`],
+//! - format-preserving encryption of integer ranges ([`FormatPreserving`]) in [`fpe`],
+//! - [`Error`] returned by the constructors for invalid parameters.
+//!
+//! Field arithmetic functions (multiplication, exponentiation, inversion, trace, ...) are in [`utils::helpers`].
+//!
+//! # Example
+//!
+//! ```
+//! use mimcge::{Cipher, MiMCGe, generate_random_bits};
+//!
+//! let cipher = MiMCGe::new(5, 33, None)?;
+//! let (plaintext, key) = (generate_random_bits(33), generate_random_bits(33));
+//! let ciphertext = cipher.encrypt(&plaintext, &key);
+//! assert_eq!(cipher.decrypt(&ciphertext, &key), plaintext);
+//! # Ok::<(), mimcge::Error>(())
+//! ```
+
+pub mod aes;
+pub mod error;
+pub mod experiments;
+pub mod fpe;
+pub mod hash;
+pub mod mac;
+pub mod mimc;
+pub mod mimc_general;
+pub mod modes;
+pub mod utils;
+#[cfg(test)]
+mod tests;
+
+pub use crate::aes::aes::AES;
+pub use crate::error::{Error, Result};
+pub use crate::fpe::fpe::FormatPreserving;
+pub use crate::hash::hash::{HashFunction, HashType, Padding};
+pub use crate::hash::miyaguchi_preneel::MiyaguchiPreneel;
+pub use crate::hash::sponge::Sponge;
+pub use crate::mac::cbc_mac::CbcMac;
+pub use crate::mac::cmac::Cmac;
+pub use crate::mac::mac::{Mac, MacType};
+pub use crate::mac::sponge_mac::SpongeMac;
+pub use crate::mimc::mimc::MiMC;
+pub use crate::mimc_general::gmimc::{GMiMC, GMiMCVariant};
+pub use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
+pub use crate::mimc_general::mimc_general::MiMCGe;
+pub use crate::mimc_general::mimc_prime::MiMCGePrime;
+pub use crate::modes::modes::Mode;
+pub use crate::utils::helpers::{Backend, BinaryField, Cipher, CipherType, DecryptionMethod, FieldElement, FieldType, fixed_round_constants, generate_random_bits, generate_round_constants, KeySchedule, RoundKeys, TweakableCipher};
+pub use crate::utils::prime_field::PrimeField;
+pub use crate::utils::uint::Uint;
@@ -0,0 +1,98 @@
+use crate::error::{Error, Result};
+use crate::hash::hash::{bits_to_bytes, bytes_to_bits, Padding};
+use crate::mac::mac::Mac;
+use crate::utils::helpers::{Cipher, FieldElement};
+use crate::utils::uint::Uint;
+
+/// CBC-MAC with length prepending. The first block is the message length in bits, followed by the message padded with
+/// zeros, and the tag is the last block of the CBC encryption with zero IV. As the length is known before the message,
+/// no valid tag is a prefix of another message, unlike the plain CBC-MAC.
+pub struct CbcMac {
+    cipher: Box<dyn Cipher>,
+}
+
+impl CbcMac {
+    pub fn new(cipher: Box<dyn Cipher>) -> Self {
+        CbcMac { cipher }
+    }
+
+    /// Tag of the message given as bit array, see [`Mac::mac`].
+    ///
+    /// Returns an error if the message length does not fit into one block.
+    pub fn mac_bits(&self, message: &[u8], key: &FieldElement) -> Result<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if block_size < usize::BITS && message.len() >> block_size != 0 {
+            return Err(Error::MessageTooLong { length: message.len(), block_size });
+        }
+        let mut blocks = FieldElement::from_uint(Uint::from(message.len() as u128), block_size).to_bits(block_size);
+        blocks.extend(Padding::Zeros.pad(message, block_size));
+
+        let mut state = FieldElement::ZERO;
+        for block in blocks.chunks(block_size as usize) {
+            state = self.cipher.encrypt(&(state ^ FieldElement::from_bits(block)), key);
+        }
+        Ok(state.to_bits(block_size))
+    }
+}
+
+impl Mac for CbcMac {
+    fn mac(&self, message: &[u8], key: &FieldElement) -> Result<Vec<u8>> {
+        Ok(bits_to_bytes(&self.mac_bits(&bytes_to_bits(message), key)?))
+    }
+
+    fn tag_size(&self) -> u32 {
+        self.cipher.block_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.cipher.key_size()
+    }
+
+    fn name(&self) -> &'static str {
+        "CBC-MAC"
+    }
+
+    fn parameters(&self) -> String {
+        format!("{}-bit tag, {} ({})", self.cipher.block_size(), self.cipher.name(), self.cipher.parameters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::mac::cbc_mac::CbcMac;
+    use crate::mac::mac::Mac;
+    use crate::mimc_general::mimc_general::MiMCGe;
+    use crate::utils::helpers::{Cipher, FieldElement, generate_random_bits};
+
+    #[test]
+    fn two_blocks() {
+        // Length block 17 and the message, which fills the second block
+        let (cipher, message, key) = (MiMCGe::with_fixed_round_constants(5, 17, None).unwrap(), FieldElement::new(0x1abcd, 17), FieldElement::new(0x2345, 17));
+        let first = cipher.encrypt(&FieldElement::new(17, 17), &key);
+        let expected = cipher.encrypt(&(first ^ message), &key);
+        let mac = CbcMac::new(Box::new(cipher));
+        assert_eq!(mac.mac_bits(&message.to_bits(17), &key), Ok(expected.to_bits(17)));
+    }
+
+    #[test]
+    fn length_prepended() {
+        // Zero padding alone would give the same blocks for both messages
+        let mac = CbcMac::new(Box::new(MiMCGe::with_fixed_round_constants(5, 33, None).unwrap()));
+        let key = generate_random_bits(33);
+        assert_ne!(mac.mac(&[1, 0], &key), mac.mac(&[1, 0, 0], &key));
+        let tag = mac.mac(b"abc", &key).unwrap();
+        assert_eq!((mac.tag_size(), tag.len()), (33, 5));
+        assert_eq!(mac.verify(b"abc", &key, &tag), Ok(true));
+        assert_eq!(mac.verify(b"abd", &key, &tag), Ok(false));
+        assert_eq!(mac.verify(b"abc", &key.flip_bit(0), &tag), Ok(false));
+    }
+
+    #[test]
+    fn message_too_long() {
+        // Length of 64 bytes does not fit into 9 bits
+        let mac = CbcMac::new(Box::new(MiMCGe::with_fixed_round_constants(5, 9, None).unwrap()));
+        assert!(mac.mac(&[0; 63], &FieldElement::ZERO).is_ok());
+        assert_eq!(mac.mac(&[0; 64], &FieldElement::ZERO), Err(Error::MessageTooLong { length: 512, block_size: 9 }));
+    }
+}
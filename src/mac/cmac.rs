@@ -0,0 +1,122 @@
+use crate::error::Result;
+use crate::hash::hash::{bits_to_bytes, bytes_to_bits};
+use crate::mac::mac::Mac;
+use crate::utils::helpers::{BinaryField, Cipher, FieldElement};
+
+/// CMAC (OMAC1) over any block size. The subkeys are K_1 = 2L and K_2 = 4L for L = E_K(0), where the doubling is the
+/// multiplication by x in GF(2^n) with the default polynomial of the block size (x^128 + x^7 + x^2 + x + 1 for 128
+/// bits, so AES gives the standard AES-CMAC). The last block is added to K_1 if it is complete and padded by 10* and
+/// added to K_2 otherwise (also for the empty message), the tag is the last block of the CBC encryption with zero IV.
+pub struct Cmac {
+    cipher: Box<dyn Cipher>,
+    field: BinaryField,
+}
+
+impl Cmac {
+    /// Returns an error if there is no default field for the block size.
+    pub fn new(cipher: Box<dyn Cipher>) -> Result<Self> {
+        let field = BinaryField::new(cipher.block_size())?;
+        Ok(Cmac { cipher, field })
+    }
+
+    /// Subkeys K_1 and K_2 of the key.
+    pub fn subkeys(&self, key: &FieldElement) -> (FieldElement, FieldElement) {
+        let x = FieldElement::new(2, self.field.block_size());
+        let first = self.field.multiply(&self.cipher.encrypt(&FieldElement::ZERO, key), &x);
+        (first, self.field.multiply(&first, &x))
+    }
+
+    /// Tag of the message given as bit array, see [`Mac::mac`].
+    pub fn mac_bits(&self, message: &[u8], key: &FieldElement) -> Vec<u8> {
+        let block_size = self.cipher.block_size();
+        let (first, second) = self.subkeys(key);
+        let mut blocks: Vec<FieldElement> = message.chunks(block_size as usize).map(FieldElement::from_bits).collect();
+        let last = match message.len() % block_size as usize {
+            0 if !message.is_empty() => blocks.pop().unwrap() ^ first,
+            length => {
+                let mut last = message[message.len() - length..].to_vec();
+                last.push(1);
+                last.resize(block_size as usize, 0);
+                blocks.truncate(message.len() / block_size as usize);
+                FieldElement::from_bits(&last) ^ second
+            }
+        };
+
+        let mut state = FieldElement::ZERO;
+        for block in blocks.iter().chain([&last]) {
+            state = self.cipher.encrypt(&(state ^ *block), key);
+        }
+        state.to_bits(block_size)
+    }
+}
+
+impl Mac for Cmac {
+    fn mac(&self, message: &[u8], key: &FieldElement) -> Result<Vec<u8>> {
+        Ok(bits_to_bytes(&self.mac_bits(&bytes_to_bits(message), key)))
+    }
+
+    fn tag_size(&self) -> u32 {
+        self.cipher.block_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.cipher.key_size()
+    }
+
+    fn name(&self) -> &'static str {
+        "CMAC"
+    }
+
+    fn parameters(&self) -> String {
+        format!("{}-bit tag, subkeys modulus {:#x}, {} ({})", self.cipher.block_size(), self.field.modulus(), self.cipher.name(), self.cipher.parameters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aes::aes::AES;
+    use crate::mac::cmac::Cmac;
+    use crate::mac::mac::Mac;
+    use crate::mimc_general::mimc_general::MiMCGe;
+    use crate::utils::helpers::{FieldElement, generate_random_bits};
+    use crate::utils::uint::Uint;
+
+    fn hex(value: &str) -> Vec<u8> {
+        (0..value.len()).step_by(2).map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn aes_key() -> FieldElement {
+        FieldElement::from_uint(Uint::from(0x2b7e151628aed2a6abf7158809cf4f3c_u128), 128)
+    }
+
+    // Test vectors of RFC 4493
+    #[test]
+    fn aes_subkeys() {
+        let cmac = Cmac::new(Box::new(AES {})).unwrap();
+        let (first, second) = cmac.subkeys(&aes_key());
+        assert_eq!(first.value(), Uint::from(0xfbeed618357133667c85e08f7236a8de_u128));
+        assert_eq!(second.value(), Uint::from(0xf7ddac306ae266ccf90bc11ee46d513b_u128));
+    }
+
+    #[test]
+    fn aes_cmac() {
+        let cmac = Cmac::new(Box::new(AES {})).unwrap();
+        let message = hex("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411");
+        assert_eq!(cmac.mac(&[], &aes_key()), Ok(hex("bb1d6929e95937287fa37d129b756746")));
+        assert_eq!(cmac.mac(&message[..16], &aes_key()), Ok(hex("070a16b46b4d4144f79bdd9dd04a287c")));
+        assert_eq!(cmac.mac(&message, &aes_key()), Ok(hex("dfa66747de9ae63030ca32611497c827")));
+    }
+
+    #[test]
+    fn partial_block() {
+        // 20 bits over 13-bit blocks, the second block is 7 bits padded by 10*
+        let cipher = MiMCGe::with_fixed_round_constants(5, 13, None).unwrap();
+        let key = generate_random_bits(13);
+        let message = generate_random_bits(20).to_bits(20);
+        let cmac = Cmac::new(Box::new(cipher)).unwrap();
+        let (_, second) = cmac.subkeys(&key);
+        let first_block = cmac.cipher.encrypt(&FieldElement::from_bits(&message[..13]), &key);
+        let last_block = FieldElement::from_uint((FieldElement::from_bits(&message[13..]).value() << 6) | Uint::from(1u128 << 5), 13) ^ second;
+        assert_eq!(cmac.mac_bits(&message, &key), cmac.cipher.encrypt(&(first_block ^ last_block), &key).to_bits(13));
+    }
+}
@@ -0,0 +1,58 @@
+//! Message authentication codes built from the ciphers, [`CbcMac`](crate::mac::cbc_mac::CbcMac) with the message length
+//! in the first block, [`Cmac`](crate::mac::cmac::Cmac) and the keyed sponge
+//! [`SpongeMac`](crate::mac::sponge_mac::SpongeMac). Messages are bytes, which are split into bits the same way as by
+//! the hash functions.
+
+use crate::error::Result;
+use crate::utils::helpers::FieldElement;
+
+/// Message authentication code of byte messages.
+pub trait Mac {
+    /// Tag of the message, packed into bytes from the most significant bit (the lowest bits of the last byte are zero if
+    /// the tag size is not a multiple of 8).
+    ///
+    /// Returns an error if the construction cannot authenticate the message, e.g. it is too long.
+    fn mac(&self, message: &[u8], key: &FieldElement) -> Result<Vec<u8>>;
+
+    /// Checks that the tag authenticates the message. Tags are compared in constant time.
+    fn verify(&self, message: &[u8], key: &FieldElement, tag: &[u8]) -> Result<bool> {
+        Ok(constant_time_eq(&self.mac(message, key)?, tag))
+    }
+
+    /// Tag size in bits.
+    fn tag_size(&self) -> u32;
+
+    /// Key size in bits.
+    fn key_size(&self) -> u32;
+
+    fn name(&self) -> &'static str;
+
+    /// Human readable parameters, e.g. "64-bit tag, MiMCGe (e = 5, n = 64, 28 rounds, modulus 0x1b)".
+    fn parameters(&self) -> String;
+}
+
+/// MAC construction chosen for the `mac` and `verify` commands. Sponge takes rate, capacity and tag size in bits, same as
+/// [`HashType::Sponge`](crate::hash::hash::HashType::Sponge).
+pub enum MacType {
+    CbcMac,
+    Cmac,
+    Sponge(Option<u32>, Option<u32>, Option<u32>),
+}
+
+/// Equality of the byte arrays, which does not stop at the first difference.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |difference, (x, y)| difference | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mac::mac::constant_time_eq;
+
+    #[test]
+    fn equality() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(constant_time_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!constant_time_eq(&[1, 2], &[1, 2, 0]));
+    }
+}
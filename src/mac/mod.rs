@@ -0,0 +1,4 @@
+pub mod cbc_mac;
+pub mod cmac;
+pub mod mac;
+pub mod sponge_mac;
@@ -0,0 +1,86 @@
+use crate::error::Result;
+use crate::hash::hash::{bits_to_bytes, bytes_to_bits, HashFunction};
+use crate::hash::sponge::Sponge;
+use crate::mac::mac::Mac;
+use crate::utils::helpers::FieldElement;
+
+/// Keyed sponge MAC, the tag is the sponge digest of the key followed by the message. The key has the size of the
+/// sponge state, so it is at least as large as the capacity. Sponges are not extendable by the length, thus the plain
+/// prefix key is secure, unlike with Merkle-Damgard hash functions.
+pub struct SpongeMac {
+    sponge: Sponge,
+}
+
+impl SpongeMac {
+    pub fn new(sponge: Sponge) -> Self {
+        SpongeMac { sponge }
+    }
+
+    /// Tag of the message given as bit array, see [`Mac::mac`].
+    pub fn mac_bits(&self, message: &[u8], key: &FieldElement) -> Vec<u8> {
+        let key_size = self.key_size();
+        let mut input = FieldElement::from_uint(key.value(), key_size).to_bits(key_size);
+        input.extend_from_slice(message);
+        self.sponge.hash_bits(&input)
+    }
+}
+
+impl Mac for SpongeMac {
+    fn mac(&self, message: &[u8], key: &FieldElement) -> Result<Vec<u8>> {
+        Ok(bits_to_bytes(&self.mac_bits(&bytes_to_bits(message), key)))
+    }
+
+    fn tag_size(&self) -> u32 {
+        self.sponge.digest_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.sponge.rate() + self.sponge.capacity()
+    }
+
+    fn name(&self) -> &'static str {
+        "SpongeMAC"
+    }
+
+    fn parameters(&self) -> String {
+        format!("{}-bit key, {}", self.key_size(), self.sponge.parameters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::hash::Padding;
+    use crate::hash::sponge::Sponge;
+    use crate::mac::mac::Mac;
+    use crate::mac::sponge_mac::SpongeMac;
+    use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
+    use crate::utils::helpers::{FieldElement, generate_random_bits};
+
+    fn sponge(digest_size: u32) -> Sponge {
+        let permutation = MiMCGeFeistel::with_fixed_round_constants(5, 64, None).unwrap();
+        Sponge::new(Box::new(permutation), 32, 32, Padding::MultiRate, digest_size).unwrap()
+    }
+
+    #[test]
+    fn key_prefix() {
+        let key = generate_random_bits(64);
+        let message = b"MiMC sponge MAC";
+        let mut bits = key.to_bits(64);
+        bits.extend(FieldElement::new(0x4d, 8).to_bits(8));
+        let mac = SpongeMac::new(sponge(64));
+        assert_eq!(mac.mac_bits(&FieldElement::new(0x4d, 8).to_bits(8), &key), sponge(64).hash_bits(&bits));
+        let tag = mac.mac(message, &key).unwrap();
+        assert_eq!((mac.key_size(), mac.tag_size(), tag.len()), (64, 64, 8));
+        assert_eq!(mac.verify(message, &key, &tag), Ok(true));
+        assert_eq!(mac.verify(message, &key.flip_bit(63), &tag), Ok(false));
+        assert_eq!(mac.verify(b"MiMC sponge MAc", &key, &tag), Ok(false));
+    }
+
+    #[test]
+    fn tag_size() {
+        let key = generate_random_bits(64);
+        let tag = SpongeMac::new(sponge(100)).mac(b"abc", &key).unwrap();
+        assert_eq!(tag.len(), 13);
+        assert_eq!(tag[..4], SpongeMac::new(sponge(32)).mac(b"abc", &key).unwrap()[..]);
+    }
+}
@@ -1,33 +1,34 @@
-use crate::experiments::tests::{encrypt_seq, encrypt_seq_stream, test_cipher, test_confusion, test_decryption_time, test_diffusion, test_encryption_time};
-use crate::utils::helpers::{CipherType, FieldElement, generate_random_bits, to_binary};
-use clap::Parser;
-use clap::builder::TypedValueParser;
-
-mod utils;
-mod mimc;
-mod experiments;
-mod aes;
-mod mimc_general;
-mod tests;
+use clap::{CommandFactory, Parser};
+use clap::error::ErrorKind;
+use mimcge::experiments::tests::{authenticate, encrypt_data, encrypt_seq, encrypt_seq_stream, format_preserving, hash_message, test_cipher, test_confusion, test_decryption_time, test_diffusion, test_encryption_time, test_tweak_diffusion};
+use mimcge::experiments::helpers::key_size;
+use std::io::Read;
+use std::path::PathBuf;
+use mimcge::{Backend, BinaryField, CipherType, DecryptionMethod, Error, FieldElement, FieldType, generate_random_bits, GMiMC, GMiMCVariant, HashType, KeySchedule, MacType, MiMCGeFeistel, Mode, Padding, Result, Uint};
 
 // TODO: add link to report in readme
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Type of test to be performed.
-    #[arg(value_parser=["diffusion", "confusion", "enc-time", "dec-time", "cipher-test", "generate-test-samples", "start-bit-stream"])]
+    /// Type of test to be performed. hash prints the digest of --input (or the standard input) with the cipher as the
+    /// permutation of a sponge or the block cipher of Miyaguchi-Preneel, see --construction. encrypt-file and
+    /// decrypt-file encrypt --input in the --mode of operation with --key and write the result to --output. fpe
+    /// encrypts the --plaintext number of [0, --domain) to another number of the range by MiMCGe over the smallest
+    /// field of the range (block size is ignored). tweak-diffusion is confusion with the tweak of MiMCGe instead of
+    /// the key. mac prints the tag of --input by the --mac construction with --key, verify checks the --tag.
+    #[arg(value_parser=["diffusion", "confusion", "tweak-diffusion", "enc-time", "dec-time", "cipher-test", "generate-test-samples", "start-bit-stream", "hash",
+        "encrypt-file", "decrypt-file", "fpe", "mac", "verify"])]
     test_type: String,
 
-    /// Cipher type.
-    #[arg(value_parser=["aes", "mimc", "mimcge"])]
+    /// Cipher type. mimcge-feistel is the MiMC-2n/n Feistel network with x^e over the half-block field, gmimc the
+    /// generalized Feistel network with --branches branches.
+    #[arg(value_parser=["aes", "mimc", "mimcge", "mimcge-feistel", "gmimc"])]
     cipher_type: String,
 
-    /// Block size (only some are implemented for MiMC/MiMCGe, only 128 bit for AES).
-    #[arg(
-        default_value_t = 17,
-        value_parser = clap::builder::PossibleValuesParser::new(["5", "8", "11", "17", "25", "31", "33", "47", "61", "83", "101", "125", "127", "128"])
-                .map(|s| s.parse::<u32>().unwrap()))]
+    /// Block size (from 2 to 511 for MiMC/MiMCGe, even from 4 to 510 for MiMCGe Feistel, multiple of the branches for
+    /// GMiMC, only 128 bit for AES).
+    #[arg(default_value_t = 17, value_parser = clap::value_parser!(u32).range(2..=511))]
     block_size: u32,
 
     /// Test size (ranging from 1 to u64). How many times to repeat the same test.
@@ -40,11 +41,15 @@ struct Args {
 
     /// Plaintext to encrypt. If not given, random one is chosen.
     #[arg(short, long, default_value = None)]
-    plaintext: Option<u128>,
+    plaintext: Option<Uint>,
 
     /// Key used in encryption.
     #[arg(short, long, default_value = None)]
-    key: Option<u128>,
+    key: Option<Uint>,
+
+    /// Tweak of MiMCGe over 2^n fields for cipher-test, added to the key in every other round.
+    #[arg(long, default_value = None)]
+    tweak: Option<Uint>,
 
     /// Exponent for MiMCGe cipher *x^n*.
     #[arg(short, long, default_value = "3")]
@@ -52,46 +57,323 @@ struct Args {
 
     /// Round constants used for MiMCGe cipher.
     #[arg(short='R', long, num_args = 1..)]
-    round_constants: Vec<u128>,
+    round_constants: Vec<Uint>,
 
     /// How many rounds to reduce for the MiMCGe cipher. (Ignored if round constants are given)
     #[arg(short, long, default_value = None)]
     round_reduction: Option<usize>,
+
+    /// Reduction polynomial for MiMC/MiMCGe field, either hex (0x80000000000000000000000000000003) or list of exponents
+    /// (127,1,0). Its degree must match the block size (half of it for mimcge-feistel, branch size for gmimc).
+    #[arg(short, long, default_value = None)]
+    modulus: Option<BinaryField>,
+
+    /// Field for MiMC/MiMCGe cipher, either binary:<polynomial> (same as --modulus) or prime:<p> for GF(p) (only for
+    /// MiMCGe). Scalar fields of BN254 (254 bits) and BLS12-381 (255 bits) are available as bn254 and bls12-381.
+    /// Number of bits of p must match the block size.
+    #[arg(short, long, default_value = None, conflicts_with = "modulus")]
+    field: Option<FieldType>,
+
+    /// Decryption method for MiMC/MiMCGe over 2^n fields. Frobenius computes runs of squarings by precomputed linear
+    /// maps, square-multiply is the plain sliding window exponentiation. Block sizes up to 17 always use lookup tables.
+    #[arg(short, long, default_value = "frobenius", value_parser=["frobenius", "square-multiply"])]
+    decryption: String,
+
+    /// Key schedule of MiMC/MiMCGe over 2^n fields. single adds the key in every round, independent expands the n-bit
+    /// key into pseudorandom round keys, iterated derives the round keys by encrypting the key with zero round keys and
+    /// 2k alternates two n-bit keys.
+    #[arg(long, default_value = "single", value_parser=["single", "independent", "iterated", "2k"])]
+    key_schedule: String,
+
+    /// Use constant-time field arithmetic for MiMC/MiMCGe over 2^n fields (no lookup tables, no branches on the data).
+    #[arg(short, long)]
+    constant_time: bool,
+
+    /// Implementation of the 2^n field multiplication. g2p uses tables generated by the g2p crate and is only available
+    /// for block sizes 5, 8, 11 and 17 with the default polynomials.
+    #[arg(short, long, default_value = "native", value_parser=["native", "g2p"])]
+    backend: String,
+
+    /// Number of branches of GMiMC, block size is split into equally large branches.
+    #[arg(long, default_value = "4")]
+    branches: u32,
+
+    /// Round function of GMiMC, contracting (crf), expanding (erf) or multi-rail (mrf, needs even number of branches).
+    #[arg(long, default_value = "erf", value_parser=["crf", "erf", "mrf"])]
+    variant: String,
+
+    /// Hash construction, sponge with the cipher as the permutation or Miyaguchi-Preneel compression function.
+    #[arg(long, default_value = "sponge", value_parser=["sponge", "miyaguchi-preneel"])]
+    construction: String,
+
+    /// Sponge rate in bits, block size minus capacity by default (half of the block if capacity is not given either).
+    #[arg(long, default_value = None)]
+    rate: Option<u32>,
+
+    /// Sponge capacity in bits, block size minus rate by default.
+    #[arg(long, default_value = None)]
+    capacity: Option<u32>,
+
+    /// Padding of the hashed message, zeros, one-zeros (10*) or multi-rate (10*1).
+    #[arg(long, default_value = "multi-rate", value_parser=["zeros", "one-zeros", "multi-rate"])]
+    padding: String,
+
+    /// Sponge digest size in bits, block size by default. Miyaguchi-Preneel digest is always one block.
+    #[arg(long, default_value = None)]
+    digest_size: Option<u32>,
+
+    /// File to hash, encrypt or decrypt, standard input if not given.
+    #[arg(short, long, default_value = None)]
+    input: Option<PathBuf>,
+
+    /// File to write the encrypted or decrypted data to, standard output if not given.
+    #[arg(short, long, default_value = None)]
+    output: Option<PathBuf>,
+
+    /// Mode of operation of encrypt-file and decrypt-file.
+    #[arg(long, default_value = "cbc", value_parser=["ecb", "cbc", "ctr", "ofb", "cfb"])]
+    mode: String,
+
+    /// MAC construction, CBC-MAC with the length in the first block, CMAC or keyed sponge (with --rate, --capacity and
+    /// --digest-size as the tag size).
+    #[arg(long, default_value = "cmac", value_parser=["cbc-mac", "cmac", "sponge"])]
+    mac: String,
+
+    /// Tag in hex checked by verify.
+    #[arg(long, default_value = None)]
+    tag: Option<String>,
+
+    /// Size N of the range [0, N) of fpe.
+    #[arg(long, default_value = None)]
+    domain: Option<u128>,
+
+    /// Decrypt the --plaintext value by fpe instead of encrypting it.
+    #[arg(long)]
+    decrypt: bool,
 }
 
 fn main() {
     let args = Args::parse();
     // println!("{:?}", args);
 
+    // Invalid cipher parameters are reported without the panic backtrace, so that sweeps can carry on
+    if let Err(error) = run(args) {
+        eprintln!("error: {error}");
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let (modulus, prime_field) = match args.field {
+        Some(FieldType::Binary(field)) => (Some(field), None),
+        Some(FieldType::Prime(field)) => (None, Some(field)),
+        None => (args.modulus, None),
+    };
+
+    if let Some(field) = prime_field {
+        if field.block_size() != args.block_size {
+            Args::command().error(ErrorKind::ArgumentConflict,
+                format!("prime of {} bits does not match block size {}", field.block_size(), args.block_size)).exit();
+        }
+        if args.cipher_type != "mimcge" {
+            Args::command().error(ErrorKind::ArgumentConflict, "prime fields are only supported by mimcge cipher").exit();
+        }
+        if args.constant_time {
+            Args::command().error(ErrorKind::ArgumentConflict, "constant-time mode is only supported over 2^n fields").exit();
+        }
+    }
+
+    let backend = match args.backend.as_str() {
+        "native" => Backend::Native,
+        "g2p" => Backend::G2p,
+        _ => unreachable!()
+    };
+
+    if backend == Backend::G2p {
+        if prime_field.is_some() {
+            Args::command().error(ErrorKind::ArgumentConflict, "g2p backend is only supported over 2^n fields").exit();
+        }
+        if args.constant_time {
+            Args::command().error(ErrorKind::ArgumentConflict, "g2p backend is not constant-time").exit();
+        }
+    }
+
+    if args.test_type == "fpe" {
+        if args.cipher_type != "mimcge" {
+            Args::command().error(ErrorKind::ArgumentConflict, "fpe is only supported by mimcge cipher").exit();
+        }
+        if args.field.is_some() || args.modulus.is_some() || args.constant_time || backend != Backend::Native {
+            Args::command().error(ErrorKind::ArgumentConflict, "fpe uses the default field of the smallest block size").exit();
+        }
+        if args.domain.is_none() || args.plaintext.is_none() || args.key.is_none() {
+            Args::command().error(ErrorKind::MissingRequiredArgument, "fpe needs --domain, --plaintext and --key").exit();
+        }
+    }
+
+    // Feistel ciphers work over the field of the half-block or a branch
+    let field_size = match args.cipher_type.as_str() {
+        "mimcge-feistel" => MiMCGeFeistel::half_size(args.block_size)?,
+        "gmimc" => GMiMC::branch_size(args.branches, args.block_size)?,
+        _ => args.block_size,
+    };
+
+    if let Some(field) = modulus {
+        if field.block_size() != field_size {
+            Args::command().error(ErrorKind::ArgumentConflict,
+                format!("modulus of degree {} does not match block size {}", field.block_size(), field_size)).exit();
+        }
+        if field.is_primitive() == Some(false) {
+            eprintln!("Warning: modulus {:#x} is not primitive", field.modulus());
+        }
+    }
+
+    let modulus = if args.constant_time || backend != Backend::Native {
+        let field = match modulus {
+            Some(field) => field,
+            None => BinaryField::new(field_size)?,
+        };
+        Some(field.with_constant_time(args.constant_time)?.with_backend(backend)?)
+    } else {
+        modulus
+    };
+
+    let decryption_method = match args.decryption.as_str() {
+        "frobenius" => DecryptionMethod::Frobenius,
+        "square-multiply" => DecryptionMethod::SquareMultiply,
+        _ => unreachable!()
+    };
+
+    let key_schedule = match args.key_schedule.as_str() {
+        "single" => KeySchedule::Single,
+        "independent" => KeySchedule::Independent,
+        "iterated" => KeySchedule::Iterated,
+        "2k" => KeySchedule::TwoKey,
+        _ => unreachable!()
+    };
+
+    if key_schedule != KeySchedule::Single && (!matches!(args.cipher_type.as_str(), "mimc" | "mimcge") || prime_field.is_some() || args.test_type == "fpe") {
+        Args::command().error(ErrorKind::ArgumentConflict, "key schedules are only supported by mimc and mimcge ciphers over 2^n fields").exit();
+    }
+
+    let variant = match args.variant.as_str() {
+        "crf" => GMiMCVariant::CRF,
+        "erf" => GMiMCVariant::ERF,
+        "mrf" => GMiMCVariant::MRF,
+        _ => unreachable!()
+    };
+
+    let padding = match args.padding.as_str() {
+        "zeros" => Padding::Zeros,
+        "one-zeros" => Padding::OneZeros,
+        "multi-rate" => Padding::MultiRate,
+        _ => unreachable!()
+    };
+
+    let hash_type = match args.construction.as_str() {
+        "sponge" => HashType::Sponge(args.rate, args.capacity, args.digest_size, padding),
+        "miyaguchi-preneel" => HashType::MiyaguchiPreneel(padding),
+        _ => unreachable!()
+    };
+
+    let mode = match args.mode.as_str() {
+        "ecb" => Mode::ECB,
+        "cbc" => Mode::CBC,
+        "ctr" => Mode::CTR,
+        "ofb" => Mode::OFB,
+        "cfb" => Mode::CFB,
+        _ => unreachable!()
+    };
+
+    let mac_type = match args.mac.as_str() {
+        "cbc-mac" => MacType::CbcMac,
+        "cmac" => MacType::Cmac,
+        "sponge" => MacType::Sponge(args.rate, args.capacity, args.digest_size),
+        _ => unreachable!()
+    };
+
+    let processes_data = matches!(args.test_type.as_str(), "hash" | "encrypt-file" | "decrypt-file" | "mac" | "verify");
+    if processes_data && prime_field.is_some() {
+        Args::command().error(ErrorKind::ArgumentConflict, "hash functions, MACs and modes of operation are only supported over 2^n fields").exit();
+    }
+    if matches!(args.test_type.as_str(), "encrypt-file" | "decrypt-file") && args.key.is_none() {
+        Args::command().error(ErrorKind::MissingRequiredArgument, "encrypt-file and decrypt-file need --key").exit();
+    }
+    if matches!(args.test_type.as_str(), "mac" | "verify") && args.key.is_none() {
+        Args::command().error(ErrorKind::MissingRequiredArgument, "mac and verify need --key").exit();
+    }
+    if args.test_type == "verify" && args.tag.is_none() {
+        Args::command().error(ErrorKind::MissingRequiredArgument, "verify needs --tag").exit();
+    }
+
     let cipher_type = match args.cipher_type.as_str() {
         "aes" => CipherType::AES,
-        "mimc" => CipherType::MiMC,
-        "mimcge" => CipherType::MiMCGe(args.exponent, &args.round_constants, args.round_reduction),
+        "mimc" => CipherType::MiMC(modulus, decryption_method, key_schedule),
+        "mimcge" => match prime_field {
+            Some(field) => CipherType::MiMCGePrime(args.exponent, &args.round_constants, args.round_reduction, field),
+            None => CipherType::MiMCGe(args.exponent, &args.round_constants, args.round_reduction, modulus, decryption_method, key_schedule),
+        },
+        "mimcge-feistel" => CipherType::MiMCGeFeistel(args.exponent, &args.round_constants, args.round_reduction, modulus),
+        "gmimc" => CipherType::GMiMC(variant, args.branches, args.exponent, &args.round_constants, args.round_reduction, modulus),
         _ => unreachable!()
     };
 
     let plaintext: FieldElement =
-        if args.plaintext.is_some() {
-            to_binary(args.plaintext.unwrap_or(0), args.block_size)
+        if let Some(plaintext) = args.plaintext {
+            FieldElement::from_uint(plaintext, args.block_size)
         } else {
             generate_random_bits(args.block_size)
         };
 
+    // Key schedules may take more than one block
+    let key_size = key_size(&cipher_type, args.block_size)?;
     let key: FieldElement =
-        if args.key.is_some() {
-            to_binary(args.key.unwrap_or(0), args.block_size)
+        if let Some(key) = args.key {
+            FieldElement::from_uint(key, key_size)
         } else {
-            generate_random_bits(args.block_size)
+            generate_random_bits(key_size)
         };
 
+    let tweak = args.tweak.map(|tweak| FieldElement::from_uint(tweak, args.block_size));
+
+    // Elements of prime field must be smaller than p
+    let (plaintext, key) = match prime_field {
+        Some(field) => (field.reduce(&plaintext), field.reduce(&key)),
+        None => (plaintext, key),
+    };
+
     match args.test_type.as_str() {
         "diffusion" => test_diffusion(args.test_size, args.block_size, cipher_type),
         "confusion" => test_confusion(args.test_size, args.block_size, cipher_type),
         "enc-time" => test_encryption_time(args.test_size, args.sample_size, args.block_size, cipher_type),
         "dec-time" => test_decryption_time(args.test_size, args.sample_size, args.block_size, cipher_type),
-        "cipher-test" => test_cipher(plaintext, args.block_size, key, cipher_type),
+        "tweak-diffusion" => test_tweak_diffusion(args.test_size, args.block_size, cipher_type),
+        "cipher-test" => test_cipher(plaintext, args.block_size, key, tweak, cipher_type),
         "generate-test-samples" => encrypt_seq(args.test_size, args.block_size, key, cipher_type),
         "start-bit-stream" => encrypt_seq_stream(args.block_size, key, cipher_type),
+        "hash" => {
+            let name = args.input.as_ref().map_or("-".to_string(), |path| path.display().to_string());
+            hash_message(&read_input(&args.input)?, &name, args.block_size, cipher_type, hash_type)
+        }
+        "fpe" => format_preserving(args.decrypt, args.plaintext.unwrap(), args.domain.unwrap(), FieldElement::from_uint(args.key.unwrap(), 128),
+                                   args.exponent, args.round_reduction),
+        "mac" | "verify" => {
+            let name = args.input.as_ref().map_or("-".to_string(), |path| path.display().to_string());
+            authenticate(&read_input(&args.input)?, &name, args.block_size, key, cipher_type, mac_type, args.tag.as_deref())
+        }
+        "encrypt-file" | "decrypt-file" => encrypt_data(args.test_type == "decrypt-file", &read_input(&args.input)?, args.output.as_deref(),
+                                                        args.block_size, key, cipher_type, mode),
         _ => unreachable!()
     }
 }
+
+/// Contents of the file, or the standard input if there is no file.
+fn read_input(path: &Option<PathBuf>) -> Result<Vec<u8>> {
+    match path {
+        Some(path) => std::fs::read(path).map_err(|error| Error::Io(format!("{}: {error}", path.display()))),
+        None => {
+            let mut data = Vec::new();
+            std::io::stdin().read_to_end(&mut data)?;
+            Ok(data)
+        }
+    }
+}
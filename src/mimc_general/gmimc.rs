@@ -0,0 +1,288 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::error::{Error, Result};
+use crate::mimc_general::mimc_general::reduce_rounds;
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{add_finite_field, BinaryField, Cipher, FieldElement, generate_round_constants};
+use crate::utils::uint::Uint;
+
+/// Round function of the generalized Feistel network, see [GMiMC](https://eprint.iacr.org/2019/397). F(x) is
+/// (x + k + c_i)^e over the branch field and the branches (x_0, ..., x_{t-1}) are rotated by one after every round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GMiMCVariant {
+    /// Contracting round function, (x_1, ..., x_{t-1}, x_0 + F(x_1 + ... + x_{t-1})).
+    CRF,
+    /// Expanding round function, (x_1 + F(x_0), ..., x_{t-1} + F(x_0), x_0).
+    ERF,
+    /// Multi-rail, t/2 Feistel functions x_{2i+1} + F(x_{2i}) in parallel before the rotation. Needs even t.
+    MRF,
+}
+
+/// GMiMC with t branches of GF(2^n) and x^e as the round function, block size is t * n bits and the key has n bits.
+///
+/// Decryption runs the rounds backwards with the same power map, thus x^e does not need to be a permutation.
+pub struct GMiMC {
+    variant: GMiMCVariant,
+    exponent: u128,
+    branches: u32,
+    /// Field of a single branch.
+    field: BinaryField,
+    plan: ExponentiationPlan,
+    rounds: usize,
+    round_constants: Vec<FieldElement>
+}
+
+impl GMiMC {
+    /// Cipher with blocks of `block_size` = t * n bits split into t = `branches` branches.
+    pub fn new(variant: GMiMCVariant, exponent: u128, branches: u32, block_size: u32, round_reduction: Option<usize>) -> Result<Self> {
+        let rounds = GMiMC::number_of_rounds(exponent, branches, block_size, round_reduction)?;
+        let round_constants = generate_round_constants(rounds, GMiMC::branch_size(branches, block_size)?);
+        GMiMC::with_round_constants(variant, exponent, branches, block_size, &round_constants)
+    }
+
+    pub fn with_round_constants(variant: GMiMCVariant, exponent: u128, branches: u32, block_size: u32, round_constants: &[FieldElement]) -> Result<Self> {
+        let field = BinaryField::new(GMiMC::branch_size(branches, block_size)?)?;
+        GMiMC::with_field(variant, exponent, branches, field, round_constants)
+    }
+
+    /// Cipher over the given branch field, i.e. with user chosen reduction polynomial of degree n.
+    ///
+    /// Returns an error if the exponent is smaller than 2, the branches do not fit the block or there are no round
+    /// constants.
+    pub fn with_field(variant: GMiMCVariant, exponent: u128, branches: u32, field: BinaryField, round_constants: &[FieldElement]) -> Result<Self> {
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        GMiMC::branch_size(branches, branches.saturating_mul(field.block_size()))?;
+        if variant == GMiMCVariant::MRF && !branches.is_multiple_of(2) {
+            return Err(Error::OddBranches(branches));
+        }
+        if round_constants.is_empty() {
+            return Err(Error::NoRoundConstants);
+        }
+        Ok(GMiMC {
+            variant,
+            exponent,
+            branches,
+            field,
+            plan: ExponentiationPlan::new(Uint::from(exponent)),
+            rounds: round_constants.len(),
+            round_constants: round_constants.to_vec()
+        })
+    }
+
+    /// Branch size n for the block size t * n.
+    ///
+    /// Returns an error if there are less than 2 branches, the block size is not their multiple, a branch would have
+    /// less than 2 bits or the block is too large.
+    pub fn branch_size(branches: u32, block_size: u32) -> Result<u32> {
+        if block_size > Uint::BITS - 1 {
+            return Err(Error::UnsupportedBlockSize(block_size));
+        }
+        if branches < 2 || !block_size.is_multiple_of(branches) || block_size / branches < 2 {
+            return Err(Error::InvalidBranches { block_size, branches });
+        }
+        Ok(block_size / branches)
+    }
+
+    /// Number of rounds for the block size t * n, ceil(t * n / log2(e)) (as MiMCGe with the same block size) and 2t more
+    /// for the full diffusion of the branches, reduced by `round_reduction`.
+    ///
+    /// Returns an error if the branches do not fit the block, the exponent is smaller than 2 or the reduction leaves no
+    /// rounds.
+    pub fn number_of_rounds(exponent: u128, branches: u32, block_size: u32, round_reduction: Option<usize>) -> Result<usize> {
+        GMiMC::branch_size(branches, block_size)?;
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        let rounds = (block_size as f32 * 2f32.log(exponent as f32)).ceil() as usize + 2 * branches as usize;
+        reduce_rounds(rounds, round_reduction)
+    }
+
+    pub fn variant(&self) -> GMiMCVariant {
+        self.variant
+    }
+
+    pub fn branches(&self) -> u32 {
+        self.branches
+    }
+
+    /// Branches (x_0, ..., x_{t-1}) of the block, x_0 are the most significant bits.
+    fn split(&self, block: &FieldElement) -> Vec<FieldElement> {
+        let n = self.field.block_size();
+        (0..self.branches).rev().map(|i| FieldElement::from_uint(block.value() >> (i * n), n)).collect()
+    }
+
+    fn join(&self, branches: &[FieldElement]) -> FieldElement {
+        let n = self.field.block_size();
+        let value = branches.iter().fold(Uint::ZERO, |block, branch| (block << n) ^ branch.value());
+        FieldElement::from_uint(value, self.branches * n)
+    }
+
+    /// (x + k + c)^e
+    fn round_function(&self, x: &FieldElement, key: &FieldElement, round_constant: &FieldElement) -> FieldElement {
+        self.field.power_with(&add_finite_field(&add_finite_field(x, key), round_constant), &self.plan)
+    }
+
+    /// Adds F to the branches of the variant, every F takes branches which are not changed, thus the layer is its own
+    /// inverse.
+    fn feistel_layer(&self, state: &mut [FieldElement], key: &FieldElement, round_constant: &FieldElement) {
+        match self.variant {
+            GMiMCVariant::CRF => {
+                let sum = state[1..].iter().fold(FieldElement::ZERO, |sum, x| add_finite_field(&sum, x));
+                state[0] = add_finite_field(&state[0], &self.round_function(&sum, key, round_constant));
+            }
+            GMiMCVariant::ERF => {
+                let f = self.round_function(&state[0], key, round_constant);
+                for x in &mut state[1..] {
+                    *x = add_finite_field(x, &f);
+                }
+            }
+            GMiMCVariant::MRF => {
+                for pair in state.chunks_exact_mut(2) {
+                    pair[1] = add_finite_field(&pair[1], &self.round_function(&pair[0], key, round_constant));
+                }
+            }
+        }
+    }
+}
+
+impl Cipher for GMiMC {
+    fn encrypt(&self, plaintext: &FieldElement, key: &FieldElement) -> FieldElement {
+        let key = FieldElement::from_uint(key.value(), self.field.block_size());
+        let mut state = self.split(plaintext);
+        for round_constant in &self.round_constants {
+            self.feistel_layer(&mut state, &key, round_constant);
+            state.rotate_left(1);
+        }
+        self.join(&state)
+    }
+
+    fn decrypt(&self, ciphertext: &FieldElement, key: &FieldElement) -> FieldElement {
+        let key = FieldElement::from_uint(key.value(), self.field.block_size());
+        let mut state = self.split(ciphertext);
+        for round_constant in self.round_constants.iter().rev() {
+            state.rotate_right(1);
+            self.feistel_layer(&mut state, &key, round_constant);
+        }
+        self.join(&state)
+    }
+
+    fn block_size(&self) -> u32 {
+        self.branches * self.field.block_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.field.block_size()
+    }
+
+    fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    fn name(&self) -> &'static str {
+        "GMiMC"
+    }
+
+    fn parameters(&self) -> String {
+        format!("{:?}, e = {}, n = {}x{}, {} rounds, modulus {:#x}", self.variant, self.exponent, self.branches,
+                self.field.block_size(), self.rounds, self.field.modulus())
+    }
+}
+
+impl fmt::Display for GMiMC {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let converted_rc: &Vec<Uint> = &self.round_constants.iter().map(|x| x.value()).collect();
+
+        f.debug_struct("GMiMC")
+            .field("\n  variant", &self.variant)
+            .field("\n  exponent", &self.exponent)
+            .field("\n  branches", &self.branches)
+            .field("\n  block size", &self.block_size())
+            .field("\n  rounds", &self.rounds)
+            .field("\n  round cost", &format_args!("{}", self.plan))
+            .field(&format!("\n  field [2^{}] modulus", &self.field.block_size()), &format_args!("{:#x}", self.field.modulus()))
+            .field("\n  round constants", converted_rc)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::mimc_general::gmimc::{GMiMC, GMiMCVariant};
+    use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
+    use crate::utils::helpers::{BinaryField, Cipher, FieldElement, generate_random_bits};
+    use crate::utils::uint::Uint;
+
+    const VARIANTS: [GMiMCVariant; 3] = [GMiMCVariant::CRF, GMiMCVariant::ERF, GMiMCVariant::MRF];
+
+    /// One round with key 7 and constant 5 over GF(2^5) with x^5 + x^2 + 1 on the branches (1, 2, 3, 4).
+    fn one_round(variant: GMiMCVariant) -> [u128; 4] {
+        let cipher = GMiMC::with_round_constants(variant, 3, 4, 20, &[FieldElement::new(5, 5)]).unwrap();
+        let plaintext = FieldElement::new((1 << 15) | (2 << 10) | (3 << 5) | 4, 20);
+        let key = FieldElement::new(7, 20);
+        let ciphertext = cipher.encrypt(&plaintext, &key);
+        assert_eq!(cipher.decrypt(&ciphertext, &key), plaintext);
+        let value = ciphertext.value().low_u128();
+        [value >> 15, (value >> 10) & 31, (value >> 5) & 31, value & 31]
+    }
+
+    fn f(x: u128) -> u128 {
+        BinaryField::new(5).unwrap().power(&FieldElement::new(x ^ 7 ^ 5, 5), 3).value().low_u128()
+    }
+
+    #[test]
+    fn round_crf() {
+        assert_eq!(one_round(GMiMCVariant::CRF), [2, 3, 4, 1 ^ f(2 ^ 3 ^ 4)]);
+    }
+
+    #[test]
+    fn round_erf() {
+        assert_eq!(one_round(GMiMCVariant::ERF), [2 ^ f(1), 3 ^ f(1), 4 ^ f(1), 1]);
+    }
+
+    #[test]
+    fn round_mrf() {
+        assert_eq!(one_round(GMiMCVariant::MRF), [2 ^ f(1), 3, 4 ^ f(3), 1]);
+    }
+
+    #[test]
+    fn decrypt() {
+        for variant in VARIANTS {
+            for (branches, block_size) in [(2, 34), (4, 64), (8, 256), (6, 510)] {
+                let cipher = GMiMC::new(variant, 3, branches, block_size, None).unwrap();
+                let (plaintext, key) = (generate_random_bits(block_size), generate_random_bits(block_size / branches));
+                assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn crf_two_branches_is_feistel() {
+        // With t = 2 the contracting function is the MiMC-2n/n round
+        let round_constants = [FieldElement::new(3, 17), FieldElement::new(11, 17), FieldElement::new(1, 17)];
+        let gmimc = GMiMC::with_round_constants(GMiMCVariant::CRF, 5, 2, 34, &round_constants).unwrap();
+        let feistel = MiMCGeFeistel::with_round_constants(5, 34, &round_constants).unwrap();
+        let (plaintext, key) = (generate_random_bits(34), generate_random_bits(17));
+        let swap = |x: FieldElement| FieldElement::from_uint((x.value() >> 17) ^ ((x.value() & Uint::mask(17)) << 17), 34);
+        assert_eq!(swap(gmimc.encrypt(&swap(plaintext), &key)), feistel.encrypt(&plaintext, &key));
+    }
+
+    #[test]
+    fn metadata() {
+        let cipher = GMiMC::new(GMiMCVariant::ERF, 3, 4, 32, Some(3)).unwrap();
+        assert_eq!((cipher.name(), cipher.block_size(), cipher.key_size(), cipher.rounds()), ("GMiMC", 32, 8, 26));
+        assert_eq!(cipher.parameters(), "ERF, e = 3, n = 4x8, 26 rounds, modulus 0x11d");
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(GMiMC::new(GMiMCVariant::ERF, 3, 3, 32, None).err(), Some(Error::InvalidBranches { block_size: 32, branches: 3 }));
+        assert_eq!(GMiMC::new(GMiMCVariant::ERF, 3, 1, 32, None).err(), Some(Error::InvalidBranches { block_size: 32, branches: 1 }));
+        assert_eq!(GMiMC::new(GMiMCVariant::ERF, 3, 16, 16, None).err(), Some(Error::InvalidBranches { block_size: 16, branches: 16 }));
+        assert_eq!(GMiMC::new(GMiMCVariant::MRF, 3, 3, 33, None).err(), Some(Error::OddBranches(3)));
+        assert_eq!(GMiMC::new(GMiMCVariant::CRF, 1, 4, 32, None).err(), Some(Error::InvalidExponent(1)));
+        assert_eq!(GMiMC::new(GMiMCVariant::CRF, 3, 4, 512, None).err(), Some(Error::UnsupportedBlockSize(512)));
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::error::{Error, Result};
+use crate::mimc_general::mimc_general::reduce_rounds;
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{add_finite_field, BinaryField, Cipher, FieldElement, fixed_round_constants, generate_round_constants};
+use crate::utils::uint::Uint;
+
+/// Feistel variant of MiMCGe, MiMC-2n/n from the [MiMC paper](https://eprint.iacr.org/2016/492) with x^e instead of
+/// x^3. Block of 2n bits is split into halves (x_L, x_R) of GF(2^n) and every round maps them to
+/// (x_R + (x_L + k + c_i)^e, x_L). The key has n bits.
+///
+/// Decryption runs the rounds backwards with the same power map, thus x^e does not need to be a permutation and no
+/// decryption exponent is computed.
+pub struct MiMCGeFeistel {
+    exponent: u128,
+    /// Field of the half-block.
+    field: BinaryField,
+    plan: ExponentiationPlan,
+    rounds: usize,
+    round_constants: Vec<FieldElement>
+}
+
+impl MiMCGeFeistel {
+    /// Cipher with blocks of `block_size` = 2n bits.
+    pub fn new(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Result<Self> {
+        let rounds = MiMCGeFeistel::number_of_rounds(exponent, block_size, round_reduction)?;
+        MiMCGeFeistel::with_round_constants(exponent, block_size, &generate_round_constants(rounds, MiMCGeFeistel::half_size(block_size)?))
+    }
+
+    /// Cipher with [`fixed_round_constants`] of the half-block, which are the same in every run.
+    pub fn with_fixed_round_constants(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Result<Self> {
+        let rounds = MiMCGeFeistel::number_of_rounds(exponent, block_size, round_reduction)?;
+        MiMCGeFeistel::with_round_constants(exponent, block_size, &fixed_round_constants(rounds, MiMCGeFeistel::half_size(block_size)?))
+    }
+
+    pub fn with_round_constants(exponent: u128, block_size: u32, round_constants: &[FieldElement]) -> Result<Self> {
+        MiMCGeFeistel::with_field(exponent, BinaryField::new(MiMCGeFeistel::half_size(block_size)?)?, round_constants)
+    }
+
+    /// Cipher over the given half-block field, i.e. with user chosen reduction polynomial of degree n.
+    ///
+    /// Returns an error if the exponent is smaller than 2 or there are no round constants.
+    pub fn with_field(exponent: u128, field: BinaryField, round_constants: &[FieldElement]) -> Result<Self> {
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        if 2 * field.block_size() > Uint::BITS - 1 {
+            return Err(Error::UnsupportedBlockSize(2 * field.block_size()));
+        }
+        if round_constants.is_empty() {
+            return Err(Error::NoRoundConstants);
+        }
+        Ok(MiMCGeFeistel {
+            exponent,
+            field,
+            plan: ExponentiationPlan::new(Uint::from(exponent)),
+            rounds: round_constants.len(),
+            round_constants: round_constants.to_vec()
+        })
+    }
+
+    /// Half-block size n for the block size 2n.
+    pub fn half_size(block_size: u32) -> Result<u32> {
+        if !block_size.is_multiple_of(2) {
+            return Err(Error::OddBlockSize(block_size));
+        }
+        Ok(block_size / 2)
+    }
+
+    /// Number of rounds needed for the block size 2n, 2 * ceil(n / log2(e)) (twice as many as MiMCGe over GF(2^n)),
+    /// reduced by `round_reduction`.
+    ///
+    /// Returns an error if the block size is odd, the exponent is smaller than 2 or the reduction leaves no rounds.
+    pub fn number_of_rounds(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Result<usize> {
+        let half_size = MiMCGeFeistel::half_size(block_size)?;
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        reduce_rounds(2 * (half_size as f32 * 2f32.log(exponent as f32)).ceil() as usize, round_reduction)
+    }
+
+    /// Plan computing x^e, used in every round of both encryption and decryption.
+    pub fn plan(&self) -> &ExponentiationPlan {
+        &self.plan
+    }
+
+    /// Halves (x_L, x_R) of the block.
+    fn split(&self, block: &FieldElement) -> (FieldElement, FieldElement) {
+        let n = self.field.block_size();
+        (FieldElement::from_uint(block.value() >> n, n), FieldElement::from_uint(block.value(), n))
+    }
+
+    fn join(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        let n = self.field.block_size();
+        FieldElement::from_uint((left.value() << n) ^ right.value(), 2 * n)
+    }
+
+    /// (x + k + c)^e
+    fn round_function(&self, x: &FieldElement, key: &FieldElement, round_constant: &FieldElement) -> FieldElement {
+        self.field.power_with(&add_finite_field(&add_finite_field(x, key), round_constant), &self.plan)
+    }
+}
+
+impl Cipher for MiMCGeFeistel {
+    fn encrypt(&self, plaintext: &FieldElement, key: &FieldElement) -> FieldElement {
+        let key = FieldElement::from_uint(key.value(), self.field.block_size());
+        let (mut left, mut right) = self.split(plaintext);
+        for round_constant in &self.round_constants {
+            (left, right) = (add_finite_field(&right, &self.round_function(&left, &key, round_constant)), left);
+        }
+        self.join(&left, &right)
+    }
+
+    fn decrypt(&self, ciphertext: &FieldElement, key: &FieldElement) -> FieldElement {
+        let key = FieldElement::from_uint(key.value(), self.field.block_size());
+        let (mut left, mut right) = self.split(ciphertext);
+        for round_constant in self.round_constants.iter().rev() {
+            (left, right) = (right, add_finite_field(&left, &self.round_function(&right, &key, round_constant)));
+        }
+        self.join(&left, &right)
+    }
+
+    fn block_size(&self) -> u32 {
+        2 * self.field.block_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.field.block_size()
+    }
+
+    fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    fn name(&self) -> &'static str {
+        "MiMCGeFeistel"
+    }
+
+    fn parameters(&self) -> String {
+        format!("e = {}, n = 2x{}, {} rounds, modulus {:#x}", self.exponent, self.field.block_size(), self.rounds, self.field.modulus())
+    }
+}
+
+impl fmt::Display for MiMCGeFeistel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let converted_rc: &Vec<Uint> = &self.round_constants.iter().map(|x| x.value()).collect();
+
+        f.debug_struct("MiMCGeFeistel")
+            .field("\n  exponent", &self.exponent)
+            .field("\n  block size", &(2 * self.field.block_size()))
+            .field("\n  rounds", &self.rounds)
+            .field("\n  round cost", &format_args!("{}", self.plan))
+            .field(&format!("\n  field [2^{}] modulus", &self.field.block_size()), &format_args!("{:#x}", self.field.modulus()))
+            .field("\n  round constants", converted_rc)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::mimc_general::mimc_feistel::MiMCGeFeistel;
+    use crate::utils::helpers::{BinaryField, Cipher, FieldElement, generate_random_bits};
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn encrypt_small() {
+        // 2 rounds over GF(2^5) with x^5 + x^2 + 1, constants 0 and 5, key 7
+        let cipher = MiMCGeFeistel::with_round_constants(3, 10, &[FieldElement::ZERO, FieldElement::new(5, 5)]).unwrap();
+        // (1, 2) -> (2 + (1 + 7)^3, 1) = (first, 1) -> (1 + (first + 7 + 5)^3, first) = (second, first)
+        let plaintext = FieldElement::new((1 << 5) | 2, 10);
+        let key = FieldElement::new(7, 10);
+        let field = BinaryField::new(5).unwrap();
+        let first = 2 ^ field.power(&FieldElement::new(1 ^ 7, 5), 3).value().low_u128();
+        let second = 1 ^ field.power(&FieldElement::new(first ^ 7 ^ 5, 5), 3).value().low_u128();
+        let ciphertext = cipher.encrypt(&plaintext, &key);
+        assert_eq!(ciphertext, FieldElement::new((second << 5) | first, 10));
+        assert_eq!(cipher.decrypt(&ciphertext, &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_34() {
+        let cipher = MiMCGeFeistel::new(3, 34, None).unwrap();
+        assert_eq!(cipher.rounds(), 22);
+        let (plaintext, key) = (generate_random_bits(34), generate_random_bits(17));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_non_permutation_16() {
+        // x^3 is not a permutation of GF(2^8), the Feistel network is still invertible
+        let cipher = MiMCGeFeistel::new(3, 16, None).unwrap();
+        let (plaintext, key) = (generate_random_bits(16), generate_random_bits(8));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_510() {
+        let cipher = MiMCGeFeistel::new(7, 510, None).unwrap();
+        let (plaintext, key) = (generate_random_bits(510), generate_random_bits(255));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn key_high_bits_ignored() {
+        let cipher = MiMCGeFeistel::new(5, 34, None).unwrap();
+        let (plaintext, key) = (generate_random_bits(34), generate_random_bits(17));
+        let long_key = FieldElement::from_uint(key.value() ^ (Uint::ONE << 20), 34);
+        assert_eq!(cipher.encrypt(&plaintext, &key), cipher.encrypt(&plaintext, &long_key));
+    }
+
+    #[test]
+    fn metadata() {
+        let cipher = MiMCGeFeistel::new(5, 34, Some(2)).unwrap();
+        assert_eq!((cipher.name(), cipher.block_size(), cipher.key_size(), cipher.rounds()), ("MiMCGeFeistel", 34, 17, 14));
+        assert_eq!(cipher.parameters(), "e = 5, n = 2x17, 14 rounds, modulus 0x20009");
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(MiMCGeFeistel::new(3, 33, None).err(), Some(Error::OddBlockSize(33)));
+        assert_eq!(MiMCGeFeistel::new(1, 34, None).err(), Some(Error::InvalidExponent(1)));
+        assert_eq!(MiMCGeFeistel::new(3, 34, Some(22)).err(), Some(Error::TooManyRoundsRemoved { rounds: 22, round_reduction: 22 }));
+        assert_eq!(MiMCGeFeistel::with_field(3, BinaryField::new(257).unwrap(), &[FieldElement::ZERO]).err(), Some(Error::UnsupportedBlockSize(514)));
+    }
+}
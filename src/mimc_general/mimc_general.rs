@@ -1,88 +1,239 @@
 use std::fmt;
 use std::fmt::Formatter;
-use crate::utils::helpers::{add_finite_field, Cipher, FieldElement, gcd, generate_round_constants, square_multiply, to_decimal};
+use crate::error::{Error, Result};
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{add_finite_field, BinaryField, Cipher, DecryptionMethod, FieldElement, fixed_round_constants, gcd, generate_round_constants, KeySchedule, RoundKeys, TweakableCipher};
+use crate::utils::power_map::PowerMap;
+use crate::utils::uint::Uint;
 
-/// Finding integer *t* such that **1+t(2^n-1) / e** is an integer. Or simplified to t * ((2^n-1) mod e) = -1 mod e.
-/// Using this since t*(2^n-1) can cause an overflow.
+/// Finding integer *t* such that **1+t*m / e** is an integer, where m is the order of the multiplicative group of the
+/// field (2^n-1 for GF(2^n) and p-1 for GF(p)). Or simplified to t * (m mod e) = -1 mod e. Using this since t*m can
+/// cause an overflow.
 ///
 /// This value will be between 1 and exponent - 1. It cannot be > exponent - 1, since then we can reduce t to t = t % e.
 ///
-/// # Panics
-///
-/// Panics if t is not find in range 0 t < e. Cannot do decryption then, since s, where x^s = x in GF(2^n) does not exist.
-fn find_t(exponent: u128, block_size: u32) -> u128 {
-    let m = (2u128.pow(block_size) - 1) % exponent;
-    for t in 1..exponent {
-        if (t * m) % exponent == exponent - 1 {
-            return t
-        }
+/// Returns an error if the exponent is smaller than 2 or t is not found in range 0 < t < e. Cannot do decryption then,
+/// since s, where x^s = x in the field does not exist.
+pub fn find_t(exponent: u128, group_order: Uint) -> Result<u128> {
+    if exponent < 2 {
+        return Err(Error::InvalidExponent(exponent));
     }
-    unreachable!()
+    let m = group_order.div_rem(Uint::from(exponent)).1.low_u128();
+    (1..exponent).find(|t| (t * m) % exponent == exponent - 1).ok_or(Error::NotPermutation(exponent))
+}
+
+/// x^e is a permutation of the field if and only if gcd(e, m) = 1 for m as the order of the multiplicative group.
+pub fn is_permutation(exponent: u128, group_order: Uint) -> bool {
+    exponent != 0 && gcd(exponent, group_order.div_rem(Uint::from(exponent)).1.low_u128()) == 1
+}
+
+/// Rounds left after removing `round_reduction` of `rounds`, at least one has to remain.
+pub(crate) fn reduce_rounds(rounds: usize, round_reduction: Option<usize>) -> Result<usize> {
+    match round_reduction {
+        Some(round_reduction) if round_reduction >= rounds => Err(Error::TooManyRoundsRemoved { rounds, round_reduction }),
+        _ => Ok(rounds - round_reduction.unwrap_or(0)),
+    }
+}
+
+/// Decryption exponent s = (1 + t*m) / e, so that (x^e)^s = x. Computed as t * q + (t * r + 1) / e where m = q * e + r,
+/// thus no intermediate value is larger than m or e^2 and nothing overflows even for 2^511.
+pub fn decryption_exponent(exponent: u128, t: u128, group_order: Uint) -> Uint {
+    let (e, t) = (Uint::from(exponent), Uint::from(t));
+    let (q, r) = group_order.div_rem(e);
+    t * q + (t * r + Uint::ONE).div_rem(e).0
 }
 
 pub struct MiMCGe {
     exponent: u128,
-    block_size: u32,
-    field: u128,
+    field: BinaryField,
     t: u128,
+    power_map: PowerMap,
     rounds: usize,
-    round_constants: Vec<FieldElement>
+    round_constants: Vec<FieldElement>,
+    key_schedule: KeySchedule,
+    key_size: u32,
 }
 
 impl MiMCGe {
-    pub fn new(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Self {
-        let rounds = (block_size as f32 * 2f32.log(exponent as f32)).ceil() as usize - round_reduction.unwrap_or(0);
+    pub fn new(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Result<Self> {
+        let rounds = MiMCGe::number_of_rounds(exponent, block_size, round_reduction)?;
         MiMCGe::with_round_constants(exponent, block_size, &generate_round_constants(rounds, block_size))
     }
 
-    pub fn with_round_constants(exponent: u128, block_size: u32, round_constants: &Vec<FieldElement>) -> Self {
-        // x^n is a permutation if and only if gcd(exponent, 2^n - 1) = 1
-        assert_eq!(gcd(exponent, 2u128.pow(block_size) - 1), 1, "This is not a permutation polynomial");
-        MiMCGe {
+    /// Cipher with [`fixed_round_constants`], which are the same in every run, e.g. to decrypt by another instance.
+    pub fn with_fixed_round_constants(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Result<Self> {
+        let rounds = MiMCGe::number_of_rounds(exponent, block_size, round_reduction)?;
+        MiMCGe::with_round_constants(exponent, block_size, &fixed_round_constants(rounds, block_size))
+    }
+
+    pub fn with_round_constants(exponent: u128, block_size: u32, round_constants: &[FieldElement]) -> Result<Self> {
+        MiMCGe::with_field(exponent, BinaryField::new(block_size)?, round_constants)
+    }
+
+    /// Cipher over the given field, i.e. with user chosen reduction polynomial.
+    pub fn with_field(exponent: u128, field: BinaryField, round_constants: &[FieldElement]) -> Result<Self> {
+        MiMCGe::with_decryption_method(exponent, field, round_constants, DecryptionMethod::default())
+    }
+
+    /// Cipher over the given field, which decrypts using `decryption_method` (block sizes up to 17 use lookup tables
+    /// for both encryption and decryption instead).
+    ///
+    /// Returns an error if x^e is not a permutation of the field or there are no round constants.
+    pub fn with_decryption_method(exponent: u128, field: BinaryField, round_constants: &[FieldElement], decryption_method: DecryptionMethod) -> Result<Self> {
+        MiMCGe::with_key_schedule(exponent, field, round_constants, decryption_method, KeySchedule::default())
+    }
+
+    /// Cipher which derives its round keys by `key_schedule`.
+    ///
+    /// Returns an error if x^e is not a permutation of the field, there are no round constants or the key of the
+    /// schedule is too large.
+    pub fn with_key_schedule(exponent: u128, field: BinaryField, round_constants: &[FieldElement], decryption_method: DecryptionMethod,
+                             key_schedule: KeySchedule) -> Result<Self> {
+        let group_order = Uint::mask(field.block_size()); // 2^n - 1
+        let t = find_t(exponent, group_order)?;
+        if !is_permutation(exponent, group_order) {
+            return Err(Error::NotPermutation(exponent));
+        }
+        if round_constants.is_empty() {
+            return Err(Error::NoRoundConstants);
+        }
+        let key_size = key_schedule.key_size(field.block_size())?;
+        Ok(MiMCGe {
             exponent,
-            block_size,
-            field: 2u128.pow(block_size),
-            t: find_t(exponent, block_size),
+            field,
+            t,
+            power_map: PowerMap::new(field, exponent, decryption_exponent(exponent, t, group_order), decryption_method),
             rounds: round_constants.len(),
-            round_constants: round_constants.to_vec()
+            round_constants: round_constants.to_vec(),
+            key_schedule,
+            key_size,
+        })
+    }
+
+    /// Number of rounds needed for the block size, ceil(n / log2(e)), reduced by `round_reduction`.
+    ///
+    /// Returns an error if the exponent is smaller than 2 or the reduction leaves no rounds.
+    pub fn number_of_rounds(exponent: u128, block_size: u32, round_reduction: Option<usize>) -> Result<usize> {
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        reduce_rounds((block_size as f32 * 2f32.log(exponent as f32)).ceil() as usize, round_reduction)
+    }
+
+    pub fn key_schedule(&self) -> KeySchedule {
+        self.key_schedule
+    }
+
+    /// Round keys k_0, ..., k_r of the key by the key schedule.
+    pub fn round_keys(&self, key: &FieldElement) -> RoundKeys {
+        self.key_schedule.round_keys(key, self.field.block_size(), self.rounds,
+            |key, i| self.power_map.forward(&add_finite_field(key, &self.round_constants[i])))
+    }
+
+    /// Encrypts the plaintext with the round keys of [`MiMCGe::round_keys`].
+    pub fn encrypt_round_keys(&self, plaintext: &FieldElement, keys: &RoundKeys) -> FieldElement {
+        let mut state: FieldElement = *plaintext;
+        for (i, round_constant) in self.round_constants.iter().enumerate() {
+            let temp = add_finite_field(&keys.get(i), round_constant);
+            state = self.power_map.forward(&add_finite_field(&state, &temp));
+        }
+        add_finite_field(&state, &keys.get(self.rounds))
+    }
+
+    /// Decrypts the ciphertext with the round keys of [`MiMCGe::round_keys`].
+    pub fn decrypt_round_keys(&self, ciphertext: &FieldElement, keys: &RoundKeys) -> FieldElement {
+        let mut state = add_finite_field(ciphertext, &keys.get(self.rounds));
+        for (i, round_constant) in self.round_constants.iter().enumerate().rev() {
+            let temp = add_finite_field(&keys.get(i), round_constant);
+            state = add_finite_field(&self.power_map.inverse(&state), &temp);
         }
+        state
+    }
+
+    /// Plan computing x^e, used in every round of encryption.
+    pub fn encryption_plan(&self) -> &ExponentiationPlan {
+        self.power_map.encryption_plan()
+    }
+
+    /// Plan computing x^s, where s is the decryption exponent, used in every round of decryption.
+    pub fn decryption_plan(&self) -> &ExponentiationPlan {
+        self.power_map.decryption_plan()
     }
 }
 
 impl Cipher for MiMCGe {
     fn encrypt(&self, plaintext: &FieldElement, key: &FieldElement) -> FieldElement {
-        let mut state: FieldElement = plaintext.to_vec();
-        for round in 0..self.rounds {
-            let mut temp= add_finite_field(&key, &self.round_constants[round]);
-            temp = add_finite_field(&state, &temp);
-            state = square_multiply(&temp, self.exponent, self.block_size);
-        }
-        add_finite_field(&state, &key)
+        self.encrypt_round_keys(plaintext, &self.round_keys(key))
     }
 
     fn decrypt(&self, ciphertext: &FieldElement, key: &FieldElement) -> FieldElement {
-        assert!(self.block_size <= 125, "Decryption for 2^125 field is not implemented (overflow of decryption exponent)");
-        let mut state: FieldElement = ciphertext.to_vec();
-        let power = (self.t * (2u128.pow(self.block_size) - 1) + 1) / self.exponent;
-        for round in self.round_constants[..1].iter().chain(self.round_constants[1..].iter().rev()) {
-            let mut temp = add_finite_field(&key, round);
-            temp = add_finite_field(&state, &temp);
-            state = square_multiply(&temp, power, self.block_size);
+        self.decrypt_round_keys(ciphertext, &self.round_keys(key))
+    }
+
+    fn block_size(&self) -> u32 {
+        self.field.block_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.key_size
+    }
+
+    fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    fn name(&self) -> &'static str {
+        "MiMCGe"
+    }
+
+    fn parameters(&self) -> String {
+        let key_schedule = match self.key_schedule {
+            KeySchedule::Single => String::new(),
+            key_schedule => format!(", {} key schedule", key_schedule.name()),
+        };
+        format!("e = {}, n = {}, {} rounds, modulus {:#x}{key_schedule}", self.exponent, self.field.block_size(), self.rounds, self.field.modulus())
+    }
+
+    fn encrypt_many(&self, blocks: &mut [FieldElement], key: &FieldElement) {
+        let keys = self.round_keys(key);
+        for block in blocks {
+            *block = self.encrypt_round_keys(block, &keys);
         }
-        add_finite_field(&state, &key)
+    }
+
+    fn decrypt_many(&self, blocks: &mut [FieldElement], key: &FieldElement) {
+        let keys = self.round_keys(key);
+        for block in blocks {
+            *block = self.decrypt_round_keys(block, &keys);
+        }
+    }
+}
+
+/// The tweak is added to the round keys of odd rounds, i.e. round i adds k_i + c_i for even i and k_i + t + c_i for odd
+/// i. The final key addition continues the alternation, thus zero tweak gives the plain MiMCGe.
+impl TweakableCipher for MiMCGe {
+    fn encrypt_tweaked(&self, plaintext: &FieldElement, key: &FieldElement, tweak: &FieldElement) -> FieldElement {
+        self.encrypt_round_keys(plaintext, &self.round_keys(key).with_tweak(tweak, self.field.block_size()))
+    }
+
+    fn decrypt_tweaked(&self, ciphertext: &FieldElement, key: &FieldElement, tweak: &FieldElement) -> FieldElement {
+        self.decrypt_round_keys(ciphertext, &self.round_keys(key).with_tweak(tweak, self.field.block_size()))
     }
 }
 
 impl fmt::Display for MiMCGe {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let converted_rc: &Vec<u128> = &self.round_constants.iter().map(|x| to_decimal(x)).collect();
+        let converted_rc: &Vec<Uint> = &self.round_constants.iter().map(|x| x.value()).collect();
 
         f.debug_struct("MiMC")
             .field("\n  exponent", &self.exponent)
-            .field("\n  block size", &self.block_size)
+            .field("\n  block size", &self.field.block_size())
             .field("\n  rounds", &self.rounds)
             .field("\n  t", &self.t)
-            .field(&*format!("\n  field [2^{}]", &self.block_size), &self.field)
+            .field("\n  key schedule", &self.key_schedule.name())
+            .field("\n  encryption cost", &format_args!("{}", self.encryption_plan()))
+            .field("\n  decryption cost", &format_args!("{}", self.decryption_plan()))
+            .field(&format!("\n  field [2^{}] modulus", &self.field.block_size()), &format_args!("{:#x}", self.field.modulus()))
             .field("\n  round constants", converted_rc)
             .finish()
     }
@@ -90,148 +241,399 @@ impl fmt::Display for MiMCGe {
 
 #[cfg(test)]
 mod tests {
-    use crate::mimc_general::mimc_general::MiMCGe;
-    use crate::utils::helpers::{Cipher, to_binary};
+    use crate::error::Error;
+    use crate::mimc_general::mimc_general::{find_t, MiMCGe};
+    use crate::utils::helpers::{BinaryField, Cipher, DecryptionMethod, FieldElement, generate_random_bits, generate_round_constants, KeySchedule, TweakableCipher};
+    use crate::utils::uint::Uint;
 
     #[test]
     fn encrypt_2() {
         let block = 8;
         // 0, 79, 42, 125, 150, 10, 103, 30
-        let round_constants = vec![vec![0;8], to_binary(79, block), to_binary(42, block), to_binary(125, block),
-                                   to_binary(150, block), to_binary(10, block), to_binary(103, block), to_binary(30, block)];
-        let cipher = MiMCGe::with_round_constants(2, block, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(79, block), FieldElement::new(42, block), FieldElement::new(125, block),
+                                   FieldElement::new(150, block), FieldElement::new(10, block), FieldElement::new(103, block), FieldElement::new(30, block)];
+        let cipher = MiMCGe::with_round_constants(2, block, &round_constants).unwrap();
         // Plaintext 143, key 162, ciphertext 83
-        assert_eq!(cipher.encrypt(&to_binary(143, block), &to_binary(162, block)), to_binary(83, block));
+        assert_eq!(cipher.encrypt(&FieldElement::new(143, block), &FieldElement::new(162, block)), FieldElement::new(83, block));
     }
 
     #[test]
     fn decrypt_2() {
         let block = 8;
         // 0, 79, 42, 125, 150, 10, 103, 30
-        let round_constants = vec![vec![0;8], to_binary(79, block), to_binary(42, block), to_binary(125, block),
-                                   to_binary(150, block), to_binary(10, block), to_binary(103, block), to_binary(30, block)];
-        let cipher = MiMCGe::with_round_constants(2, block, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(79, block), FieldElement::new(42, block), FieldElement::new(125, block),
+                                   FieldElement::new(150, block), FieldElement::new(10, block), FieldElement::new(103, block), FieldElement::new(30, block)];
+        let cipher = MiMCGe::with_round_constants(2, block, &round_constants).unwrap();
         // Ciphertext 83, key 162, ciphertext 143
-        assert_eq!(cipher.decrypt(&to_binary(83, block), &to_binary(162, block)), to_binary(143, block));
+        assert_eq!(cipher.decrypt(&FieldElement::new(83, block), &FieldElement::new(162, block)), FieldElement::new(143, block));
     }
 
     // Same test as in with original MiMC x^3
     #[test]
     fn encrypt_3() {
         // 0, 5, 22, 16
-        let round_constants = vec![vec![0;5], vec![0,0,1,0,1], vec![1,0,1,1,0], vec![1,0,0,0,0]];
-        let cipher = MiMCGe::with_round_constants(3, 5, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::from_bits(&[0,0,1,0,1]), FieldElement::from_bits(&[1,0,1,1,0]), FieldElement::from_bits(&[1,0,0,0,0])];
+        let cipher = MiMCGe::with_round_constants(3, 5, &round_constants).unwrap();
         // Plaintext 15, key 29, ciphertext 7
-        assert_eq!(cipher.encrypt(&vec![0,1,1,1,1], &vec![1,1,1,0,1]), vec![0,0,1,1,1]);
+        assert_eq!(cipher.encrypt(&FieldElement::from_bits(&[0,1,1,1,1]), &FieldElement::from_bits(&[1,1,1,0,1])), FieldElement::from_bits(&[0,0,1,1,1]));
     }
 
     // Same test as in with original MiMC x^3
     #[test]
     fn decrypt_3() {
         // 0, 5, 22, 16
-        let round_constants = vec![vec![0;5], vec![0,0,1,0,1], vec![1,0,1,1,0], vec![1,0,0,0,0]];
-        let cipher = MiMCGe::with_round_constants(3, 5, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::from_bits(&[0,0,1,0,1]), FieldElement::from_bits(&[1,0,1,1,0]), FieldElement::from_bits(&[1,0,0,0,0])];
+        let cipher = MiMCGe::with_round_constants(3, 5, &round_constants).unwrap();
         // Ciphertext 7, key 29, plaintext 15
-        assert_eq!(cipher.decrypt(&vec![0,0,1,1,1], &vec![1,1,1,0,1]), vec![0,1,1,1,1]);
+        assert_eq!(cipher.decrypt(&FieldElement::from_bits(&[0,0,1,1,1]), &FieldElement::from_bits(&[1,1,1,0,1])), FieldElement::from_bits(&[0,1,1,1,1]));
     }
 
     #[test]
     fn encrypt_5() {
         // 0, 30, 11
-        let round_constants = vec![vec![0;5], vec![1,1,1,1,0], vec![0,1,0,1,1]];
-        let cipher = MiMCGe::with_round_constants(5, 5, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::from_bits(&[1,1,1,1,0]), FieldElement::from_bits(&[0,1,0,1,1])];
+        let cipher = MiMCGe::with_round_constants(5, 5, &round_constants).unwrap();
         // Plaintext 16, key 23, ciphertext 12
-        assert_eq!(cipher.encrypt(&vec![1,0,0,0,0], &vec![1,0,1,1,1]), vec![0,1,1,0,0]);
+        assert_eq!(cipher.encrypt(&FieldElement::from_bits(&[1,0,0,0,0]), &FieldElement::from_bits(&[1,0,1,1,1])), FieldElement::from_bits(&[0,1,1,0,0]));
     }
 
     #[test]
     fn decrypt_5() {
         // 0, 30, 11
-        let round_constants = vec![vec![0;5], vec![1,1,1,1,0], vec![0,1,0,1,1]];
-        let cipher = MiMCGe::with_round_constants(5, 5, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::from_bits(&[1,1,1,1,0]), FieldElement::from_bits(&[0,1,0,1,1])];
+        let cipher = MiMCGe::with_round_constants(5, 5, &round_constants).unwrap();
         // Ciphertext 12, key 23, plaintext 16
-        assert_eq!(cipher.decrypt(&vec![0,1,1,0,0], &vec![1,0,1,1,1]), vec![1,0,0,0,0]);
+        assert_eq!(cipher.decrypt(&FieldElement::from_bits(&[0,1,1,0,0]), &FieldElement::from_bits(&[1,0,1,1,1])), FieldElement::from_bits(&[1,0,0,0,0]));
     }
 
     #[test]
     fn encrypt_7() {
         let block = 11;
         // 0, 501, 1136, 2029
-        let round_constants = vec![vec![0;11], to_binary(501, block), to_binary(1136, block), to_binary(2029, block)];
-        let cipher = MiMCGe::with_round_constants(7, block, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(501, block), FieldElement::new(1136, block), FieldElement::new(2029, block)];
+        let cipher = MiMCGe::with_round_constants(7, block, &round_constants).unwrap();
         // Plaintext 1440, key 154, ciphertext 1029
-        assert_eq!(cipher.encrypt(&to_binary(1440, block), &to_binary(154, block)), to_binary(1029, block));
+        assert_eq!(cipher.encrypt(&FieldElement::new(1440, block), &FieldElement::new(154, block)), FieldElement::new(1029, block));
     }
 
     #[test]
     fn decrypt_7() {
         let block = 11;
         // 0, 501, 1136, 2029
-        let round_constants = vec![vec![0;11], to_binary(501, block), to_binary(1136, block), to_binary(2029, block)];
-        let cipher = MiMCGe::with_round_constants(7, block, &round_constants);
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(501, block), FieldElement::new(1136, block), FieldElement::new(2029, block)];
+        let cipher = MiMCGe::with_round_constants(7, block, &round_constants).unwrap();
         // Ciphertext 1440, key 154, plaintext 1029
-        assert_eq!(cipher.decrypt(&to_binary(1029, block), &to_binary(154, block)), to_binary(1440, block));
+        assert_eq!(cipher.decrypt(&FieldElement::new(1029, block), &FieldElement::new(154, block)), FieldElement::new(1440, block));
+    }
+
+    // Same as encrypt_7 with the default polynomial given explicitly
+    #[test]
+    fn encrypt_with_field_7() {
+        let block = 11;
+        // 0, 501, 1136, 2029
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(501, block), FieldElement::new(1136, block), FieldElement::new(2029, block)];
+        let cipher = MiMCGe::with_field(7, BinaryField::with_modulus(Uint::from(0x805u64)).unwrap(), &round_constants).unwrap(); // x^11 + x^2 + 1
+        // Plaintext 1440, key 154, ciphertext 1029
+        assert_eq!(cipher.encrypt(&FieldElement::new(1440, block), &FieldElement::new(154, block)), FieldElement::new(1029, block));
+    }
+
+    #[test]
+    fn decrypt_with_field_7() {
+        let block = 11;
+        // 0, 501, 1136, 2029
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(501, block), FieldElement::new(1136, block), FieldElement::new(2029, block)];
+        let cipher = MiMCGe::with_field(7, BinaryField::with_modulus(Uint::from(0xA01u64)).unwrap(), &round_constants).unwrap(); // x^11 + x^9 + 1
+        let ciphertext = cipher.encrypt(&FieldElement::new(1440, block), &FieldElement::new(154, block));
+        // Different field representation gives different ciphertext
+        assert_ne!(ciphertext, FieldElement::new(1029, block));
+        assert_eq!(cipher.decrypt(&ciphertext, &FieldElement::new(154, block)), FieldElement::new(1440, block));
+    }
+
+    #[test]
+    fn with_field_reducible() {
+        // x^11 + x + 1
+        assert_eq!(BinaryField::with_modulus(Uint::from(0x803u64)), Err(Error::ReducibleModulus(Uint::from(0x803u64))));
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        // 2^8 - 1 is divisible by 3
+        assert_eq!(MiMCGe::new(3, 8, None).err(), Some(Error::NotPermutation(3)));
+        assert_eq!(MiMCGe::new(1, 17, None).err(), Some(Error::InvalidExponent(1)));
+        assert_eq!(MiMCGe::new(3, 512, None).err(), Some(Error::UnsupportedBlockSize(512)));
+        assert_eq!(MiMCGe::new(3, 17, Some(11)).err(), Some(Error::TooManyRoundsRemoved { rounds: 11, round_reduction: 11 }));
+        assert!(MiMCGe::new(3, 17, Some(10)).is_ok());
+        assert_eq!(MiMCGe::with_round_constants(3, 17, &[]).err(), Some(Error::NoRoundConstants));
+        assert_eq!(find_t(0, Uint::mask(17)), Err(Error::InvalidExponent(0)));
     }
 
     #[test]
     fn encrypt_largest_3() {
         let block = 125;
-        let round_constants = vec![to_binary(0, block), to_binary(22518598525362737223782453369640873505, block), to_binary(41279493745247100212424497075042082289, block), to_binary(5107248381825823756822893588559890131, block), to_binary(17134787330113970672398341660256277911, block), to_binary(41379477521495789941276970052125042591, block), to_binary(6257383778005196176979691700957136288, block),
-            to_binary(15730425326360043386545811709543603502, block), to_binary(8226002342377594062487335185734003576, block), to_binary(28306188454952390457464481796346627754, block), to_binary(26414913368863978723687991365005809983, block), to_binary(26190354078703677320956332953613612927, block), to_binary(18739727043509433097578378796840726609, block), to_binary(13508989441608251041007833449921519880, block),
-            to_binary(38381964355223679481018416380889084556, block), to_binary(31161814714623194397408346364489716770, block), to_binary(32934457635429160815107860710513742589, block), to_binary(26741680603146037214397872553555190766, block), to_binary(3107426661571313149565869224512046096, block), to_binary(40198687631088615974585082090700986998, block), to_binary(5476969655650457568097010922063797835, block),
-            to_binary(41461862553193984450999192143908749498, block), to_binary(12327403700919129259788074806075185938, block), to_binary(4688292551730950202753753342430543751, block), to_binary(21905517153202268016662264333486113258, block), to_binary(40298766568895148109296556534951900641, block), to_binary(7431960764102833751099153944386722509, block), to_binary(41597672896597230345689763217693153997, block),
-            to_binary(39356322169560908611575791106241040910, block), to_binary(30915125800993868940638839796466169844, block), to_binary(7707638768011105844732221982838828339, block), to_binary(33174363695575065474040085269150833698, block), to_binary(36982357710749791314228438301434523725, block), to_binary(19199018493051685511967287122738540215, block), to_binary(40814375592240133474309999032349509611, block),
-            to_binary(5485505981129994479439989387629513167, block), to_binary(18200596548306610510030168557287998566, block), to_binary(28074850514512958691340130478009550182, block), to_binary(37920797015914161870459494996451215556, block), to_binary(21050846053341268473358818826145389604, block), to_binary(34978730106863042178059482795274579996, block), to_binary(16640784828529582217405800935335623427, block),
-            to_binary(14114032100252213628432831366356214055, block), to_binary(23449122208521263582712105148016793814, block), to_binary(42455541929312132737715909265759290100, block), to_binary(12488447674234989749856990624263179660, block), to_binary(38271021498491585088981180160936744832, block), to_binary(21273202135632886455021824132386351032, block), to_binary(42199420419474960633636624546229779561, block),
-            to_binary(6891303654180050448666157782054320675, block), to_binary(26025095744652179736363571671131702415, block), to_binary(19564141832034057701144679755146673853, block), to_binary(17249764329103120432139810318146750750, block), to_binary(15217432852446460228639605851859388557, block), to_binary(12878646208280817961286200191382358430, block), to_binary(1087373804502272802957995835970161013, block),
-            to_binary(35797366831230768258722877554563483929, block), to_binary(11960160329115209900930986940476452226, block), to_binary(18896113138883958064421435497817475600, block), to_binary(2158883091397684885863752555626906586, block), to_binary(25138877804784333102186542262583242232, block), to_binary(20021012291658384193256540135270439351, block), to_binary(1989622572028916443533248947617776030, block),
-            to_binary(7205033936298891818499388494006800195, block), to_binary(17497161803080260119745458385898769753, block), to_binary(37578834402327662055394134160005729299, block), to_binary(21240978322657773079645023711625750132, block), to_binary(37236911606149191099742906052946416063, block), to_binary(42081022960725496538871605827198703624, block), to_binary(18684432868189542060755476402097972613, block),
-            to_binary(5883437725093564569545747673014498194, block), to_binary(27031668972804118613304002349961229405, block), to_binary(9831444421268533385150305261192003966, block), to_binary(14151207877237846092529077669506552902, block), to_binary(31004815350678692215653382429727003270, block), to_binary(8246329830944397747374334927475534030, block), to_binary(16610515120689042289211774724145727829, block),
-            to_binary(2072040885702662349062282296309023930, block), to_binary(8381710917421248726076725161839749053, block)];
-        let cipher = MiMCGe::with_round_constants(3, block, &round_constants);
-        assert_eq!(cipher.encrypt(&to_binary(9468632022148749579697753766856589000, block), &to_binary(8467268564892985217747340741738563498, block)), to_binary(8019515472302977383992575657936144960, block));
+        let round_constants = vec![FieldElement::new(0, block), FieldElement::new(22518598525362737223782453369640873505, block), FieldElement::new(41279493745247100212424497075042082289, block), FieldElement::new(5107248381825823756822893588559890131, block), FieldElement::new(17134787330113970672398341660256277911, block), FieldElement::new(41379477521495789941276970052125042591, block), FieldElement::new(6257383778005196176979691700957136288, block),
+            FieldElement::new(15730425326360043386545811709543603502, block), FieldElement::new(8226002342377594062487335185734003576, block), FieldElement::new(28306188454952390457464481796346627754, block), FieldElement::new(26414913368863978723687991365005809983, block), FieldElement::new(26190354078703677320956332953613612927, block), FieldElement::new(18739727043509433097578378796840726609, block), FieldElement::new(13508989441608251041007833449921519880, block),
+            FieldElement::new(38381964355223679481018416380889084556, block), FieldElement::new(31161814714623194397408346364489716770, block), FieldElement::new(32934457635429160815107860710513742589, block), FieldElement::new(26741680603146037214397872553555190766, block), FieldElement::new(3107426661571313149565869224512046096, block), FieldElement::new(40198687631088615974585082090700986998, block), FieldElement::new(5476969655650457568097010922063797835, block),
+            FieldElement::new(41461862553193984450999192143908749498, block), FieldElement::new(12327403700919129259788074806075185938, block), FieldElement::new(4688292551730950202753753342430543751, block), FieldElement::new(21905517153202268016662264333486113258, block), FieldElement::new(40298766568895148109296556534951900641, block), FieldElement::new(7431960764102833751099153944386722509, block), FieldElement::new(41597672896597230345689763217693153997, block),
+            FieldElement::new(39356322169560908611575791106241040910, block), FieldElement::new(30915125800993868940638839796466169844, block), FieldElement::new(7707638768011105844732221982838828339, block), FieldElement::new(33174363695575065474040085269150833698, block), FieldElement::new(36982357710749791314228438301434523725, block), FieldElement::new(19199018493051685511967287122738540215, block), FieldElement::new(40814375592240133474309999032349509611, block),
+            FieldElement::new(5485505981129994479439989387629513167, block), FieldElement::new(18200596548306610510030168557287998566, block), FieldElement::new(28074850514512958691340130478009550182, block), FieldElement::new(37920797015914161870459494996451215556, block), FieldElement::new(21050846053341268473358818826145389604, block), FieldElement::new(34978730106863042178059482795274579996, block), FieldElement::new(16640784828529582217405800935335623427, block),
+            FieldElement::new(14114032100252213628432831366356214055, block), FieldElement::new(23449122208521263582712105148016793814, block), FieldElement::new(42455541929312132737715909265759290100, block), FieldElement::new(12488447674234989749856990624263179660, block), FieldElement::new(38271021498491585088981180160936744832, block), FieldElement::new(21273202135632886455021824132386351032, block), FieldElement::new(42199420419474960633636624546229779561, block),
+            FieldElement::new(6891303654180050448666157782054320675, block), FieldElement::new(26025095744652179736363571671131702415, block), FieldElement::new(19564141832034057701144679755146673853, block), FieldElement::new(17249764329103120432139810318146750750, block), FieldElement::new(15217432852446460228639605851859388557, block), FieldElement::new(12878646208280817961286200191382358430, block), FieldElement::new(1087373804502272802957995835970161013, block),
+            FieldElement::new(35797366831230768258722877554563483929, block), FieldElement::new(11960160329115209900930986940476452226, block), FieldElement::new(18896113138883958064421435497817475600, block), FieldElement::new(2158883091397684885863752555626906586, block), FieldElement::new(25138877804784333102186542262583242232, block), FieldElement::new(20021012291658384193256540135270439351, block), FieldElement::new(1989622572028916443533248947617776030, block),
+            FieldElement::new(7205033936298891818499388494006800195, block), FieldElement::new(17497161803080260119745458385898769753, block), FieldElement::new(37578834402327662055394134160005729299, block), FieldElement::new(21240978322657773079645023711625750132, block), FieldElement::new(37236911606149191099742906052946416063, block), FieldElement::new(42081022960725496538871605827198703624, block), FieldElement::new(18684432868189542060755476402097972613, block),
+            FieldElement::new(5883437725093564569545747673014498194, block), FieldElement::new(27031668972804118613304002349961229405, block), FieldElement::new(9831444421268533385150305261192003966, block), FieldElement::new(14151207877237846092529077669506552902, block), FieldElement::new(31004815350678692215653382429727003270, block), FieldElement::new(8246329830944397747374334927475534030, block), FieldElement::new(16610515120689042289211774724145727829, block),
+            FieldElement::new(2072040885702662349062282296309023930, block), FieldElement::new(8381710917421248726076725161839749053, block)];
+        let cipher = MiMCGe::with_round_constants(3, block, &round_constants).unwrap();
+        assert_eq!(cipher.encrypt(&FieldElement::new(9468632022148749579697753766856589000, block), &FieldElement::new(8467268564892985217747340741738563498, block)), FieldElement::new(8019515472302977383992575657936144960, block));
     }
 
     #[test]
     fn decrypt_largest_3() {
         let block = 125;
-        let round_constants = vec![to_binary(0, block), to_binary(22518598525362737223782453369640873505, block), to_binary(41279493745247100212424497075042082289, block), to_binary(5107248381825823756822893588559890131, block), to_binary(17134787330113970672398341660256277911, block), to_binary(41379477521495789941276970052125042591, block), to_binary(6257383778005196176979691700957136288, block),
-                                   to_binary(15730425326360043386545811709543603502, block), to_binary(8226002342377594062487335185734003576, block), to_binary(28306188454952390457464481796346627754, block), to_binary(26414913368863978723687991365005809983, block), to_binary(26190354078703677320956332953613612927, block), to_binary(18739727043509433097578378796840726609, block), to_binary(13508989441608251041007833449921519880, block),
-                                   to_binary(38381964355223679481018416380889084556, block), to_binary(31161814714623194397408346364489716770, block), to_binary(32934457635429160815107860710513742589, block), to_binary(26741680603146037214397872553555190766, block), to_binary(3107426661571313149565869224512046096, block), to_binary(40198687631088615974585082090700986998, block), to_binary(5476969655650457568097010922063797835, block),
-                                   to_binary(41461862553193984450999192143908749498, block), to_binary(12327403700919129259788074806075185938, block), to_binary(4688292551730950202753753342430543751, block), to_binary(21905517153202268016662264333486113258, block), to_binary(40298766568895148109296556534951900641, block), to_binary(7431960764102833751099153944386722509, block), to_binary(41597672896597230345689763217693153997, block),
-                                   to_binary(39356322169560908611575791106241040910, block), to_binary(30915125800993868940638839796466169844, block), to_binary(7707638768011105844732221982838828339, block), to_binary(33174363695575065474040085269150833698, block), to_binary(36982357710749791314228438301434523725, block), to_binary(19199018493051685511967287122738540215, block), to_binary(40814375592240133474309999032349509611, block),
-                                   to_binary(5485505981129994479439989387629513167, block), to_binary(18200596548306610510030168557287998566, block), to_binary(28074850514512958691340130478009550182, block), to_binary(37920797015914161870459494996451215556, block), to_binary(21050846053341268473358818826145389604, block), to_binary(34978730106863042178059482795274579996, block), to_binary(16640784828529582217405800935335623427, block),
-                                   to_binary(14114032100252213628432831366356214055, block), to_binary(23449122208521263582712105148016793814, block), to_binary(42455541929312132737715909265759290100, block), to_binary(12488447674234989749856990624263179660, block), to_binary(38271021498491585088981180160936744832, block), to_binary(21273202135632886455021824132386351032, block), to_binary(42199420419474960633636624546229779561, block),
-                                   to_binary(6891303654180050448666157782054320675, block), to_binary(26025095744652179736363571671131702415, block), to_binary(19564141832034057701144679755146673853, block), to_binary(17249764329103120432139810318146750750, block), to_binary(15217432852446460228639605851859388557, block), to_binary(12878646208280817961286200191382358430, block), to_binary(1087373804502272802957995835970161013, block),
-                                   to_binary(35797366831230768258722877554563483929, block), to_binary(11960160329115209900930986940476452226, block), to_binary(18896113138883958064421435497817475600, block), to_binary(2158883091397684885863752555626906586, block), to_binary(25138877804784333102186542262583242232, block), to_binary(20021012291658384193256540135270439351, block), to_binary(1989622572028916443533248947617776030, block),
-                                   to_binary(7205033936298891818499388494006800195, block), to_binary(17497161803080260119745458385898769753, block), to_binary(37578834402327662055394134160005729299, block), to_binary(21240978322657773079645023711625750132, block), to_binary(37236911606149191099742906052946416063, block), to_binary(42081022960725496538871605827198703624, block), to_binary(18684432868189542060755476402097972613, block),
-                                   to_binary(5883437725093564569545747673014498194, block), to_binary(27031668972804118613304002349961229405, block), to_binary(9831444421268533385150305261192003966, block), to_binary(14151207877237846092529077669506552902, block), to_binary(31004815350678692215653382429727003270, block), to_binary(8246329830944397747374334927475534030, block), to_binary(16610515120689042289211774724145727829, block),
-                                   to_binary(2072040885702662349062282296309023930, block), to_binary(8381710917421248726076725161839749053, block)];
-        let cipher = MiMCGe::with_round_constants(3, block, &round_constants);
-        assert_eq!(cipher.decrypt(&to_binary(8019515472302977383992575657936144960, block), &to_binary(8467268564892985217747340741738563498, block)), to_binary(9468632022148749579697753766856589000, block));
+        let round_constants = vec![FieldElement::new(0, block), FieldElement::new(22518598525362737223782453369640873505, block), FieldElement::new(41279493745247100212424497075042082289, block), FieldElement::new(5107248381825823756822893588559890131, block), FieldElement::new(17134787330113970672398341660256277911, block), FieldElement::new(41379477521495789941276970052125042591, block), FieldElement::new(6257383778005196176979691700957136288, block),
+                                   FieldElement::new(15730425326360043386545811709543603502, block), FieldElement::new(8226002342377594062487335185734003576, block), FieldElement::new(28306188454952390457464481796346627754, block), FieldElement::new(26414913368863978723687991365005809983, block), FieldElement::new(26190354078703677320956332953613612927, block), FieldElement::new(18739727043509433097578378796840726609, block), FieldElement::new(13508989441608251041007833449921519880, block),
+                                   FieldElement::new(38381964355223679481018416380889084556, block), FieldElement::new(31161814714623194397408346364489716770, block), FieldElement::new(32934457635429160815107860710513742589, block), FieldElement::new(26741680603146037214397872553555190766, block), FieldElement::new(3107426661571313149565869224512046096, block), FieldElement::new(40198687631088615974585082090700986998, block), FieldElement::new(5476969655650457568097010922063797835, block),
+                                   FieldElement::new(41461862553193984450999192143908749498, block), FieldElement::new(12327403700919129259788074806075185938, block), FieldElement::new(4688292551730950202753753342430543751, block), FieldElement::new(21905517153202268016662264333486113258, block), FieldElement::new(40298766568895148109296556534951900641, block), FieldElement::new(7431960764102833751099153944386722509, block), FieldElement::new(41597672896597230345689763217693153997, block),
+                                   FieldElement::new(39356322169560908611575791106241040910, block), FieldElement::new(30915125800993868940638839796466169844, block), FieldElement::new(7707638768011105844732221982838828339, block), FieldElement::new(33174363695575065474040085269150833698, block), FieldElement::new(36982357710749791314228438301434523725, block), FieldElement::new(19199018493051685511967287122738540215, block), FieldElement::new(40814375592240133474309999032349509611, block),
+                                   FieldElement::new(5485505981129994479439989387629513167, block), FieldElement::new(18200596548306610510030168557287998566, block), FieldElement::new(28074850514512958691340130478009550182, block), FieldElement::new(37920797015914161870459494996451215556, block), FieldElement::new(21050846053341268473358818826145389604, block), FieldElement::new(34978730106863042178059482795274579996, block), FieldElement::new(16640784828529582217405800935335623427, block),
+                                   FieldElement::new(14114032100252213628432831366356214055, block), FieldElement::new(23449122208521263582712105148016793814, block), FieldElement::new(42455541929312132737715909265759290100, block), FieldElement::new(12488447674234989749856990624263179660, block), FieldElement::new(38271021498491585088981180160936744832, block), FieldElement::new(21273202135632886455021824132386351032, block), FieldElement::new(42199420419474960633636624546229779561, block),
+                                   FieldElement::new(6891303654180050448666157782054320675, block), FieldElement::new(26025095744652179736363571671131702415, block), FieldElement::new(19564141832034057701144679755146673853, block), FieldElement::new(17249764329103120432139810318146750750, block), FieldElement::new(15217432852446460228639605851859388557, block), FieldElement::new(12878646208280817961286200191382358430, block), FieldElement::new(1087373804502272802957995835970161013, block),
+                                   FieldElement::new(35797366831230768258722877554563483929, block), FieldElement::new(11960160329115209900930986940476452226, block), FieldElement::new(18896113138883958064421435497817475600, block), FieldElement::new(2158883091397684885863752555626906586, block), FieldElement::new(25138877804784333102186542262583242232, block), FieldElement::new(20021012291658384193256540135270439351, block), FieldElement::new(1989622572028916443533248947617776030, block),
+                                   FieldElement::new(7205033936298891818499388494006800195, block), FieldElement::new(17497161803080260119745458385898769753, block), FieldElement::new(37578834402327662055394134160005729299, block), FieldElement::new(21240978322657773079645023711625750132, block), FieldElement::new(37236911606149191099742906052946416063, block), FieldElement::new(42081022960725496538871605827198703624, block), FieldElement::new(18684432868189542060755476402097972613, block),
+                                   FieldElement::new(5883437725093564569545747673014498194, block), FieldElement::new(27031668972804118613304002349961229405, block), FieldElement::new(9831444421268533385150305261192003966, block), FieldElement::new(14151207877237846092529077669506552902, block), FieldElement::new(31004815350678692215653382429727003270, block), FieldElement::new(8246329830944397747374334927475534030, block), FieldElement::new(16610515120689042289211774724145727829, block),
+                                   FieldElement::new(2072040885702662349062282296309023930, block), FieldElement::new(8381710917421248726076725161839749053, block)];
+        let cipher = MiMCGe::with_round_constants(3, block, &round_constants).unwrap();
+        assert_eq!(cipher.decrypt(&FieldElement::new(8019515472302977383992575657936144960, block), &FieldElement::new(8467268564892985217747340741738563498, block)), FieldElement::new(9468632022148749579697753766856589000, block));
     }
 
     #[test]
     fn encrypt_largest_17() {
         let block = 125;
-        let round_constants = vec![to_binary(0, block), to_binary(22518598525362737223782453369640873505, block), to_binary(41279493745247100212424497075042082289, block), to_binary(5107248381825823756822893588559890131, block), to_binary(17134787330113970672398341660256277911, block), to_binary(41379477521495789941276970052125042591, block), to_binary(6257383778005196176979691700957136288, block),
-                                   to_binary(15730425326360043386545811709543603502, block), to_binary(8226002342377594062487335185734003576, block), to_binary(28306188454952390457464481796346627754, block), to_binary(26414913368863978723687991365005809983, block), to_binary(26190354078703677320956332953613612927, block), to_binary(18739727043509433097578378796840726609, block), to_binary(13508989441608251041007833449921519880, block),
-                                   to_binary(38381964355223679481018416380889084556, block), to_binary(31161814714623194397408346364489716770, block), to_binary(32934457635429160815107860710513742589, block), to_binary(26741680603146037214397872553555190766, block), to_binary(3107426661571313149565869224512046096, block), to_binary(40198687631088615974585082090700986998, block), to_binary(5476969655650457568097010922063797835, block),
-                                   to_binary(41461862553193984450999192143908749498, block), to_binary(12327403700919129259788074806075185938, block), to_binary(4688292551730950202753753342430543751, block), to_binary(21905517153202268016662264333486113258, block), to_binary(40298766568895148109296556534951900641, block), to_binary(7431960764102833751099153944386722509, block), to_binary(41597672896597230345689763217693153997, block),
-                                   to_binary(39356322169560908611575791106241040910, block), to_binary(30915125800993868940638839796466169844, block), to_binary(7707638768011105844732221982838828339, block)];
-        let cipher = MiMCGe::with_round_constants(17, block, &round_constants);
-        assert_eq!(cipher.encrypt(&to_binary(3539930619944888682700143720924760077, block), &to_binary(2072040885702662349062282296309023930, block)), to_binary(1985296284827060896312693521822282292, block));
+        let round_constants = vec![FieldElement::new(0, block), FieldElement::new(22518598525362737223782453369640873505, block), FieldElement::new(41279493745247100212424497075042082289, block), FieldElement::new(5107248381825823756822893588559890131, block), FieldElement::new(17134787330113970672398341660256277911, block), FieldElement::new(41379477521495789941276970052125042591, block), FieldElement::new(6257383778005196176979691700957136288, block),
+                                   FieldElement::new(15730425326360043386545811709543603502, block), FieldElement::new(8226002342377594062487335185734003576, block), FieldElement::new(28306188454952390457464481796346627754, block), FieldElement::new(26414913368863978723687991365005809983, block), FieldElement::new(26190354078703677320956332953613612927, block), FieldElement::new(18739727043509433097578378796840726609, block), FieldElement::new(13508989441608251041007833449921519880, block),
+                                   FieldElement::new(38381964355223679481018416380889084556, block), FieldElement::new(31161814714623194397408346364489716770, block), FieldElement::new(32934457635429160815107860710513742589, block), FieldElement::new(26741680603146037214397872553555190766, block), FieldElement::new(3107426661571313149565869224512046096, block), FieldElement::new(40198687631088615974585082090700986998, block), FieldElement::new(5476969655650457568097010922063797835, block),
+                                   FieldElement::new(41461862553193984450999192143908749498, block), FieldElement::new(12327403700919129259788074806075185938, block), FieldElement::new(4688292551730950202753753342430543751, block), FieldElement::new(21905517153202268016662264333486113258, block), FieldElement::new(40298766568895148109296556534951900641, block), FieldElement::new(7431960764102833751099153944386722509, block), FieldElement::new(41597672896597230345689763217693153997, block),
+                                   FieldElement::new(39356322169560908611575791106241040910, block), FieldElement::new(30915125800993868940638839796466169844, block), FieldElement::new(7707638768011105844732221982838828339, block)];
+        let cipher = MiMCGe::with_round_constants(17, block, &round_constants).unwrap();
+        assert_eq!(cipher.encrypt(&FieldElement::new(3539930619944888682700143720924760077, block), &FieldElement::new(2072040885702662349062282296309023930, block)), FieldElement::new(1985296284827060896312693521822282292, block));
     }
 
     #[test]
     fn decrypt_largest_17() {
         let block = 125;
-        let round_constants = vec![to_binary(0, block), to_binary(22518598525362737223782453369640873505, block), to_binary(41279493745247100212424497075042082289, block), to_binary(5107248381825823756822893588559890131, block), to_binary(17134787330113970672398341660256277911, block), to_binary(41379477521495789941276970052125042591, block), to_binary(6257383778005196176979691700957136288, block),
-                                   to_binary(15730425326360043386545811709543603502, block), to_binary(8226002342377594062487335185734003576, block), to_binary(28306188454952390457464481796346627754, block), to_binary(26414913368863978723687991365005809983, block), to_binary(26190354078703677320956332953613612927, block), to_binary(18739727043509433097578378796840726609, block), to_binary(13508989441608251041007833449921519880, block),
-                                   to_binary(38381964355223679481018416380889084556, block), to_binary(31161814714623194397408346364489716770, block), to_binary(32934457635429160815107860710513742589, block), to_binary(26741680603146037214397872553555190766, block), to_binary(3107426661571313149565869224512046096, block), to_binary(40198687631088615974585082090700986998, block), to_binary(5476969655650457568097010922063797835, block),
-                                   to_binary(41461862553193984450999192143908749498, block), to_binary(12327403700919129259788074806075185938, block), to_binary(4688292551730950202753753342430543751, block), to_binary(21905517153202268016662264333486113258, block), to_binary(40298766568895148109296556534951900641, block), to_binary(7431960764102833751099153944386722509, block), to_binary(41597672896597230345689763217693153997, block),
-                                   to_binary(39356322169560908611575791106241040910, block), to_binary(30915125800993868940638839796466169844, block), to_binary(7707638768011105844732221982838828339, block)];
-        let cipher = MiMCGe::with_round_constants(17, block, &round_constants);
-        assert_eq!(cipher.decrypt(&to_binary(1985296284827060896312693521822282292, block), &to_binary(2072040885702662349062282296309023930, block)), to_binary(3539930619944888682700143720924760077, block));
+        let round_constants = vec![FieldElement::new(0, block), FieldElement::new(22518598525362737223782453369640873505, block), FieldElement::new(41279493745247100212424497075042082289, block), FieldElement::new(5107248381825823756822893588559890131, block), FieldElement::new(17134787330113970672398341660256277911, block), FieldElement::new(41379477521495789941276970052125042591, block), FieldElement::new(6257383778005196176979691700957136288, block),
+                                   FieldElement::new(15730425326360043386545811709543603502, block), FieldElement::new(8226002342377594062487335185734003576, block), FieldElement::new(28306188454952390457464481796346627754, block), FieldElement::new(26414913368863978723687991365005809983, block), FieldElement::new(26190354078703677320956332953613612927, block), FieldElement::new(18739727043509433097578378796840726609, block), FieldElement::new(13508989441608251041007833449921519880, block),
+                                   FieldElement::new(38381964355223679481018416380889084556, block), FieldElement::new(31161814714623194397408346364489716770, block), FieldElement::new(32934457635429160815107860710513742589, block), FieldElement::new(26741680603146037214397872553555190766, block), FieldElement::new(3107426661571313149565869224512046096, block), FieldElement::new(40198687631088615974585082090700986998, block), FieldElement::new(5476969655650457568097010922063797835, block),
+                                   FieldElement::new(41461862553193984450999192143908749498, block), FieldElement::new(12327403700919129259788074806075185938, block), FieldElement::new(4688292551730950202753753342430543751, block), FieldElement::new(21905517153202268016662264333486113258, block), FieldElement::new(40298766568895148109296556534951900641, block), FieldElement::new(7431960764102833751099153944386722509, block), FieldElement::new(41597672896597230345689763217693153997, block),
+                                   FieldElement::new(39356322169560908611575791106241040910, block), FieldElement::new(30915125800993868940638839796466169844, block), FieldElement::new(7707638768011105844732221982838828339, block)];
+        let cipher = MiMCGe::with_round_constants(17, block, &round_constants).unwrap();
+        assert_eq!(cipher.decrypt(&FieldElement::new(1985296284827060896312693521822282292, block), &FieldElement::new(2072040885702662349062282296309023930, block)), FieldElement::new(3539930619944888682700143720924760077, block));
+    }
+
+    // Decryption exponent (1 + t(2^n - 1)) / e does not fit into u128 for these block sizes
+    #[test]
+    fn decrypt_127_3() {
+        let cipher = MiMCGe::with_round_constants(3, 127, &generate_round_constants(4, 127)).unwrap();
+        let (plaintext, key) = (generate_random_bits(127), generate_random_bits(127));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_128_7() {
+        let cipher = MiMCGe::with_round_constants(7, 128, &generate_round_constants(4, 128)).unwrap();
+        let (plaintext, key) = (generate_random_bits(128), generate_random_bits(128));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_255_5() {
+        let cipher = MiMCGe::with_round_constants(5, 255, &generate_round_constants(4, 255)).unwrap();
+        let (plaintext, key) = (generate_random_bits(255), generate_random_bits(255));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_511_3() {
+        let cipher = MiMCGe::with_round_constants(3, 511, &generate_round_constants(3, 511)).unwrap();
+        let (plaintext, key) = (generate_random_bits(511), generate_random_bits(511));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn decrypt_square_multiply_128_7() {
+        let round_constants = generate_round_constants(4, 128);
+        let frobenius = MiMCGe::with_decryption_method(7, BinaryField::new(128).unwrap(), &round_constants, DecryptionMethod::Frobenius).unwrap();
+        let square_multiply = MiMCGe::with_decryption_method(7, BinaryField::new(128).unwrap(), &round_constants, DecryptionMethod::SquareMultiply).unwrap();
+        let (ciphertext, key) = (generate_random_bits(128), generate_random_bits(128));
+        assert_eq!(frobenius.decrypt(&ciphertext, &key), square_multiply.decrypt(&ciphertext, &key));
+    }
+
+    #[test]
+    fn decrypt_constant_time_17_5() {
+        let field = BinaryField::new(17).unwrap().with_constant_time(true).unwrap();
+        let cipher = MiMCGe::with_field(5, field, &generate_round_constants(MiMCGe::number_of_rounds(5, 17, None).unwrap(), 17)).unwrap();
+        let (plaintext, key) = (generate_random_bits(17), generate_random_bits(17));
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn plans_127_3() {
+        let cipher = MiMCGe::with_round_constants(3, 127, &generate_round_constants(4, 127)).unwrap();
+        assert_eq!(cipher.encryption_plan().exponent(), Uint::from(3u64));
+        assert_eq!((cipher.encryption_plan().squarings(), cipher.encryption_plan().multiplications()), (1, 1));
+        // t = 2, so s = (2^128 - 1) / 3 = 0x5555...5
+        assert_eq!(cipher.decryption_plan().exponent(), Uint::mask(128).div_rem_u64(3).0);
+        // Binary method would need 126 squarings and 63 multiplications
+        assert_eq!((cipher.decryption_plan().squarings(), cipher.decryption_plan().multiplications()), (123, 31));
+    }
+
+    #[test]
+    fn metadata() {
+        let cipher = MiMCGe::new(5, 17, Some(1)).unwrap();
+        assert_eq!((cipher.name(), cipher.block_size(), cipher.key_size(), cipher.rounds()), ("MiMCGe", 17, 17, 7));
+        assert_eq!(cipher.parameters(), "e = 5, n = 17, 7 rounds, modulus 0x20009");
+    }
+
+    #[test]
+    fn in_place_33() {
+        let cipher = MiMCGe::new(3, 33, None).unwrap();
+        let key = generate_random_bits(33);
+        let plaintexts: Vec<FieldElement> = (0..10).map(|_| generate_random_bits(33)).collect();
+        let mut blocks = plaintexts.clone();
+        cipher.encrypt_many(&mut blocks, &key);
+        assert_eq!(blocks[3], cipher.encrypt(&plaintexts[3], &key));
+        cipher.decrypt_in_place(&mut blocks[3], &key);
+        assert_eq!(blocks[3], plaintexts[3]);
+        cipher.decrypt_many(&mut blocks[4..], &key);
+        assert_eq!(blocks[4..], plaintexts[4..]);
+    }
+
+    #[test]
+    fn wide_plaintext() {
+        // Bits above the block size are ignored by the S-box tables of 5 bits
+        let cipher = MiMCGe::new(3, 5, None).unwrap();
+        let (key, wide) = (generate_random_bits(5), FieldElement::new((1 << 20) | 13, 32));
+        let ciphertext = cipher.encrypt(&FieldElement::new(13, 5), &key);
+        assert_eq!(cipher.encrypt(&wide, &key), ciphertext);
+        assert_eq!(cipher.decrypt(&(ciphertext ^ FieldElement::new(1 << 20, 32)), &key), FieldElement::new(13, 5));
+    }
+
+    #[test]
+    fn wide_tweak() {
+        let cipher = MiMCGe::new(3, 5, None).unwrap();
+        let (plaintext, key, tweak) = (generate_random_bits(5), generate_random_bits(5), FieldElement::new(13, 5));
+        let wide = FieldElement::new((1 << 20) | 13, 32);
+        let ciphertext = cipher.encrypt_tweaked(&plaintext, &key, &tweak);
+        assert_eq!(cipher.encrypt_tweaked(&plaintext, &key, &wide), ciphertext);
+        assert_eq!(cipher.decrypt_tweaked(&ciphertext, &key, &wide), plaintext);
+    }
+
+    #[test]
+    fn zero_tweak() {
+        let cipher = MiMCGe::new(5, 33, None).unwrap();
+        let (plaintext, key) = (generate_random_bits(33), generate_random_bits(33));
+        let ciphertext = cipher.encrypt(&plaintext, &key);
+        assert_eq!(cipher.encrypt_tweaked(&plaintext, &key, &FieldElement::ZERO), ciphertext);
+        assert_eq!(cipher.decrypt_tweaked(&ciphertext, &key, &FieldElement::ZERO), plaintext);
+    }
+
+    #[test]
+    fn tweak_alternates() {
+        // 3 rounds over GF(2^5): (((p + k)^3 + k + t + c_1)^3 + k + c_2)^3 + k + t
+        let field = BinaryField::new(5).unwrap();
+        let round_constants = [FieldElement::ZERO, FieldElement::new(9, 5), FieldElement::new(20, 5)];
+        let cipher = MiMCGe::with_round_constants(3, 5, &round_constants).unwrap();
+        let (p, k, t) = (FieldElement::new(6, 5), FieldElement::new(13, 5), FieldElement::new(17, 5));
+        let mut expected = field.power(&(p ^ k), 3);
+        expected = field.power(&(expected ^ k ^ t ^ round_constants[1]), 3);
+        expected = field.power(&(expected ^ k ^ round_constants[2]), 3) ^ k ^ t;
+        assert_eq!(cipher.encrypt_tweaked(&p, &k, &t), expected);
+        assert_eq!(cipher.decrypt_tweaked(&expected, &k, &t), p);
+    }
+
+    #[test]
+    fn decrypt_tweaked_127() {
+        let cipher = MiMCGe::new(3, 127, None).unwrap();
+        let (plaintext, key, tweak) = (generate_random_bits(127), generate_random_bits(127), generate_random_bits(127));
+        let ciphertext = cipher.encrypt_tweaked(&plaintext, &key, &tweak);
+        assert_ne!(ciphertext, cipher.encrypt(&plaintext, &key));
+        assert_ne!(ciphertext, cipher.encrypt_tweaked(&plaintext, &key, &tweak.flip_bit(0)));
+        assert_eq!(cipher.decrypt_tweaked(&ciphertext, &key, &tweak), plaintext);
+    }
+
+    #[test]
+    fn key_schedules() {
+        let (field, round_constants) = (BinaryField::new(5).unwrap(), [FieldElement::ZERO, FieldElement::new(9, 5), FieldElement::new(20, 5)]);
+        let cipher = |key_schedule| MiMCGe::with_key_schedule(3, field, &round_constants, DecryptionMethod::default(), key_schedule).unwrap();
+        let round = |x: FieldElement, k: FieldElement, c: FieldElement| field.power(&(x ^ k ^ c), 3);
+        let p = FieldElement::new(6, 5);
+        let (k0, k1) = (FieldElement::new(13, 5), FieldElement::new(2, 5));
+
+        // k_0 is the key and the other round keys are expanded from it
+        let independent = cipher(KeySchedule::Independent);
+        let keys = independent.round_keys(&k0);
+        assert_eq!(keys.get(0), k0);
+        let expected = round(round(round(p, k0, round_constants[0]), keys.get(1), round_constants[1]), keys.get(2), round_constants[2]) ^ keys.get(3);
+        assert_eq!((independent.key_size(), independent.encrypt(&p, &k0)), (5, expected));
+        assert_eq!(independent.decrypt(&expected, &k0), p);
+        assert_eq!(independent.round_keys(&k0), keys);
+        assert_ne!(independent.round_keys(&k1), keys);
+
+        let two_key = cipher(KeySchedule::TwoKey);
+        let key = FieldElement::new((2 << 5) | 13, 10);
+        let expected = round(round(round(p, k0, round_constants[0]), k1, round_constants[1]), k0, round_constants[2]) ^ k1;
+        assert_eq!((two_key.key_size(), two_key.encrypt(&p, &key)), (10, expected));
+        assert_eq!(two_key.decrypt(&expected, &key), p);
+
+        // Round keys are the states of encrypting k_0 with zero keys
+        let iterated = cipher(KeySchedule::Iterated);
+        let keys = [k0, round(k0, FieldElement::ZERO, round_constants[0])];
+        let keys = [keys[0], keys[1], round(keys[1], FieldElement::ZERO, round_constants[1])];
+        let k3 = round(keys[2], FieldElement::ZERO, round_constants[2]);
+        let expected = round(round(round(p, keys[0], round_constants[0]), keys[1], round_constants[1]), keys[2], round_constants[2]) ^ k3;
+        assert_eq!((iterated.key_size(), iterated.encrypt(&p, &k0)), (5, expected));
+        assert_eq!(iterated.decrypt(&expected, &k0), p);
+        assert_eq!(iterated.parameters(), "e = 3, n = 5, 3 rounds, modulus 0x25, iterated key schedule");
+    }
+
+    #[test]
+    fn key_schedules_generalize_single() {
+        let field = BinaryField::new(33).unwrap();
+        let round_constants = generate_round_constants(MiMCGe::number_of_rounds(5, 33, Some(8)).unwrap(), 33);
+        let cipher = |key_schedule| MiMCGe::with_key_schedule(5, field, &round_constants, DecryptionMethod::default(), key_schedule).unwrap();
+        let (plaintext, key, tweak) = (generate_random_bits(33), generate_random_bits(33), generate_random_bits(33));
+        let ciphertext = cipher(KeySchedule::Single).encrypt_tweaked(&plaintext, &key, &tweak);
+        // The same word twice
+        let repeated = (key.value() << 33) | key.value();
+        assert_eq!(cipher(KeySchedule::TwoKey).encrypt_tweaked(&plaintext, &FieldElement::from_uint(repeated, 66), &tweak), ciphertext);
+        assert_ne!(cipher(KeySchedule::Independent).encrypt_tweaked(&plaintext, &key, &tweak), ciphertext);
+        assert_ne!(cipher(KeySchedule::Iterated).encrypt_tweaked(&plaintext, &key, &tweak), ciphertext);
+        let two_key = cipher(KeySchedule::TwoKey);
+        let long_key = generate_random_bits(66);
+        assert_eq!(two_key.decrypt_tweaked(&two_key.encrypt_tweaked(&plaintext, &long_key, &tweak), &long_key, &tweak), plaintext);
+    }
+
+    #[test]
+    fn many_blocks_key_schedules() {
+        let field = BinaryField::new(33).unwrap();
+        let round_constants = generate_round_constants(MiMCGe::number_of_rounds(5, 33, Some(8)).unwrap(), 33);
+        for key_schedule in [KeySchedule::Single, KeySchedule::Independent, KeySchedule::Iterated, KeySchedule::TwoKey] {
+            let cipher = MiMCGe::with_key_schedule(5, field, &round_constants, DecryptionMethod::default(), key_schedule).unwrap();
+            let key = generate_random_bits(cipher.key_size());
+            let plaintexts: Vec<FieldElement> = (0..4).map(|_| generate_random_bits(33)).collect();
+            let mut blocks = plaintexts.clone();
+            cipher.encrypt_many(&mut blocks, &key);
+            assert_eq!(blocks, plaintexts.iter().map(|p| cipher.encrypt(p, &key)).collect::<Vec<FieldElement>>());
+            cipher.decrypt_many(&mut blocks, &key);
+            assert_eq!(blocks, plaintexts);
+        }
+    }
+
+    #[test]
+    fn key_too_large() {
+        // Two keys of 257 bits do not fit into 512 bits, the independent schedule needs only one
+        let field = BinaryField::new(257).unwrap();
+        let round_constants = generate_round_constants(MiMCGe::number_of_rounds(3, 257, None).unwrap(), 257);
+        assert_eq!(MiMCGe::with_key_schedule(3, field, &round_constants, DecryptionMethod::default(), KeySchedule::TwoKey).err(), Some(Error::KeyTooLarge(514)));
+        assert!(MiMCGe::with_key_schedule(3, field, &round_constants, DecryptionMethod::default(), KeySchedule::Independent).is_ok());
     }
 }
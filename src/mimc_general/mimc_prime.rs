@@ -0,0 +1,213 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::error::{Error, Result};
+use crate::mimc_general::mimc_general::{decryption_exponent, find_t, is_permutation, reduce_rounds};
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{Cipher, FieldElement, generate_round_constants};
+use crate::utils::prime_field::PrimeField;
+use crate::utils::uint::Uint;
+
+/// MiMCGe over prime field GF(p) as defined in the original MiMC paper, i.e. x_(i+1) = (x_i + k + c_i)^e mod p. Unlike
+/// in GF(2^n), addition is not its own inverse, so decryption subtracts the key and round constants.
+///
+/// Inputs are reduced modulo p, thus only plaintexts and keys smaller than p are decrypted back to themselves.
+pub struct MiMCGePrime {
+    exponent: u128,
+    field: PrimeField,
+    t: u128,
+    encryption_plan: ExponentiationPlan,
+    decryption_plan: ExponentiationPlan,
+    rounds: usize,
+    round_constants: Vec<FieldElement>
+}
+
+impl MiMCGePrime {
+    pub fn new(exponent: u128, field: PrimeField, round_reduction: Option<usize>) -> Result<Self> {
+        let rounds = MiMCGePrime::number_of_rounds(exponent, &field, round_reduction)?;
+        MiMCGePrime::with_round_constants(exponent, field, &generate_round_constants(rounds, field.block_size()))
+    }
+
+    /// Cipher with the given round constants, they are reduced modulo p.
+    ///
+    /// Returns an error if x^e is not a permutation of the field or there are no round constants.
+    pub fn with_round_constants(exponent: u128, field: PrimeField, round_constants: &[FieldElement]) -> Result<Self> {
+        let group_order = field.modulus() - Uint::ONE; // p - 1
+        let t = find_t(exponent, group_order)?;
+        if !is_permutation(exponent, group_order) {
+            return Err(Error::NotPermutation(exponent));
+        }
+        if round_constants.is_empty() {
+            return Err(Error::NoRoundConstants);
+        }
+        Ok(MiMCGePrime {
+            exponent,
+            field,
+            t,
+            encryption_plan: ExponentiationPlan::new(Uint::from(exponent)),
+            decryption_plan: ExponentiationPlan::new(decryption_exponent(exponent, t, group_order)),
+            rounds: round_constants.len(),
+            round_constants: round_constants.iter().map(|x| field.reduce(x)).collect()
+        })
+    }
+
+    /// Number of rounds needed for the field, ceil(log_e(p)), reduced by `round_reduction`.
+    ///
+    /// Returns an error if the exponent is smaller than 2 or the reduction leaves no rounds.
+    pub fn number_of_rounds(exponent: u128, field: &PrimeField, round_reduction: Option<usize>) -> Result<usize> {
+        if exponent < 2 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+        reduce_rounds((field.log2_order() / (exponent as f64).log2()).ceil() as usize, round_reduction)
+    }
+
+    /// Plan computing x^e, used in every round of encryption.
+    pub fn encryption_plan(&self) -> &ExponentiationPlan {
+        &self.encryption_plan
+    }
+
+    /// Plan computing x^s, where s is the decryption exponent, used in every round of decryption.
+    pub fn decryption_plan(&self) -> &ExponentiationPlan {
+        &self.decryption_plan
+    }
+}
+
+impl Cipher for MiMCGePrime {
+    fn encrypt(&self, plaintext: &FieldElement, key: &FieldElement) -> FieldElement {
+        let key = self.field.reduce(key);
+        let mut state = self.field.reduce(plaintext);
+        for round_constant in &self.round_constants {
+            let temp = self.field.add(&self.field.add(&state, &key), round_constant);
+            state = self.field.power_with(&temp, &self.encryption_plan);
+        }
+        self.field.add(&state, &key)
+    }
+
+    fn decrypt(&self, ciphertext: &FieldElement, key: &FieldElement) -> FieldElement {
+        let key = self.field.reduce(key);
+        let mut state = self.field.subtract(&self.field.reduce(ciphertext), &key);
+        for round_constant in self.round_constants.iter().rev() {
+            let temp = self.field.power_with(&state, &self.decryption_plan);
+            state = self.field.subtract(&self.field.subtract(&temp, &key), round_constant);
+        }
+        state
+    }
+
+    fn block_size(&self) -> u32 {
+        self.field.block_size()
+    }
+
+    fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    fn name(&self) -> &'static str {
+        "MiMCGePrime"
+    }
+
+    fn parameters(&self) -> String {
+        format!("e = {}, n = {}, {} rounds, {}", self.exponent, self.field.block_size(), self.rounds, self.field)
+    }
+}
+
+impl fmt::Display for MiMCGePrime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let converted_rc: &Vec<Uint> = &self.round_constants.iter().map(|x| x.value()).collect();
+
+        f.debug_struct("MiMCGePrime")
+            .field("\n  exponent", &self.exponent)
+            .field("\n  field", &format_args!("{}", self.field))
+            .field("\n  rounds", &self.rounds)
+            .field("\n  t", &self.t)
+            .field("\n  encryption cost", &format_args!("{}", self.encryption_plan))
+            .field("\n  decryption cost", &format_args!("{}", self.decryption_plan))
+            .field("\n  round constants", converted_rc)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::mimc_general::mimc_prime::MiMCGePrime;
+    use crate::utils::helpers::{Cipher, FieldElement, generate_random_bits};
+    use crate::utils::prime_field::PrimeField;
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn encrypt_101_3() {
+        let field = PrimeField::new(Uint::from(101u64)).unwrap();
+        // 0, 5, 17, 42, 88
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(5, 7), FieldElement::new(17, 7), FieldElement::new(42, 7), FieldElement::new(88, 7)];
+        let cipher = MiMCGePrime::with_round_constants(3, field, &round_constants).unwrap();
+        // Plaintext 33, key 71, ciphertext 66
+        assert_eq!(cipher.encrypt(&FieldElement::new(33, 7), &FieldElement::new(71, 7)), FieldElement::new(66, 7));
+    }
+
+    #[test]
+    fn decrypt_101_3() {
+        let field = PrimeField::new(Uint::from(101u64)).unwrap();
+        // 0, 5, 17, 42, 88
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(5, 7), FieldElement::new(17, 7), FieldElement::new(42, 7), FieldElement::new(88, 7)];
+        let cipher = MiMCGePrime::with_round_constants(3, field, &round_constants).unwrap();
+        // Ciphertext 66, key 71, plaintext 33
+        assert_eq!(cipher.decrypt(&FieldElement::new(66, 7), &FieldElement::new(71, 7)), FieldElement::new(33, 7));
+    }
+
+    #[test]
+    fn decrypt_255_5() {
+        // 2^255 - 19
+        let field = PrimeField::new((Uint::ONE << 255) - Uint::from(19u64)).unwrap();
+        let cipher = MiMCGePrime::new(5, field, Some(105)).unwrap();
+        let plaintext = field.reduce(&generate_random_bits(255));
+        let key = generate_random_bits(255);
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn encrypt_bn254_7() {
+        let field = PrimeField::preset("bn254").unwrap();
+        // 0, 1, 2, 3
+        let round_constants = vec![FieldElement::ZERO, FieldElement::new(1, 254), FieldElement::new(2, 254), FieldElement::new(3, 254)];
+        let cipher = MiMCGePrime::with_round_constants(7, field, &round_constants).unwrap();
+        let ciphertext: Uint = "7250991499514021714827298151470675294434772585204369417294991782669855245549".parse().unwrap();
+        // Plaintext 12345, key 678
+        assert_eq!(cipher.encrypt(&FieldElement::new(12345, 254), &FieldElement::new(678, 254)), FieldElement::from_uint(ciphertext, 254));
+        assert_eq!(cipher.decrypt(&FieldElement::from_uint(ciphertext, 254), &FieldElement::new(678, 254)), FieldElement::new(12345, 254));
+    }
+
+    #[test]
+    fn decrypt_bls12_381_5() {
+        let field = PrimeField::preset("bls12-381").unwrap();
+        let cipher = MiMCGePrime::new(5, field, None).unwrap();
+        let plaintext = field.reduce(&generate_random_bits(255));
+        let key = generate_random_bits(255);
+        assert_eq!(cipher.decrypt(&cipher.encrypt(&plaintext, &key), &key), plaintext);
+    }
+
+    #[test]
+    fn number_of_rounds() {
+        assert_eq!(MiMCGePrime::number_of_rounds(3, &PrimeField::new(Uint::from(101u64)).unwrap(), None).unwrap(), 5);
+        assert_eq!(MiMCGePrime::number_of_rounds(5, &PrimeField::new((Uint::ONE << 255) - Uint::from(19u64)).unwrap(), None).unwrap(), 110);
+        // Same as MiMC-7 in circomlib
+        assert_eq!(MiMCGePrime::number_of_rounds(7, &PrimeField::preset("bn254").unwrap(), None).unwrap(), 91);
+        assert_eq!(MiMCGePrime::number_of_rounds(5, &PrimeField::preset("bls12-381").unwrap(), None).unwrap(), 110);
+    }
+
+    #[test]
+    fn not_permutation() {
+        // 101 - 1 is divisible by 5
+        let cipher = MiMCGePrime::with_round_constants(5, PrimeField::new(Uint::from(101u64)).unwrap(), &[FieldElement::ZERO]);
+        assert_eq!(cipher.err(), Some(Error::NotPermutation(5)));
+        // x^2 is never a permutation of GF(p)
+        assert_eq!(MiMCGePrime::new(2, PrimeField::new(Uint::from(101u64)).unwrap(), None).err(), Some(Error::NotPermutation(2)));
+        assert_eq!(MiMCGePrime::new(3, PrimeField::new(Uint::from(101u64)).unwrap(), Some(5)).err(),
+            Some(Error::TooManyRoundsRemoved { rounds: 5, round_reduction: 5 }));
+    }
+
+    #[test]
+    fn metadata() {
+        let cipher = MiMCGePrime::new(3, PrimeField::new(Uint::from(101u64)).unwrap(), None).unwrap();
+        assert_eq!((cipher.name(), cipher.block_size(), cipher.rounds()), ("MiMCGePrime", 7, 5));
+        assert_eq!(cipher.parameters(), "e = 3, n = 7, 5 rounds, GF(101)");
+    }
+}
@@ -1 +1,4 @@
-pub mod mimc_general;
\ No newline at end of file
+pub mod gmimc;
+pub mod mimc_feistel;
+pub mod mimc_general;
+pub mod mimc_prime;
@@ -0,0 +1,307 @@
+//! Modes of operation over any [`Cipher`], so that messages of any length can be encrypted. Messages are bytes, which
+//! are split into blocks of n bits (the most significant bit of the first byte first), thus block sizes which are not
+//! a multiple of 8 work the same way.
+//!
+//! ECB and CBC pad the message by 1 followed by zeros up to a whole number of blocks which is also a whole number of
+//! bytes (a multiple of lcm(n, 8) bits), so the ciphertext has no partial bytes. CTR, OFB and CFB encrypt the message
+//! by a key stream, thus the ciphertext has the same length as the message.
+
+use crate::error::{Error, Result};
+use crate::hash::hash::{bits_to_bytes, bytes_to_bits, Padding};
+use crate::utils::helpers::{Cipher, FieldElement, gcd, generate_random_bits};
+use crate::utils::uint::Uint;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Electronic codebook, every block is encrypted separately.
+    ECB,
+    /// Cipher block chaining, C_i = E(P_i + C_{i-1}) with C_0 = IV.
+    CBC,
+    /// Counter, C_i = P_i + E(IV + i) where the counter wraps around 2^n.
+    CTR,
+    /// Output feedback, C_i = P_i + O_i where O_i = E(O_{i-1}) and O_0 = IV.
+    OFB,
+    /// Cipher feedback with full block segments, C_i = P_i + E(C_{i-1}) with C_0 = IV.
+    CFB,
+}
+
+impl Mode {
+    /// Whether the message is padded, i.e. the mode needs the decryption of the cipher.
+    pub fn is_padded(self) -> bool {
+        matches!(self, Mode::ECB | Mode::CBC)
+    }
+
+    /// Whether the mode takes an initialization vector (or initial counter).
+    pub fn has_iv(self) -> bool {
+        self != Mode::ECB
+    }
+}
+
+/// Number of bits the padded message is a multiple of, lcm(n, 8).
+fn padding_size(block_size: u32) -> u32 {
+    block_size * 8 / gcd(block_size as u128, 8) as u32
+}
+
+/// Removes the 1 and zeros added by the padding. The padding is at most lcm(n, 8) bits and follows whole bytes of the
+/// message.
+fn unpad(mut bits: Vec<u8>, block_size: u32) -> Result<Vec<u8>> {
+    let padding_start = bits.len().saturating_sub(padding_size(block_size) as usize);
+    match bits.iter().rposition(|&bit| bit == 1) {
+        Some(one) if one >= padding_start && one.is_multiple_of(8) => {
+            bits.truncate(one);
+            Ok(bits)
+        }
+        _ => Err(Error::InvalidPadding),
+    }
+}
+
+fn to_blocks(bits: &[u8], block_size: u32) -> Vec<FieldElement> {
+    bits.chunks_exact(block_size as usize).map(FieldElement::from_bits).collect()
+}
+
+fn from_blocks(blocks: &[FieldElement], block_size: u32) -> Vec<u8> {
+    blocks.iter().flat_map(|block| block.to_bits(block_size)).collect()
+}
+
+/// Adds the key stream to the bits, the last block of the key stream is cut to the length of the message.
+fn add_key_stream(bits: &[u8], key_stream: &[FieldElement], block_size: u32) -> Vec<u8> {
+    bits.iter().zip(from_blocks(key_stream, block_size)).map(|(bit, key_bit)| bit ^ key_bit).collect()
+}
+
+/// Key stream of CTR mode, E(IV + i) for every block of the message.
+fn counter_key_stream(cipher: &dyn Cipher, key: &FieldElement, iv: &FieldElement, length: usize) -> Vec<FieldElement> {
+    let block_size = cipher.block_size();
+    let mut key_stream: Vec<FieldElement> = (0..length.div_ceil(block_size as usize))
+        .map(|i| FieldElement::from_uint(iv.value() + Uint::from(i as u128), block_size))
+        .collect();
+    cipher.encrypt_many(&mut key_stream, key);
+    key_stream
+}
+
+/// Encrypts the message in the mode, `iv` is ignored by ECB.
+pub fn encrypt(cipher: &dyn Cipher, mode: Mode, key: &FieldElement, iv: &FieldElement, plaintext: &[u8]) -> Vec<u8> {
+    let block_size = cipher.block_size();
+    let bits = bytes_to_bits(plaintext);
+    let bits = if mode.is_padded() { Padding::OneZeros.pad(&bits, padding_size(block_size)) } else { bits };
+
+    let ciphertext = match mode {
+        Mode::ECB => {
+            let mut blocks = to_blocks(&bits, block_size);
+            cipher.encrypt_many(&mut blocks, key);
+            from_blocks(&blocks, block_size)
+        }
+        Mode::CBC => {
+            let mut previous = *iv;
+            let blocks: Vec<FieldElement> = to_blocks(&bits, block_size).into_iter().map(|block| {
+                previous = cipher.encrypt(&(block ^ previous), key);
+                previous
+            }).collect();
+            from_blocks(&blocks, block_size)
+        }
+        Mode::CTR => add_key_stream(&bits, &counter_key_stream(cipher, key, iv, bits.len()), block_size),
+        Mode::OFB => {
+            let mut output = *iv;
+            let key_stream: Vec<FieldElement> = (0..bits.len().div_ceil(block_size as usize)).map(|_| {
+                output = cipher.encrypt(&output, key);
+                output
+            }).collect();
+            add_key_stream(&bits, &key_stream, block_size)
+        }
+        Mode::CFB => {
+            let mut previous = *iv;
+            bits.chunks(block_size as usize).flat_map(|segment| {
+                let ciphertext = add_key_stream(segment, &[cipher.encrypt(&previous, key)], block_size);
+                if segment.len() == block_size as usize {
+                    previous = FieldElement::from_bits(&ciphertext);
+                }
+                ciphertext
+            }).collect()
+        }
+    };
+    bits_to_bytes(&ciphertext)
+}
+
+/// Decrypts the ciphertext of [`encrypt`].
+///
+/// Returns an error if the ciphertext of ECB or CBC is not a multiple of lcm(n, 8) bits or its padding is invalid, i.e.
+/// the last lcm(n, 8) bits do not end with 1 and zeros after a whole byte. A wrong key gives invalid padding with
+/// probability about 255/256, the other decryptions are garbage.
+pub fn decrypt(cipher: &dyn Cipher, mode: Mode, key: &FieldElement, iv: &FieldElement, ciphertext: &[u8]) -> Result<Vec<u8>> {
+    let block_size = cipher.block_size();
+    let bits = bytes_to_bits(ciphertext);
+    if mode.is_padded() && (bits.is_empty() || !bits.len().is_multiple_of(padding_size(block_size) as usize)) {
+        return Err(Error::InvalidCiphertextLength { length: bits.len(), block_size });
+    }
+
+    let plaintext = match mode {
+        Mode::ECB => {
+            let mut blocks = to_blocks(&bits, block_size);
+            cipher.decrypt_many(&mut blocks, key);
+            unpad(from_blocks(&blocks, block_size), block_size)?
+        }
+        Mode::CBC => {
+            let ciphertext_blocks = to_blocks(&bits, block_size);
+            let mut blocks = ciphertext_blocks.clone();
+            cipher.decrypt_many(&mut blocks, key);
+            for (i, block) in blocks.iter_mut().enumerate() {
+                *block = *block ^ if i == 0 { *iv } else { ciphertext_blocks[i - 1] };
+            }
+            unpad(from_blocks(&blocks, block_size), block_size)?
+        }
+        // Key stream modes decrypt by encryption
+        Mode::CTR | Mode::OFB => return Ok(encrypt(cipher, mode, key, iv, ciphertext)),
+        Mode::CFB => {
+            let mut previous = *iv;
+            bits.chunks(block_size as usize).flat_map(|segment| {
+                let plaintext = add_key_stream(segment, &[cipher.encrypt(&previous, key)], block_size);
+                if segment.len() == block_size as usize {
+                    previous = FieldElement::from_bits(segment);
+                }
+                plaintext
+            }).collect()
+        }
+    };
+    Ok(bits_to_bytes(&plaintext))
+}
+
+/// Number of bytes of the initialization vector stored before the ciphertext, n bits rounded up to whole bytes.
+pub fn iv_bytes(mode: Mode, block_size: u32) -> usize {
+    if mode.has_iv() { block_size.div_ceil(8) as usize } else { 0 }
+}
+
+/// Encrypts the message with a random initialization vector, which is stored in the first [`iv_bytes`] bytes of the
+/// result.
+pub fn encrypt_message(cipher: &dyn Cipher, mode: Mode, key: &FieldElement, plaintext: &[u8]) -> Vec<u8> {
+    let block_size = cipher.block_size();
+    let iv = if mode.has_iv() { generate_random_bits(block_size) } else { FieldElement::ZERO };
+    let mut result = bits_to_bytes(&iv.to_bits(block_size));
+    result.truncate(iv_bytes(mode, block_size));
+    result.extend(encrypt(cipher, mode, key, &iv, plaintext));
+    result
+}
+
+/// Decrypts the result of [`encrypt_message`].
+pub fn decrypt_message(cipher: &dyn Cipher, mode: Mode, key: &FieldElement, data: &[u8]) -> Result<Vec<u8>> {
+    let block_size = cipher.block_size();
+    let iv_bytes = iv_bytes(mode, block_size);
+    if data.len() < iv_bytes {
+        return Err(Error::InvalidCiphertextLength { length: 8 * data.len(), block_size });
+    }
+    let iv = FieldElement::from_bits(&bytes_to_bits(&data[..iv_bytes])[..if mode.has_iv() { block_size as usize } else { 0 }]);
+    decrypt(cipher, mode, key, &iv, &data[iv_bytes..])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aes::aes::AES;
+    use crate::error::Error;
+    use crate::mimc_general::mimc_general::MiMCGe;
+    use crate::modes::modes::{decrypt, decrypt_message, encrypt, encrypt_message, Mode, unpad};
+    use crate::utils::helpers::{Cipher, FieldElement, generate_random_bits};
+
+    const MODES: [Mode; 5] = [Mode::ECB, Mode::CBC, Mode::CTR, Mode::OFB, Mode::CFB];
+
+    #[test]
+    fn decrypt_all_modes() {
+        let ciphers: [Box<dyn Cipher>; 6] = [Box::new(MiMCGe::new(3, 5, None).unwrap()), Box::new(MiMCGe::new(3, 17, None).unwrap()),
+            Box::new(MiMCGe::new(5, 33, None).unwrap()), Box::new(MiMCGe::new(7, 64, None).unwrap()), Box::new(MiMCGe::new(3, 127, None).unwrap()), Box::new(AES {})];
+        for cipher in ciphers {
+            let cipher = cipher.as_ref();
+            let block_size = cipher.block_size();
+            let (key, iv) = (generate_random_bits(block_size), generate_random_bits(block_size));
+            for mode in MODES {
+                for length in [0, 1, 2, 16, 17, 100] {
+                    let plaintext: Vec<u8> = (0..length).map(|i| (i * 37 + 11) as u8).collect();
+                    let ciphertext = encrypt(cipher, mode, &key, &iv, &plaintext);
+                    assert_eq!(decrypt(cipher, mode, &key, &iv, &ciphertext), Ok(plaintext), "{mode:?} n = {block_size} length {length}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ciphertext_length() {
+        let cipher = MiMCGe::new(3, 17, None).unwrap();
+        let (key, iv) = (generate_random_bits(17), generate_random_bits(17));
+        // 8 blocks of 17 bits are 17 bytes, the padding always adds at least one bit
+        assert_eq!(encrypt(&cipher, Mode::CBC, &key, &iv, &[0; 16]).len(), 17);
+        assert_eq!(encrypt(&cipher, Mode::ECB, &key, &iv, &[0; 17]).len(), 34);
+        assert_eq!(encrypt(&cipher, Mode::CFB, &key, &iv, &[0; 17]).len(), 17);
+        assert_eq!(decrypt(&cipher, Mode::ECB, &key, &iv, &[0; 16]), Err(Error::InvalidCiphertextLength { length: 128, block_size: 17 }));
+    }
+
+    #[test]
+    fn ecb_equal_blocks() {
+        let cipher = AES {};
+        let key = generate_random_bits(128);
+        let ciphertext = encrypt(&cipher, Mode::ECB, &key, &FieldElement::ZERO, &[7; 32]);
+        assert_eq!(ciphertext[..16], ciphertext[16..32]);
+        assert_eq!(ciphertext.len(), 48);
+    }
+
+    #[test]
+    fn cbc_first_block() {
+        let cipher = MiMCGe::new(7, 64, None).unwrap();
+        let (key, iv) = (generate_random_bits(64), generate_random_bits(64));
+        let plaintext = 0x0123_4567_89ab_cdefu64.to_be_bytes();
+        let expected = cipher.encrypt(&(FieldElement::new(0x0123_4567_89ab_cdef, 64) ^ iv), &key);
+        assert_eq!(encrypt(&cipher, Mode::CBC, &key, &iv, &plaintext)[..8], expected.value().low_u128().to_be_bytes()[8..]);
+    }
+
+    #[test]
+    fn ctr_key_stream() {
+        let cipher = MiMCGe::new(3, 33, None).unwrap();
+        let (key, iv) = (generate_random_bits(33), FieldElement::new((1 << 33) - 1, 33));
+        // Counter wraps around to zero after the first block, 64 bits of the message take 2 blocks of the key stream
+        let ciphertext = encrypt(&cipher, Mode::CTR, &key, &iv, &[0; 8]);
+        let key_stream = (cipher.encrypt(&iv, &key).value().low_u128() << 33) | cipher.encrypt(&FieldElement::ZERO, &key).value().low_u128();
+        assert_eq!(ciphertext, ((key_stream >> 2) as u64).to_be_bytes());
+    }
+
+    #[test]
+    fn wrong_key() {
+        let cipher = MiMCGe::new(5, 17, None).unwrap();
+        let (key, iv) = (generate_random_bits(17), generate_random_bits(17));
+        let ciphertext = encrypt(&cipher, Mode::CBC, &key, &iv, b"message");
+        let wrong_key = key.flip_bit(3);
+        assert_ne!(decrypt(&cipher, Mode::CBC, &wrong_key, &iv, &ciphertext), Ok(b"message".to_vec()));
+    }
+
+    #[test]
+    fn wrong_key_invalid_padding() {
+        let cipher = MiMCGe::with_fixed_round_constants(5, 17, None).unwrap();
+        let (key, wrong_key, iv) = (FieldElement::new(0x1b2c3, 17), FieldElement::new(0x1b2c2, 17), FieldElement::new(0x0f0f0, 17));
+        for mode in [Mode::ECB, Mode::CBC] {
+            let ciphertext = encrypt(&cipher, mode, &key, &iv, b"message");
+            assert_eq!(decrypt(&cipher, mode, &wrong_key, &iv, &ciphertext), Err(Error::InvalidPadding), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn padding_position() {
+        // lcm(5, 8) = 40 bits of padding at most, the message before the padding is whole bytes
+        let mut bits = vec![0; 80];
+        bits[40] = 1;
+        assert_eq!(unpad(bits.clone(), 5), Ok(vec![0; 40]));
+        assert_eq!(unpad(bits.clone(), 17), Ok(vec![0; 40]));
+        bits[40] = 0;
+        bits[32] = 1;
+        assert_eq!(unpad(bits.clone(), 5), Err(Error::InvalidPadding));
+        bits[32] = 0;
+        bits[41] = 1;
+        assert_eq!(unpad(bits.clone(), 5), Err(Error::InvalidPadding));
+        assert_eq!(unpad(vec![0; 80], 5), Err(Error::InvalidPadding));
+    }
+
+    #[test]
+    fn message_with_iv() {
+        let cipher = MiMCGe::new(5, 17, None).unwrap();
+        let key = generate_random_bits(17);
+        for mode in MODES {
+            let data = encrypt_message(&cipher, mode, &key, b"some data");
+            assert_eq!(decrypt_message(&cipher, mode, &key, &data), Ok(b"some data".to_vec()));
+        }
+        assert_eq!(encrypt_message(&cipher, Mode::CTR, &key, b"some data").len(), 3 + 9);
+        assert_eq!(decrypt_message(&cipher, Mode::CTR, &key, &[1, 2]), Err(Error::InvalidCiphertextLength { length: 16, block_size: 17 }));
+    }
+}
@@ -10,3 +10,11 @@ mod test_associativity;
 mod test_commutativity;
 #[cfg(test)]
 mod test_distributivity;
+#[cfg(test)]
+mod test_division;
+#[cfg(test)]
+mod test_constant_time;
+#[cfg(test)]
+mod test_trace;
+#[cfg(test)]
+mod test_g2p;
@@ -1,74 +1,104 @@
-use crate::utils::helpers::{add_finite_field, generate_random_bits, to_binary};
+use crate::utils::helpers::{add_finite_field, FieldElement, generate_random_bits};
 
 /// Identity: 0 + a = a in GF(2^n)
 #[test]
 fn finite_field_additive_identity_5() {
     let a = generate_random_bits(5);
-    assert_eq!(add_finite_field(&vec![0,0,0,0,0], &a), a)
+    assert_eq!(add_finite_field(&FieldElement::from_bits(&[0,0,0,0,0]), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_8() {
     let a = generate_random_bits(8);
-    assert_eq!(add_finite_field(&vec![0,0,0,0,0,0,0,0], &a), a)
+    assert_eq!(add_finite_field(&FieldElement::from_bits(&[0,0,0,0,0,0,0,0]), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_11() {
     let a = generate_random_bits(11);
-    assert_eq!(add_finite_field(&vec![0,0,0,0,0,0,0,0,0,0,0], &a), a)
+    assert_eq!(add_finite_field(&FieldElement::from_bits(&[0,0,0,0,0,0,0,0,0,0,0]), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_17() {
     let a = generate_random_bits(17);
-    assert_eq!(add_finite_field(&vec![0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0], &a), a)
+    assert_eq!(add_finite_field(&FieldElement::from_bits(&[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_31() {
     let a = generate_random_bits(31);
-    assert_eq!(add_finite_field(&to_binary(0, 31), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0, 31), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_33() {
     let a = generate_random_bits(33);
-    assert_eq!(add_finite_field(&to_binary(0, 33), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0, 33), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_47() {
     let a = generate_random_bits(47);
-    assert_eq!(add_finite_field(&to_binary(0, 47), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0, 47), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_61() {
     let a = generate_random_bits(61);
-    assert_eq!(add_finite_field(&to_binary(0,61), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0,61), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_83() {
     let a = generate_random_bits(83);
-    assert_eq!(add_finite_field(&to_binary(0, 83), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0, 83), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_101() {
     let a = generate_random_bits(101);
-    assert_eq!(add_finite_field(&to_binary(0, 101), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0, 101), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_125() {
     let a = generate_random_bits(125);
-    assert_eq!(add_finite_field(&to_binary(0,125), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0,125), &a), a)
 }
 
 #[test]
 fn finite_field_additive_identity_127() {
     let a = generate_random_bits(127);
-    assert_eq!(add_finite_field(&to_binary(0,127), &a), a)
+    assert_eq!(add_finite_field(&FieldElement::new(0,127), &a), a)
+}
+
+#[test]
+fn finite_field_additive_identity_128() {
+    let a = generate_random_bits(128);
+    assert_eq!(add_finite_field(&FieldElement::new(0,128), &a), a)
+}
+
+#[test]
+fn finite_field_additive_identity_129() {
+    let a = generate_random_bits(129);
+    assert_eq!(add_finite_field(&FieldElement::new(0,129), &a), a)
+}
+
+#[test]
+fn finite_field_additive_identity_255() {
+    let a = generate_random_bits(255);
+    assert_eq!(add_finite_field(&FieldElement::new(0,255), &a), a)
+}
+
+#[test]
+fn finite_field_additive_identity_256() {
+    let a = generate_random_bits(256);
+    assert_eq!(add_finite_field(&FieldElement::new(0,256), &a), a)
+}
+
+#[test]
+fn finite_field_additive_identity_257() {
+    let a = generate_random_bits(257);
+    assert_eq!(add_finite_field(&FieldElement::new(0,257), &a), a)
 }
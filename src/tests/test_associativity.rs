@@ -7,7 +7,7 @@ fn associativity_5() {
     let b = generate_random_bits(5);
     let c = generate_random_bits(5);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 5), &c, 5), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 5), 5));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 5).unwrap(), &c, 5).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 5).unwrap(), 5).unwrap());
 }
 
 #[test]
@@ -16,7 +16,7 @@ fn associativity_8() {
     let b = generate_random_bits(8);
     let c = generate_random_bits(8);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 8), &c, 8), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 8), 8));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 8).unwrap(), &c, 8).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 8).unwrap(), 8).unwrap());
 }
 
 #[test]
@@ -25,7 +25,7 @@ fn associativity_11() {
     let b = generate_random_bits(11);
     let c = generate_random_bits(11);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 11), &c, 11), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 11), 11));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 11).unwrap(), &c, 11).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 11).unwrap(), 11).unwrap());
 }
 
 #[test]
@@ -34,7 +34,7 @@ fn associativity_17() {
     let b = generate_random_bits(17);
     let c = generate_random_bits(17);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 17), &c, 17), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 17), 17));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 17).unwrap(), &c, 17).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 17).unwrap(), 17).unwrap());
 }
 
 #[test]
@@ -43,7 +43,7 @@ fn associativity_25() {
     let b = generate_random_bits(25);
     let c = generate_random_bits(25);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 25), &c, 25), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 25), 25));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 25).unwrap(), &c, 25).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 25).unwrap(), 25).unwrap());
 }
 
 #[test]
@@ -52,7 +52,7 @@ fn associativity_31() {
     let b = generate_random_bits(31);
     let c = generate_random_bits(31);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 31), &c, 31), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 31), 31));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 31).unwrap(), &c, 31).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 31).unwrap(), 31).unwrap());
 }
 
 
@@ -62,7 +62,7 @@ fn associativity_33() {
     let b = generate_random_bits(33);
     let c = generate_random_bits(33);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 33), &c, 33), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 33), 33));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 33).unwrap(), &c, 33).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 33).unwrap(), 33).unwrap());
 }
 
 #[test]
@@ -71,7 +71,7 @@ fn associativity_47() {
     let b = generate_random_bits(47);
     let c = generate_random_bits(47);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 47), &c, 47), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 47), 47));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 47).unwrap(), &c, 47).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 47).unwrap(), 47).unwrap());
 }
 
 #[test]
@@ -80,7 +80,7 @@ fn associativity_61() {
     let b = generate_random_bits(61);
     let c = generate_random_bits(61);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 61), &c, 61), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 61), 61));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 61).unwrap(), &c, 61).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 61).unwrap(), 61).unwrap());
 }
 
 #[test]
@@ -89,7 +89,7 @@ fn associativity_83() {
     let b = generate_random_bits(83);
     let c = generate_random_bits(83);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 83), &c, 83), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 83), 83));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 83).unwrap(), &c, 83).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 83).unwrap(), 83).unwrap());
 }
 
 #[test]
@@ -98,7 +98,7 @@ fn associativity_101() {
     let b = generate_random_bits(101);
     let c = generate_random_bits(101);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 101), &c, 101), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 101), 101));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 101).unwrap(), &c, 101).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 101).unwrap(), 101).unwrap());
 }
 
 #[test]
@@ -107,7 +107,7 @@ fn associativity_125() {
     let b = generate_random_bits(125);
     let c = generate_random_bits(125);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 125), &c, 125), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 125), 125));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 125).unwrap(), &c, 125).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 125).unwrap(), 125).unwrap());
 }
 
 #[test]
@@ -116,5 +116,49 @@ fn associativity_127() {
     let b = generate_random_bits(127);
     let c = generate_random_bits(127);
     assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
-    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 127), &c, 127), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 127), 127));
-}
\ No newline at end of file
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 127).unwrap(), &c, 127).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 127).unwrap(), 127).unwrap());
+}
+#[test]
+fn associativity_128() {
+    let a = generate_random_bits(128);
+    let b = generate_random_bits(128);
+    let c = generate_random_bits(128);
+    assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 128).unwrap(), &c, 128).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 128).unwrap(), 128).unwrap());
+}
+
+#[test]
+fn associativity_129() {
+    let a = generate_random_bits(129);
+    let b = generate_random_bits(129);
+    let c = generate_random_bits(129);
+    assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 129).unwrap(), &c, 129).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 129).unwrap(), 129).unwrap());
+}
+
+#[test]
+fn associativity_255() {
+    let a = generate_random_bits(255);
+    let b = generate_random_bits(255);
+    let c = generate_random_bits(255);
+    assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 255).unwrap(), &c, 255).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 255).unwrap(), 255).unwrap());
+}
+
+#[test]
+fn associativity_256() {
+    let a = generate_random_bits(256);
+    let b = generate_random_bits(256);
+    let c = generate_random_bits(256);
+    assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 256).unwrap(), &c, 256).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 256).unwrap(), 256).unwrap());
+}
+
+#[test]
+fn associativity_257() {
+    let a = generate_random_bits(257);
+    let b = generate_random_bits(257);
+    let c = generate_random_bits(257);
+    assert_eq!(add_finite_field(&add_finite_field(&a, &b), &c), add_finite_field(&a, &add_finite_field(&b, &c)));
+    assert_eq!(multiply_finite_field(&multiply_finite_field(&a, &b, 257).unwrap(), &c, 257).unwrap(), multiply_finite_field(&a, &multiply_finite_field(&b, &c, 257).unwrap(), 257).unwrap());
+}
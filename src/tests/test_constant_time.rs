@@ -0,0 +1,220 @@
+use std::cell::Cell;
+use std::ops::{BitAnd, BitXor, Shl, Shr};
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{_multiply_constant_time, _multiply_finite_field, BinaryField, Bits, FieldElement, generate_random_bits, irreducible_polynomial};
+use crate::utils::uint::Uint;
+
+thread_local! {
+    static OPERATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// u128 which counts every operation done on it, including comparisons, as these are what the branches depend on.
+#[derive(Clone, Copy, Debug, Default)]
+struct Counted(u128);
+
+fn count() {
+    OPERATIONS.with(|operations| operations.set(operations.get() + 1));
+}
+
+/// Number of operations done by `f`.
+fn operations(f: impl FnOnce()) -> usize {
+    OPERATIONS.with(|operations| operations.set(0));
+    f();
+    OPERATIONS.with(|operations| operations.get())
+}
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Self) -> bool {
+        count();
+        self.0 == other.0
+    }
+}
+
+impl From<u64> for Counted {
+    fn from(value: u64) -> Self {
+        Counted(value as u128)
+    }
+}
+
+impl BitAnd for Counted {
+    type Output = Counted;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        count();
+        Counted(self.0 & rhs.0)
+    }
+}
+
+impl BitXor for Counted {
+    type Output = Counted;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        count();
+        Counted(self.0 ^ rhs.0)
+    }
+}
+
+impl Shl<u32> for Counted {
+    type Output = Counted;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        count();
+        Counted(self.0 << rhs)
+    }
+}
+
+impl Shr<u32> for Counted {
+    type Output = Counted;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        count();
+        Counted(self.0 >> rhs)
+    }
+}
+
+impl Bits for Counted {
+    fn spread_low_bit(self) -> Self {
+        count();
+        Counted(self.0.spread_low_bit())
+    }
+}
+
+fn polynomial(block_size: u32) -> (Counted, Counted) {
+    (Counted(1 << (block_size - 1)), Counted(irreducible_polynomial(block_size).unwrap().low_u128()))
+}
+
+/// Operations of the constant-time multiplication of random a, b, of zeros and of all ones must be the same.
+fn assert_constant_multiplication(block_size: u32) {
+    let poly = polynomial(block_size);
+    let ones = Uint::mask(block_size).low_u128();
+    let (a, b) = (generate_random_bits(block_size).value().low_u128(), generate_random_bits(block_size).value().low_u128());
+    let expected = operations(|| { _multiply_constant_time(Counted(a), Counted(b), poly, block_size); });
+    for (a, b) in [(0, 0), (ones, ones), (1, ones), (ones, 0)] {
+        assert_eq!(operations(|| { _multiply_constant_time(Counted(a), Counted(b), poly, block_size); }), expected);
+    }
+}
+
+// Operation count of the constant-time multiplication does not depend on the operands
+#[test]
+fn constant_time_multiplication_operations_5() {
+    assert_constant_multiplication(5)
+}
+
+#[test]
+fn constant_time_multiplication_operations_8() {
+    assert_constant_multiplication(8)
+}
+
+#[test]
+fn constant_time_multiplication_operations_11() {
+    assert_constant_multiplication(11)
+}
+
+#[test]
+fn constant_time_multiplication_operations_17() {
+    assert_constant_multiplication(17)
+}
+
+#[test]
+fn constant_time_multiplication_operations_31() {
+    assert_constant_multiplication(31)
+}
+
+#[test]
+fn constant_time_multiplication_operations_61() {
+    assert_constant_multiplication(61)
+}
+
+#[test]
+fn constant_time_multiplication_operations_127() {
+    assert_constant_multiplication(127)
+}
+
+#[test]
+fn constant_time_multiplication_operations_128() {
+    assert_constant_multiplication(128)
+}
+
+// Operation count of the constant-time exponentiation depends only on the exponent
+#[test]
+fn constant_time_exponentiation_operations_127() {
+    let poly = polynomial(127);
+    let plan = ExponentiationPlan::new(Uint::mask(128).div_rem_u64(3).0);
+    let power = |a: u128| operations(|| { plan.apply(Counted(a), Counted(1), |x, y| _multiply_constant_time(x, y, poly, 127)); });
+    let expected = power(generate_random_bits(127).value().low_u128());
+    assert_eq!(power(0), expected);
+    assert_eq!(power(1), expected);
+    assert_eq!(power(Uint::mask(127).low_u128()), expected);
+}
+
+// Regular multiplication exits early, so its operation count leaks the operands
+#[test]
+fn variable_time_multiplication_operations_127() {
+    let poly = polynomial(127);
+    let small = operations(|| { _multiply_finite_field(Counted(1), Counted(3), poly); });
+    let large = operations(|| { _multiply_finite_field(Counted(1), Counted(u128::MAX >> 1), poly); });
+    assert!(small < large);
+}
+
+// ---------------------------------------------
+// Constant-time arithmetic gives the same results
+#[test]
+fn constant_time_multiplication_5() {
+    let (a, b) = (generate_random_bits(5), generate_random_bits(5));
+    assert_eq!(BinaryField::new(5).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(5).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_17() {
+    let (a, b) = (generate_random_bits(17), generate_random_bits(17));
+    assert_eq!(BinaryField::new(17).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(17).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_61() {
+    let (a, b) = (generate_random_bits(61), generate_random_bits(61));
+    assert_eq!(BinaryField::new(61).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(61).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_127() {
+    let (a, b) = (generate_random_bits(127), generate_random_bits(127));
+    assert_eq!(BinaryField::new(127).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(127).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_128() {
+    let (a, b) = (generate_random_bits(128), generate_random_bits(128));
+    assert_eq!(BinaryField::new(128).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(128).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_129() {
+    let (a, b) = (generate_random_bits(129), generate_random_bits(129));
+    assert_eq!(BinaryField::new(129).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(129).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_255() {
+    let (a, b) = (generate_random_bits(255), generate_random_bits(255));
+    assert_eq!(BinaryField::new(255).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(255).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_multiplication_257() {
+    let (a, b) = (generate_random_bits(257), generate_random_bits(257));
+    assert_eq!(BinaryField::new(257).unwrap().with_constant_time(true).unwrap().multiply(&a, &b), BinaryField::new(257).unwrap().multiply(&a, &b))
+}
+
+#[test]
+fn constant_time_power_255() {
+    let a = generate_random_bits(255);
+    assert_eq!(BinaryField::new(255).unwrap().with_constant_time(true).unwrap().power(&a, 5), BinaryField::new(255).unwrap().power(&a, 5))
+}
+
+#[test]
+fn constant_time_identity_17() {
+    let one = FieldElement::new(1, 17);
+    let a = generate_random_bits(17);
+    assert_eq!(BinaryField::new(17).unwrap().with_constant_time(true).unwrap().multiply(&a, &one), a)
+}
@@ -6,7 +6,7 @@ fn distributivity_5() {
     let a = generate_random_bits(5);
     let b = generate_random_bits(5);
     let c = generate_random_bits(5);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 5), add_finite_field(&multiply_finite_field(&a, &c, 5), &multiply_finite_field(&b, &c, 5)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 5).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 5).unwrap(), &multiply_finite_field(&b, &c, 5).unwrap()));
 }
 
 #[test]
@@ -14,7 +14,7 @@ fn distributivity_8() {
     let a = generate_random_bits(8);
     let b = generate_random_bits(8);
     let c = generate_random_bits(8);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 8), add_finite_field(&multiply_finite_field(&a, &c, 8), &multiply_finite_field(&b, &c, 8)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 8).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 8).unwrap(), &multiply_finite_field(&b, &c, 8).unwrap()));
 }
 
 #[test]
@@ -22,7 +22,7 @@ fn distributivity_11() {
     let a = generate_random_bits(11);
     let b = generate_random_bits(11);
     let c = generate_random_bits(11);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 11), add_finite_field(&multiply_finite_field(&a, &c, 11), &multiply_finite_field(&b, &c, 11)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 11).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 11).unwrap(), &multiply_finite_field(&b, &c, 11).unwrap()));
 }
 
 #[test]
@@ -30,7 +30,7 @@ fn distributivity_17() {
     let a = generate_random_bits(17);
     let b = generate_random_bits(17);
     let c = generate_random_bits(17);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 17), add_finite_field(&multiply_finite_field(&a, &c, 17), &multiply_finite_field(&b, &c, 17)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 17).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 17).unwrap(), &multiply_finite_field(&b, &c, 17).unwrap()));
 }
 
 #[test]
@@ -38,7 +38,7 @@ fn distributivity_25() {
     let a = generate_random_bits(25);
     let b = generate_random_bits(25);
     let c = generate_random_bits(25);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 25), add_finite_field(&multiply_finite_field(&a, &c, 25), &multiply_finite_field(&b, &c, 25)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 25).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 25).unwrap(), &multiply_finite_field(&b, &c, 25).unwrap()));
 }
 
 #[test]
@@ -46,7 +46,7 @@ fn distributivity_31() {
     let a = generate_random_bits(31);
     let b = generate_random_bits(31);
     let c = generate_random_bits(31);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 31), add_finite_field(&multiply_finite_field(&a, &c, 31), &multiply_finite_field(&b, &c, 31)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 31).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 31).unwrap(), &multiply_finite_field(&b, &c, 31).unwrap()));
 }
 
 #[test]
@@ -54,7 +54,7 @@ fn distributivity_33() {
     let a = generate_random_bits(33);
     let b = generate_random_bits(33);
     let c = generate_random_bits(33);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 33), add_finite_field(&multiply_finite_field(&a, &c, 33), &multiply_finite_field(&b, &c, 33)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 33).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 33).unwrap(), &multiply_finite_field(&b, &c, 33).unwrap()));
 }
 
 #[test]
@@ -62,7 +62,7 @@ fn distributivity_47() {
     let a = generate_random_bits(47);
     let b = generate_random_bits(47);
     let c = generate_random_bits(47);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 47), add_finite_field(&multiply_finite_field(&a, &c, 47), &multiply_finite_field(&b, &c, 47)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 47).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 47).unwrap(), &multiply_finite_field(&b, &c, 47).unwrap()));
 }
 
 #[test]
@@ -70,7 +70,7 @@ fn distributivity_61() {
     let a = generate_random_bits(61);
     let b = generate_random_bits(61);
     let c = generate_random_bits(61);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 61), add_finite_field(&multiply_finite_field(&a, &c, 61), &multiply_finite_field(&b, &c, 61)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 61).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 61).unwrap(), &multiply_finite_field(&b, &c, 61).unwrap()));
 }
 
 #[test]
@@ -78,7 +78,7 @@ fn distributivity_83() {
     let a = generate_random_bits(83);
     let b = generate_random_bits(83);
     let c = generate_random_bits(83);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 83), add_finite_field(&multiply_finite_field(&a, &c, 83), &multiply_finite_field(&b, &c, 83)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 83).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 83).unwrap(), &multiply_finite_field(&b, &c, 83).unwrap()));
 }
 
 #[test]
@@ -86,7 +86,7 @@ fn distributivity_101() {
     let a = generate_random_bits(101);
     let b = generate_random_bits(101);
     let c = generate_random_bits(101);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 101), add_finite_field(&multiply_finite_field(&a, &c, 101), &multiply_finite_field(&b, &c, 101)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 101).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 101).unwrap(), &multiply_finite_field(&b, &c, 101).unwrap()));
 }
 
 #[test]
@@ -94,7 +94,7 @@ fn distributivity_125() {
     let a = generate_random_bits(125);
     let b = generate_random_bits(125);
     let c = generate_random_bits(125);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 125), add_finite_field(&multiply_finite_field(&a, &c, 125), &multiply_finite_field(&b, &c, 125)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 125).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 125).unwrap(), &multiply_finite_field(&b, &c, 125).unwrap()));
 }
 
 #[test]
@@ -102,5 +102,45 @@ fn distributivity_127() {
     let a = generate_random_bits(127);
     let b = generate_random_bits(127);
     let c = generate_random_bits(127);
-    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 127), add_finite_field(&multiply_finite_field(&a, &c, 127), &multiply_finite_field(&b, &c, 127)));
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 127).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 127).unwrap(), &multiply_finite_field(&b, &c, 127).unwrap()));
+}
+
+#[test]
+fn distributivity_128() {
+    let a = generate_random_bits(128);
+    let b = generate_random_bits(128);
+    let c = generate_random_bits(128);
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 128).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 128).unwrap(), &multiply_finite_field(&b, &c, 128).unwrap()));
+}
+
+#[test]
+fn distributivity_129() {
+    let a = generate_random_bits(129);
+    let b = generate_random_bits(129);
+    let c = generate_random_bits(129);
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 129).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 129).unwrap(), &multiply_finite_field(&b, &c, 129).unwrap()));
+}
+
+#[test]
+fn distributivity_255() {
+    let a = generate_random_bits(255);
+    let b = generate_random_bits(255);
+    let c = generate_random_bits(255);
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 255).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 255).unwrap(), &multiply_finite_field(&b, &c, 255).unwrap()));
+}
+
+#[test]
+fn distributivity_256() {
+    let a = generate_random_bits(256);
+    let b = generate_random_bits(256);
+    let c = generate_random_bits(256);
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 256).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 256).unwrap(), &multiply_finite_field(&b, &c, 256).unwrap()));
+}
+
+#[test]
+fn distributivity_257() {
+    let a = generate_random_bits(257);
+    let b = generate_random_bits(257);
+    let c = generate_random_bits(257);
+    assert_eq!(multiply_finite_field(&add_finite_field(&a, &b), &c, 257).unwrap(), add_finite_field(&multiply_finite_field(&a, &c, 257).unwrap(), &multiply_finite_field(&b, &c, 257).unwrap()));
 }
@@ -0,0 +1,213 @@
+use crate::utils::helpers::{BinaryField, divide_finite_field, FieldElement, generate_random_bits, invert_finite_field, multiply_finite_field, square_multiply};
+use crate::utils::uint::Uint;
+
+/// Random non-zero element, as zero has no inverse.
+fn generate_non_zero(block_size: u32) -> FieldElement {
+    loop {
+        let a = generate_random_bits(block_size);
+        if a != FieldElement::ZERO {
+            return a;
+        }
+    }
+}
+
+// Tests a^-1 = a^(2^n - 2), as a^(2^n - 1) = 1 for every non-zero a in GF(2^n)
+#[test]
+fn finite_field_inverse_5() {
+    let a = generate_non_zero(5);
+    assert_eq!(invert_finite_field(&a, 5), square_multiply(&a, 2u128.pow(5) - 2, 5))
+}
+
+#[test]
+fn finite_field_inverse_8() {
+    let a = generate_non_zero(8);
+    assert_eq!(invert_finite_field(&a, 8), square_multiply(&a, 2u128.pow(8) - 2, 8))
+}
+
+#[test]
+fn finite_field_inverse_11() {
+    let a = generate_non_zero(11);
+    assert_eq!(invert_finite_field(&a, 11), square_multiply(&a, 2u128.pow(11) - 2, 11))
+}
+
+#[test]
+fn finite_field_inverse_17() {
+    let a = generate_non_zero(17);
+    assert_eq!(invert_finite_field(&a, 17), square_multiply(&a, 2u128.pow(17) - 2, 17))
+}
+
+#[test]
+fn finite_field_inverse_25() {
+    let a = generate_non_zero(25);
+    assert_eq!(invert_finite_field(&a, 25), square_multiply(&a, 2u128.pow(25) - 2, 25))
+}
+
+#[test]
+fn finite_field_inverse_31() {
+    let a = generate_non_zero(31);
+    assert_eq!(invert_finite_field(&a, 31), square_multiply(&a, 2u128.pow(31) - 2, 31))
+}
+
+#[test]
+fn finite_field_inverse_33() {
+    let a = generate_non_zero(33);
+    assert_eq!(invert_finite_field(&a, 33), square_multiply(&a, 2u128.pow(33) - 2, 33))
+}
+
+#[test]
+fn finite_field_inverse_47() {
+    let a = generate_non_zero(47);
+    assert_eq!(invert_finite_field(&a, 47), square_multiply(&a, 2u128.pow(47) - 2, 47))
+}
+
+#[test]
+fn finite_field_inverse_61() {
+    let a = generate_non_zero(61);
+    assert_eq!(invert_finite_field(&a, 61), square_multiply(&a, 2u128.pow(61) - 2, 61))
+}
+
+#[test]
+fn finite_field_inverse_83() {
+    let a = generate_non_zero(83);
+    assert_eq!(invert_finite_field(&a, 83), square_multiply(&a, 2u128.pow(83) - 2, 83))
+}
+
+#[test]
+fn finite_field_inverse_101() {
+    let a = generate_non_zero(101);
+    assert_eq!(invert_finite_field(&a, 101), square_multiply(&a, 2u128.pow(101) - 2, 101))
+}
+
+#[test]
+fn finite_field_inverse_125() {
+    let a = generate_non_zero(125);
+    assert_eq!(invert_finite_field(&a, 125), square_multiply(&a, 2u128.pow(125) - 2, 125))
+}
+
+#[test]
+fn finite_field_inverse_127() {
+    let a = generate_non_zero(127);
+    assert_eq!(invert_finite_field(&a, 127), square_multiply(&a, 2u128.pow(127) - 2, 127))
+}
+
+#[test]
+fn finite_field_inverse_128() {
+    let a = generate_non_zero(128);
+    assert_eq!(invert_finite_field(&a, 128), square_multiply(&a, u128::MAX - 1, 128))
+}
+
+// ---------------------------------------------
+// Block sizes above 128, 2^n - 2 does not fit into u128, so the wide exponentiation is used
+#[test]
+fn finite_field_inverse_129() {
+    let a = generate_non_zero(129);
+    assert_eq!(invert_finite_field(&a, 129).unwrap(), BinaryField::new(129).unwrap().power_wide(&a, Uint::mask(129) - Uint::ONE))
+}
+
+#[test]
+fn finite_field_inverse_255() {
+    let a = generate_non_zero(255);
+    assert_eq!(invert_finite_field(&a, 255).unwrap(), BinaryField::new(255).unwrap().power_wide(&a, Uint::mask(255) - Uint::ONE))
+}
+
+#[test]
+fn finite_field_inverse_256() {
+    let a = generate_non_zero(256);
+    assert_eq!(invert_finite_field(&a, 256).unwrap(), BinaryField::new(256).unwrap().power_wide(&a, Uint::mask(256) - Uint::ONE))
+}
+
+#[test]
+fn finite_field_inverse_257() {
+    let a = generate_non_zero(257);
+    assert_eq!(invert_finite_field(&a, 257).unwrap(), BinaryField::new(257).unwrap().power_wide(&a, Uint::mask(257) - Uint::ONE))
+}
+
+// ---------------------------------------------
+// Tests (a / b) * b = a
+#[test]
+fn finite_field_division_5() {
+    let a = generate_random_bits(5);
+    let b = generate_non_zero(5);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 5).unwrap(), &b, 5).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_8() {
+    let a = generate_random_bits(8);
+    let b = generate_non_zero(8);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 8).unwrap(), &b, 8).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_17() {
+    let a = generate_random_bits(17);
+    let b = generate_non_zero(17);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 17).unwrap(), &b, 17).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_61() {
+    let a = generate_random_bits(61);
+    let b = generate_non_zero(61);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 61).unwrap(), &b, 61).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_127() {
+    let a = generate_random_bits(127);
+    let b = generate_non_zero(127);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 127).unwrap(), &b, 127).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_128() {
+    let a = generate_random_bits(128);
+    let b = generate_non_zero(128);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 128).unwrap(), &b, 128).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_129() {
+    let a = generate_random_bits(129);
+    let b = generate_non_zero(129);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 129).unwrap(), &b, 129).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_255() {
+    let a = generate_random_bits(255);
+    let b = generate_non_zero(255);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 255).unwrap(), &b, 255).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_256() {
+    let a = generate_random_bits(256);
+    let b = generate_non_zero(256);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 256).unwrap(), &b, 256).unwrap(), a)
+}
+
+#[test]
+fn finite_field_division_257() {
+    let a = generate_random_bits(257);
+    let b = generate_non_zero(257);
+    assert_eq!(multiply_finite_field(&divide_finite_field(&a, &b, 257).unwrap(), &b, 257).unwrap(), a)
+}
+
+// ---------------------------------------------
+// Zero has no inverse
+#[test]
+fn finite_field_inverse_zero() {
+    assert!(invert_finite_field(&FieldElement::ZERO, 8).is_err());
+    assert!(invert_finite_field(&FieldElement::ZERO, 257).is_err());
+}
+
+#[test]
+fn finite_field_division_by_zero() {
+    assert!(divide_finite_field(&generate_random_bits(127), &FieldElement::ZERO, 127).is_err());
+}
+
+#[test]
+fn finite_field_inverse_one() {
+    assert_eq!(invert_finite_field(&FieldElement::new(1, 255), 255), Ok(FieldElement::new(1, 255)));
+}
@@ -0,0 +1,144 @@
+use crate::error::Error;
+use crate::utils::g2p_field;
+use crate::utils::helpers::{Backend, BinaryField, FieldElement, generate_random_bits, invert_finite_field, multiply_finite_field, square_multiply};
+use rand::{Rng, thread_rng};
+
+const SAMPLES: usize = 1000;
+
+/// Random elements multiplied natively and by g2p must be the same
+fn assert_multiplication(block_size: u32) {
+    for _ in 0..SAMPLES {
+        let (a, b) = (generate_random_bits(block_size), generate_random_bits(block_size));
+        let expected = g2p_field::multiply(a.value().low_u128(), b.value().low_u128(), block_size);
+        assert_eq!(multiply_finite_field(&a, &b, block_size).unwrap(), FieldElement::new(expected, block_size));
+    }
+}
+
+/// Random elements raised to random exponents natively and by g2p must be the same
+fn assert_exponentiation(block_size: u32) {
+    for _ in 0..SAMPLES {
+        let a = generate_random_bits(block_size);
+        let exponent = thread_rng().gen_range(0..1 << 20);
+        let expected = g2p_field::power(a.value().low_u128(), exponent, block_size);
+        assert_eq!(square_multiply(&a, exponent as u128, block_size).unwrap(), FieldElement::new(expected, block_size));
+    }
+}
+
+/// Native inverse and g2p inverse must be the same for every non-zero element (first 2^12 - 1 of them for larger fields)
+fn assert_inversion(block_size: u32) {
+    for a in 1..1u128 << block_size.min(12) {
+        let expected = g2p_field::invert(a, block_size);
+        assert_eq!(invert_finite_field(&FieldElement::new(a, block_size), block_size), Ok(FieldElement::new(expected, block_size)));
+    }
+}
+
+/// Cipher field with g2p backend must give the same results as the native one
+fn assert_backend(block_size: u32) {
+    let native = BinaryField::new(block_size).unwrap();
+    let g2p = BinaryField::new(block_size).unwrap().with_backend(Backend::G2p).unwrap();
+    for _ in 0..SAMPLES {
+        let (a, b) = (generate_random_bits(block_size), generate_random_bits(block_size));
+        assert_eq!(g2p.multiply(&a, &b), native.multiply(&a, &b));
+        assert_eq!(g2p.power(&a, 7), native.power(&a, 7));
+    }
+}
+
+// Cross-validation of multiplication against g2p
+#[test]
+fn g2p_multiplication_5() {
+    assert_multiplication(5)
+}
+
+#[test]
+fn g2p_multiplication_8() {
+    assert_multiplication(8)
+}
+
+#[test]
+fn g2p_multiplication_11() {
+    assert_multiplication(11)
+}
+
+#[test]
+fn g2p_multiplication_17() {
+    assert_multiplication(17)
+}
+
+// ---------------------------------------------
+// Cross-validation of exponentiation against g2p
+#[test]
+fn g2p_exponentiation_5() {
+    assert_exponentiation(5)
+}
+
+#[test]
+fn g2p_exponentiation_8() {
+    assert_exponentiation(8)
+}
+
+#[test]
+fn g2p_exponentiation_11() {
+    assert_exponentiation(11)
+}
+
+#[test]
+fn g2p_exponentiation_17() {
+    assert_exponentiation(17)
+}
+
+// ---------------------------------------------
+// Cross-validation of inversion against g2p
+#[test]
+fn g2p_inversion_5() {
+    assert_inversion(5)
+}
+
+#[test]
+fn g2p_inversion_8() {
+    assert_inversion(8)
+}
+
+#[test]
+fn g2p_inversion_11() {
+    assert_inversion(11)
+}
+
+#[test]
+fn g2p_inversion_17() {
+    assert_inversion(17)
+}
+
+// ---------------------------------------------
+// g2p backend of BinaryField
+#[test]
+fn g2p_backend_5() {
+    assert_backend(5)
+}
+
+#[test]
+fn g2p_backend_8() {
+    assert_backend(8)
+}
+
+#[test]
+fn g2p_backend_11() {
+    assert_backend(11)
+}
+
+#[test]
+fn g2p_backend_17() {
+    assert_backend(17)
+}
+
+#[test]
+fn g2p_backend_unsupported() {
+    assert_eq!(BinaryField::new(13).unwrap().with_backend(Backend::G2p), Err(Error::BackendUnavailable(13)));
+}
+
+#[test]
+fn g2p_backend_not_constant_time() {
+    let field = BinaryField::new(17).unwrap();
+    assert_eq!(field.with_constant_time(true).unwrap().with_backend(Backend::G2p), Err(Error::BackendNotConstantTime));
+    assert_eq!(field.with_backend(Backend::G2p).unwrap().with_constant_time(true), Err(Error::BackendNotConstantTime));
+    assert!(field.with_backend(Backend::G2p).unwrap().with_constant_time(false).is_ok());
+}
@@ -1,8 +1,10 @@
-use crate::utils::helpers::{add_finite_field, multiply_finite_field, square_multiply, to_binary, to_decimal};
+use crate::utils::helpers::{add_finite_field, FieldElement, fixed_round_constants, irreducible_polynomial, multiply_finite_field, square_multiply, to_binary, to_decimal};
+use crate::utils::polynomials::is_irreducible;
+use crate::utils::uint::Uint;
 
 #[test]
 fn convert_to_decimal() {
-    assert_eq!(to_decimal(&vec![1,1,0,1,0,1]), 53);
+    assert_eq!(to_decimal(&[1,1,0,1,0,1]), 53);
 }
 
 #[test]
@@ -10,77 +12,115 @@ fn convert_to_binary() {
     assert_eq!(to_binary(53, 6), vec![1,1,0,1,0,1]);
 }
 
+#[test]
+fn convert_field_element_bits() {
+    let a = FieldElement::from_bits(&[1,1,0,1,0,1]);
+    assert_eq!(a, FieldElement::new(53, 6));
+    assert_eq!(a.to_bits(6), vec![1,1,0,1,0,1]);
+    assert_eq!(FieldElement::new(0b1110101, 6), a); // Bits above block size are discarded
+}
+
 #[test]
 fn add_over_finite_field() {
-    let a = vec![1,0,1,1,0]; // 22
-    let b = vec![0,0,0,0,0]; // 0
-    assert_eq!(add_finite_field(&a, &b), vec![1,0,1,1,0]); // 22
+    let a = FieldElement::from_bits(&[1,0,1,1,0]); // 22
+    let b = FieldElement::from_bits(&[0,0,0,0,0]); // 0
+    assert_eq!(add_finite_field(&a, &b), FieldElement::from_bits(&[1,0,1,1,0])); // 22
 }
 
 #[test]
 fn add_over_finite_field_17() {
-    let a = vec![1,0,1,1,0,1,0,0,0,1,1,1,0,1,1,0,1]; // 92397
-    let b = vec![1,0,0,0,1,0,0,0,0,1,0,1,1,1,0,0,0]; // 69816
-    assert_eq!(add_finite_field(&a, &b), vec![0,0,1,1,1,1,0,0,0,0,1,0,1,0,1,0,1]); // 30805
+    let a = FieldElement::from_bits(&[1,0,1,1,0,1,0,0,0,1,1,1,0,1,1,0,1]); // 92397
+    let b = FieldElement::from_bits(&[1,0,0,0,1,0,0,0,0,1,0,1,1,1,0,0,0]); // 69816
+    assert_eq!(add_finite_field(&a, &b), FieldElement::from_bits(&[0,0,1,1,1,1,0,0,0,0,1,0,1,0,1,0,1])); // 30805
 }
 
 #[test]
 fn add_over_finite_field_25() {
-    let a = vec![0,0,0,0,0,1,0,0,0,1,1,1,1,1,0,0,1,1,1,1,1,1,1,1,1]; // 588287
-    let b = vec![1,0,1,0,1,1,1,0,0,0,0,1,1,1,1,0,0,0,1,1,1,0,1,1,0]; // 22822006
-    assert_eq!(add_finite_field(&a, &b), vec![1,0,1,0,1,0,1,0,0,1,1,0,0,0,1,0,1,1,0,0,0,1,0,0,1]); // 22332809
+    let a = FieldElement::from_bits(&[0,0,0,0,0,1,0,0,0,1,1,1,1,1,0,0,1,1,1,1,1,1,1,1,1]); // 588287
+    let b = FieldElement::from_bits(&[1,0,1,0,1,1,1,0,0,0,0,1,1,1,1,0,0,0,1,1,1,0,1,1,0]); // 22822006
+    assert_eq!(add_finite_field(&a, &b), FieldElement::from_bits(&[1,0,1,0,1,0,1,0,0,1,1,0,0,0,1,0,1,1,0,0,0,1,0,0,1])); // 22332809
 }
 
 #[test]
 fn multiply_over_finite_field_zero() {
-    let a = vec![1,1,0,1,0]; // 26
-    let b = vec![0,0,0,0,0]; // 0
-    assert_eq!(multiply_finite_field(&a, &b, 5), vec![0,0,0,0,0]); // 0
+    let a = FieldElement::from_bits(&[1,1,0,1,0]); // 26
+    let b = FieldElement::from_bits(&[0,0,0,0,0]); // 0
+    assert_eq!(multiply_finite_field(&a, &b, 5).unwrap(), FieldElement::from_bits(&[0,0,0,0,0])); // 0
 }
 
 #[test]
 fn multiply_over_finite_field_one() {
-    let a = vec![1,1,0,1,0]; // 26
-    let b = vec![0,0,0,0,1]; // 1
-    assert_eq!(multiply_finite_field(&a, &b, 5), vec![1,1,0,1,0]); // 26
+    let a = FieldElement::from_bits(&[1,1,0,1,0]); // 26
+    let b = FieldElement::from_bits(&[0,0,0,0,1]); // 1
+    assert_eq!(multiply_finite_field(&a, &b, 5).unwrap(), FieldElement::from_bits(&[1,1,0,1,0])); // 26
 }
 
 #[test]
 fn multiply_over_finite_field_5() {
-    let a = vec![1,1,0,1,0]; // 26
-    let b = vec![1,0,0,0,0]; // 16
-    assert_eq!(multiply_finite_field(&a, &b, 5), vec![1,1,1,0,0]); // 28
+    let a = FieldElement::from_bits(&[1,1,0,1,0]); // 26
+    let b = FieldElement::from_bits(&[1,0,0,0,0]); // 16
+    assert_eq!(multiply_finite_field(&a, &b, 5).unwrap(), FieldElement::from_bits(&[1,1,1,0,0])); // 28
 }
 
 #[test]
 fn multiply_over_finite_field_17() {
-    let a = vec![1,0,1,1,0,1,0,0,0,1,1,1,0,1,1,0,1]; // 92397
-    let b = vec![1,0,0,0,1,0,0,0,0,1,0,1,1,1,0,0,0]; // 69816
-    assert_eq!(multiply_finite_field(&a, &b, 17), vec![0,0,0,1,1,0,1,1,0,0,1,1,0,0,0,0,1]); // 13921
+    let a = FieldElement::from_bits(&[1,0,1,1,0,1,0,0,0,1,1,1,0,1,1,0,1]); // 92397
+    let b = FieldElement::from_bits(&[1,0,0,0,1,0,0,0,0,1,0,1,1,1,0,0,0]); // 69816
+    assert_eq!(multiply_finite_field(&a, &b, 17).unwrap(), FieldElement::from_bits(&[0,0,0,1,1,0,1,1,0,0,1,1,0,0,0,0,1])); // 13921
 }
 
 #[test]
 fn multiply_over_finite_field_25() {
-    let a = vec![0,0,0,0,0,1,0,0,0,1,1,1,1,1,0,0,1,1,1,1,1,1,1,1,1]; // 588287
-    let b = vec![1,0,1,0,1,1,1,0,0,0,0,1,1,1,1,0,0,0,1,1,1,0,1,1,0]; // 22822006
-    assert_eq!(multiply_finite_field(&a, &b, 25), vec![0,0,1,1,0,0,0,1,0,1,1,0,0,1,0,0,0,0,0,1,1,1,0,0,0]); // 6473784
+    let a = FieldElement::from_bits(&[0,0,0,0,0,1,0,0,0,1,1,1,1,1,0,0,1,1,1,1,1,1,1,1,1]); // 588287
+    let b = FieldElement::from_bits(&[1,0,1,0,1,1,1,0,0,0,0,1,1,1,1,0,0,0,1,1,1,0,1,1,0]); // 22822006
+    assert_eq!(multiply_finite_field(&a, &b, 25).unwrap(), FieldElement::from_bits(&[0,0,1,1,0,0,0,1,0,1,1,0,0,1,0,0,0,0,0,1,1,1,0,0,0])); // 6473784
 }
 
 #[test]
 fn multiply_over_finite_field_31() {
-    let a = vec![0,0,0,0,1,0,0,1,0,1,1,0,0,1,1,1,0,0,1,0,0,1,1,0,1,0,1,1,0,0,1]; // 78877529
-    let b = vec![1,0,1,0,0,0,0,1,0,1,1,0,1,0,0,0,0,1,1,0,0,1,1,1,0,1,1,0,1,1,1]; // 1353986999
-    assert_eq!(multiply_finite_field(&a, &b, 31), vec![1,1,1,1,1,1,1,1,0,1,1,1,0,0,1,0,1,1,1,0,0,0,0,1,0,0,1,0,1,0,1]); // 2142859413
+    let a = FieldElement::from_bits(&[0,0,0,0,1,0,0,1,0,1,1,0,0,1,1,1,0,0,1,0,0,1,1,0,1,0,1,1,0,0,1]); // 78877529
+    let b = FieldElement::from_bits(&[1,0,1,0,0,0,0,1,0,1,1,0,1,0,0,0,0,1,1,0,0,1,1,1,0,1,1,0,1,1,1]); // 1353986999
+    assert_eq!(multiply_finite_field(&a, &b, 31).unwrap(), FieldElement::from_bits(&[1,1,1,1,1,1,1,1,0,1,1,1,0,0,1,0,1,1,1,0,0,0,0,1,0,0,1,0,1,0,1])); // 2142859413
 }
 
 #[test]
 fn square_over_finite_field_17() {
-    let a = vec![0,1,0,1,1,1,0,0,0,0,0,1,0,0,1,1,0]; // 47142
-    assert_eq!(square_multiply(&a, 5, 17), vec![0,0,1,1,1,1,0,1,1,1,1,1,1,1,0,0,1]); // 31737
+    let a = FieldElement::from_bits(&[0,1,0,1,1,1,0,0,0,0,0,1,0,0,1,1,0]); // 47142
+    assert_eq!(square_multiply(&a, 5, 17).unwrap(), FieldElement::from_bits(&[0,0,1,1,1,1,0,1,1,1,1,1,1,1,0,0,1])); // 31737
 }
 
 #[test]
 fn square_over_finite_field_25() {
-    let a = vec![0,1,0,1,1,1,0,0,0,0,0,1,0,0,1,1,0,1,0,1,1,1,0,1,0]; // 12068538
-    assert_eq!(square_multiply(&a, 5135, 25), vec![1,0,1,1,1,1,1,0,1,0,1,1,1,1,1,0,0,0,0,0,1,1,1,1,1]); // 25000991
+    let a = FieldElement::from_bits(&[0,1,0,1,1,1,0,0,0,0,0,1,0,0,1,1,0,1,0,1,1,1,0,1,0]); // 12068538
+    assert_eq!(square_multiply(&a, 5135, 25).unwrap(), FieldElement::from_bits(&[1,0,1,1,1,1,1,0,1,0,1,1,1,1,1,0,0,0,0,0,1,1,1,1,1])); // 25000991
+}
+
+#[test]
+fn multiply_over_finite_field_128() {
+    // x^127 * x = x^128 = x^7 + x^2 + x + 1 with the GCM polynomial
+    assert_eq!(multiply_finite_field(&FieldElement::new(1 << 127, 128), &FieldElement::new(2, 128), 128).unwrap(), FieldElement::new(0x87, 128));
+}
+
+#[test]
+fn multiply_over_finite_field_257() {
+    // x^256 * x^2 = x^258 = x^13 + x with x^257 + x^12 + 1
+    let a = FieldElement::from_uint(Uint::ONE << 256, 257);
+    assert_eq!(multiply_finite_field(&a, &FieldElement::new(4, 257), 257).unwrap(), FieldElement::new(0x2002, 257));
+}
+
+#[test]
+fn predefined_polynomials_irreducible() {
+    for block_size in [5, 8, 11, 17, 25, 31, 33, 47, 61, 83, 101, 125, 127, 128, 129, 255, 256, 257] {
+        assert!(is_irreducible(irreducible_polynomial(block_size).unwrap()), "Polynomial for {block_size} is reducible");
+    }
+}
+
+#[test]
+fn fixed_round_constants_are_reproducible() {
+    let constants = fixed_round_constants(20, 127);
+    assert_eq!(constants, fixed_round_constants(20, 127));
+    assert_eq!(constants[0], FieldElement::ZERO);
+    assert!(constants[1..].iter().all(|c| c.value() < Uint::ONE << 127 && !c.value().is_zero()));
+    // Shorter list is a prefix of the longer one
+    assert_eq!(fixed_round_constants(5, 127), constants[..5]);
 }
@@ -1,64 +1,64 @@
-use crate::utils::helpers::{generate_random_bits, square_multiply, to_binary, to_decimal};
+use crate::utils::helpers::{FieldElement, generate_random_bits, square_multiply};
 
 // Tests identity: for 1^n = 1 for any n
 #[test]
 fn finite_field_multiplicative_identity_5() {
-    assert_eq!(square_multiply(&vec![0,0,0,0,1], 5,5), vec![0,0,0,0,1]);
+    assert_eq!(square_multiply(&FieldElement::from_bits(&[0,0,0,0,1]), 5,5).unwrap(), FieldElement::from_bits(&[0,0,0,0,1]));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_8() {
-    assert_eq!(square_multiply(&vec![0,0,0,0,0,0,0,1], 5,8), vec![0,0,0,0,0,0,0,1]);
+    assert_eq!(square_multiply(&FieldElement::from_bits(&[0,0,0,0,0,0,0,1]), 5,8).unwrap(), FieldElement::from_bits(&[0,0,0,0,0,0,0,1]));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_11() {
-    assert_eq!(square_multiply(&vec![0,0,0,0,0,0,0,0,0,0,1], 5,11), vec![0,0,0,0,0,0,0,0,0,0,1]);
+    assert_eq!(square_multiply(&FieldElement::from_bits(&[0,0,0,0,0,0,0,0,0,0,1]), 5,11).unwrap(), FieldElement::from_bits(&[0,0,0,0,0,0,0,0,0,0,1]));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_17() {
-    assert_eq!(square_multiply(&vec![0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1], 5,17), vec![0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1]);
+    assert_eq!(square_multiply(&FieldElement::from_bits(&[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1]), 5,17).unwrap(), FieldElement::from_bits(&[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1]));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_31() {
-    assert_eq!(square_multiply(&to_binary(1,31), 5,31), to_binary(1, 31));
+    assert_eq!(square_multiply(&FieldElement::new(1,31), 5,31).unwrap(), FieldElement::new(1, 31));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_33() {
-    assert_eq!(square_multiply(&to_binary(1,33), 5,33), to_binary(1, 33));
+    assert_eq!(square_multiply(&FieldElement::new(1,33), 5,33).unwrap(), FieldElement::new(1, 33));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_47() {
-    assert_eq!(square_multiply(&to_binary(1,47), 5,47), to_binary(1, 47));
+    assert_eq!(square_multiply(&FieldElement::new(1,47), 5,47).unwrap(), FieldElement::new(1, 47));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_61() {
-    assert_eq!(square_multiply(&to_binary(1,61), 5,61), to_binary(1, 61));
+    assert_eq!(square_multiply(&FieldElement::new(1,61), 5,61).unwrap(), FieldElement::new(1, 61));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_83() {
-    assert_eq!(square_multiply(&to_binary(1,83), 5,83), to_binary(1, 83));
+    assert_eq!(square_multiply(&FieldElement::new(1,83), 5,83).unwrap(), FieldElement::new(1, 83));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_101() {
-    assert_eq!(square_multiply(&to_binary(1,101), 5,101), to_binary(1, 101));
+    assert_eq!(square_multiply(&FieldElement::new(1,101), 5,101).unwrap(), FieldElement::new(1, 101));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_125() {
-    assert_eq!(square_multiply(&to_binary(1,125), 5,125), to_binary(1, 125));
+    assert_eq!(square_multiply(&FieldElement::new(1,125), 5,125).unwrap(), FieldElement::new(1, 125));
 }
 
 #[test]
 fn finite_field_multiplicative_identity_127() {
-    assert_eq!(square_multiply(&to_binary(1,127), 5,127), to_binary(1, 127));
+    assert_eq!(square_multiply(&FieldElement::new(1,127), 5,127).unwrap(), FieldElement::new(1, 127));
 }
 
 // ---------------------------------------------
@@ -66,71 +66,135 @@ fn finite_field_multiplicative_identity_127() {
 #[test]
 fn finite_field_multiplicative_inverse_5() {
     let a = generate_random_bits(5);
-    assert_eq!(square_multiply(&a, 2u128.pow(5), 5), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(5), 5).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_8() {
     let a = generate_random_bits(8);
-    assert_eq!(square_multiply(&a, 2u128.pow(8), 8), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(8), 8).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_11() {
     let a = generate_random_bits(11);
-    assert_eq!(square_multiply(&a, 2u128.pow(11), 11), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(11), 11).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_17() {
     let a = generate_random_bits(17);
-    assert_eq!(square_multiply(&a, 2u128.pow(17), 17), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(17), 17).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_31() {
     let a = generate_random_bits(31);
-    assert_eq!(square_multiply(&a, 2u128.pow(31), 31), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(31), 31).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_33() {
     let a = generate_random_bits(33);
-    assert_eq!(square_multiply(&a, 2u128.pow(33), 33), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(33), 33).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_47() {
     let a = generate_random_bits(47);
-    assert_eq!(square_multiply(&a, 2u128.pow(47), 47), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(47), 47).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_61() {
     let a = generate_random_bits(61);
-    assert_eq!(square_multiply(&a, 2u128.pow(61), 61), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(61), 61).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_83() {
     let a = generate_random_bits(83);
-    assert_eq!(square_multiply(&a, 2u128.pow(83), 83), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(83), 83).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_101() {
     let a = generate_random_bits(101);
-    assert_eq!(square_multiply(&a, 2u128.pow(101), 101), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(101), 101).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_125() {
     let a = generate_random_bits(125);
-    assert_eq!(square_multiply(&a, 2u128.pow(125), 125), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(125), 125).unwrap(), a)
 }
 
 #[test]
 fn finite_field_multiplicative_inverse_127() {
     let a = generate_random_bits(127);
-    assert_eq!(square_multiply(&a, 2u128.pow(127), 127), a)
+    assert_eq!(square_multiply(&a, 2u128.pow(127), 127).unwrap(), a)
+}
+
+// ---------------------------------------------
+// Block sizes without predefined polynomial, x^q = x in GF(q) must hold for generated polynomials as well
+#[test]
+fn finite_field_multiplicative_inverse_generated_13() {
+    let a = generate_random_bits(13);
+    assert_eq!(square_multiply(&a, 2u128.pow(13), 13).unwrap(), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_generated_19() {
+    let a = generate_random_bits(19);
+    assert_eq!(square_multiply(&a, 2u128.pow(19), 19).unwrap(), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_generated_23() {
+    let a = generate_random_bits(23);
+    assert_eq!(square_multiply(&a, 2u128.pow(23), 23).unwrap(), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_generated_29() {
+    let a = generate_random_bits(29);
+    assert_eq!(square_multiply(&a, 2u128.pow(29), 29).unwrap(), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_generated_113() {
+    let a = generate_random_bits(113);
+    assert_eq!(square_multiply(&a, 2u128.pow(113), 113).unwrap(), a)
+}
+
+// ---------------------------------------------
+// Block sizes above 127, 2^n does not fit into the exponent, so x^(2^n) = x is checked by squaring n times
+#[test]
+fn finite_field_multiplicative_inverse_128() {
+    let a = generate_random_bits(128);
+    assert_eq!((0..128).fold(a, |x, _| square_multiply(&x, 2, 128).unwrap()), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_129() {
+    let a = generate_random_bits(129);
+    assert_eq!((0..129).fold(a, |x, _| square_multiply(&x, 2, 129).unwrap()), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_255() {
+    let a = generate_random_bits(255);
+    assert_eq!((0..255).fold(a, |x, _| square_multiply(&x, 2, 255).unwrap()), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_256() {
+    let a = generate_random_bits(256);
+    assert_eq!((0..256).fold(a, |x, _| square_multiply(&x, 2, 256).unwrap()), a)
+}
+
+#[test]
+fn finite_field_multiplicative_inverse_257() {
+    let a = generate_random_bits(257);
+    assert_eq!((0..257).fold(a, |x, _| square_multiply(&x, 2, 257).unwrap()), a)
 }
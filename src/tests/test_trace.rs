@@ -0,0 +1,441 @@
+use crate::error::Error;
+use crate::utils::helpers::{add_finite_field, FieldElement, generate_random_bits, half_trace_finite_field, multiply_finite_field, solve_quadratic_finite_field, sqrt_finite_field, trace_finite_field};
+
+/// Tr(a) = a + a^2 + a^4 + ... + a^(2^(n-1)), Tr(a + b) = Tr(a) + Tr(b), Tr(a^2) = Tr(a) and Tr(1) = n mod 2
+fn assert_trace(block_size: u32) {
+    let (a, b) = (generate_random_bits(block_size), generate_random_bits(block_size));
+    let trace = |x: &FieldElement| trace_finite_field(x, block_size).unwrap();
+    let (mut conjugate, mut sum) = (a, a);
+    for _ in 1..block_size {
+        conjugate = multiply_finite_field(&conjugate, &conjugate, block_size).unwrap();
+        sum = add_finite_field(&sum, &conjugate);
+    }
+    assert_eq!(sum, FieldElement::new(trace(&a) as u128, block_size));
+    assert_eq!(trace(&add_finite_field(&a, &b)), trace(&a) ^ trace(&b));
+    assert_eq!(trace(&multiply_finite_field(&a, &a, block_size).unwrap()), trace(&a));
+    assert_eq!(trace(&FieldElement::new(1, block_size)), block_size % 2 == 1);
+    assert!(!trace(&FieldElement::ZERO));
+}
+
+/// sqrt(a)^2 = a and sqrt(a^2) = a
+fn assert_sqrt(block_size: u32) {
+    let a = generate_random_bits(block_size);
+    let root = sqrt_finite_field(&a, block_size).unwrap();
+    assert_eq!(multiply_finite_field(&root, &root, block_size).unwrap(), a);
+    assert_eq!(sqrt_finite_field(&multiply_finite_field(&a, &a, block_size).unwrap(), block_size).unwrap(), a);
+}
+
+/// x^2 + x = c is solved exactly when Tr(c) = 0, c = y^2 + y always has a solution
+fn assert_quadratic(block_size: u32) {
+    let square_plus = |x: &FieldElement| add_finite_field(&multiply_finite_field(x, x, block_size).unwrap(), x);
+    let c = generate_random_bits(block_size);
+    match solve_quadratic_finite_field(&c, block_size).unwrap() {
+        Some(x) => assert_eq!(square_plus(&x), c),
+        None => assert!(trace_finite_field(&c, block_size).unwrap()),
+    }
+    let c = square_plus(&generate_random_bits(block_size));
+    let x = solve_quadratic_finite_field(&c, block_size).unwrap().unwrap();
+    assert_eq!(square_plus(&x), c);
+}
+
+// Tests trace
+#[test]
+fn finite_field_trace_5() {
+    assert_trace(5)
+}
+
+#[test]
+fn finite_field_trace_8() {
+    assert_trace(8)
+}
+
+#[test]
+fn finite_field_trace_11() {
+    assert_trace(11)
+}
+
+#[test]
+fn finite_field_trace_17() {
+    assert_trace(17)
+}
+
+#[test]
+fn finite_field_trace_25() {
+    assert_trace(25)
+}
+
+#[test]
+fn finite_field_trace_31() {
+    assert_trace(31)
+}
+
+#[test]
+fn finite_field_trace_33() {
+    assert_trace(33)
+}
+
+#[test]
+fn finite_field_trace_47() {
+    assert_trace(47)
+}
+
+#[test]
+fn finite_field_trace_61() {
+    assert_trace(61)
+}
+
+#[test]
+fn finite_field_trace_83() {
+    assert_trace(83)
+}
+
+#[test]
+fn finite_field_trace_101() {
+    assert_trace(101)
+}
+
+#[test]
+fn finite_field_trace_125() {
+    assert_trace(125)
+}
+
+#[test]
+fn finite_field_trace_127() {
+    assert_trace(127)
+}
+
+#[test]
+fn finite_field_trace_128() {
+    assert_trace(128)
+}
+
+#[test]
+fn finite_field_trace_129() {
+    assert_trace(129)
+}
+
+#[test]
+fn finite_field_trace_255() {
+    assert_trace(255)
+}
+
+#[test]
+fn finite_field_trace_256() {
+    assert_trace(256)
+}
+
+#[test]
+fn finite_field_trace_257() {
+    assert_trace(257)
+}
+
+// ---------------------------------------------
+// Tests half-trace, H(c)^2 + H(c) = c + Tr(c) for odd n
+#[test]
+fn finite_field_half_trace_5() {
+    let c = generate_random_bits(5);
+    let h = half_trace_finite_field(&c, 5).unwrap();
+    let expected = if trace_finite_field(&c, 5).unwrap() { add_finite_field(&c, &FieldElement::new(1, 5)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 5).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_11() {
+    let c = generate_random_bits(11);
+    let h = half_trace_finite_field(&c, 11).unwrap();
+    let expected = if trace_finite_field(&c, 11).unwrap() { add_finite_field(&c, &FieldElement::new(1, 11)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 11).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_17() {
+    let c = generate_random_bits(17);
+    let h = half_trace_finite_field(&c, 17).unwrap();
+    let expected = if trace_finite_field(&c, 17).unwrap() { add_finite_field(&c, &FieldElement::new(1, 17)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 17).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_25() {
+    let c = generate_random_bits(25);
+    let h = half_trace_finite_field(&c, 25).unwrap();
+    let expected = if trace_finite_field(&c, 25).unwrap() { add_finite_field(&c, &FieldElement::new(1, 25)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 25).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_31() {
+    let c = generate_random_bits(31);
+    let h = half_trace_finite_field(&c, 31).unwrap();
+    let expected = if trace_finite_field(&c, 31).unwrap() { add_finite_field(&c, &FieldElement::new(1, 31)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 31).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_33() {
+    let c = generate_random_bits(33);
+    let h = half_trace_finite_field(&c, 33).unwrap();
+    let expected = if trace_finite_field(&c, 33).unwrap() { add_finite_field(&c, &FieldElement::new(1, 33)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 33).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_47() {
+    let c = generate_random_bits(47);
+    let h = half_trace_finite_field(&c, 47).unwrap();
+    let expected = if trace_finite_field(&c, 47).unwrap() { add_finite_field(&c, &FieldElement::new(1, 47)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 47).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_61() {
+    let c = generate_random_bits(61);
+    let h = half_trace_finite_field(&c, 61).unwrap();
+    let expected = if trace_finite_field(&c, 61).unwrap() { add_finite_field(&c, &FieldElement::new(1, 61)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 61).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_83() {
+    let c = generate_random_bits(83);
+    let h = half_trace_finite_field(&c, 83).unwrap();
+    let expected = if trace_finite_field(&c, 83).unwrap() { add_finite_field(&c, &FieldElement::new(1, 83)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 83).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_101() {
+    let c = generate_random_bits(101);
+    let h = half_trace_finite_field(&c, 101).unwrap();
+    let expected = if trace_finite_field(&c, 101).unwrap() { add_finite_field(&c, &FieldElement::new(1, 101)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 101).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_125() {
+    let c = generate_random_bits(125);
+    let h = half_trace_finite_field(&c, 125).unwrap();
+    let expected = if trace_finite_field(&c, 125).unwrap() { add_finite_field(&c, &FieldElement::new(1, 125)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 125).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_127() {
+    let c = generate_random_bits(127);
+    let h = half_trace_finite_field(&c, 127).unwrap();
+    let expected = if trace_finite_field(&c, 127).unwrap() { add_finite_field(&c, &FieldElement::new(1, 127)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 127).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_129() {
+    let c = generate_random_bits(129);
+    let h = half_trace_finite_field(&c, 129).unwrap();
+    let expected = if trace_finite_field(&c, 129).unwrap() { add_finite_field(&c, &FieldElement::new(1, 129)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 129).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_255() {
+    let c = generate_random_bits(255);
+    let h = half_trace_finite_field(&c, 255).unwrap();
+    let expected = if trace_finite_field(&c, 255).unwrap() { add_finite_field(&c, &FieldElement::new(1, 255)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 255).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_257() {
+    let c = generate_random_bits(257);
+    let h = half_trace_finite_field(&c, 257).unwrap();
+    let expected = if trace_finite_field(&c, 257).unwrap() { add_finite_field(&c, &FieldElement::new(1, 257)) } else { c };
+    assert_eq!(add_finite_field(&multiply_finite_field(&h, &h, 257).unwrap(), &h), expected)
+}
+
+#[test]
+fn finite_field_half_trace_even() {
+    assert_eq!(half_trace_finite_field(&generate_random_bits(8), 8), Err(Error::EvenBlockSize(8)));
+}
+
+// ---------------------------------------------
+// Tests square root
+#[test]
+fn finite_field_sqrt_5() {
+    assert_sqrt(5)
+}
+
+#[test]
+fn finite_field_sqrt_8() {
+    assert_sqrt(8)
+}
+
+#[test]
+fn finite_field_sqrt_11() {
+    assert_sqrt(11)
+}
+
+#[test]
+fn finite_field_sqrt_17() {
+    assert_sqrt(17)
+}
+
+#[test]
+fn finite_field_sqrt_25() {
+    assert_sqrt(25)
+}
+
+#[test]
+fn finite_field_sqrt_31() {
+    assert_sqrt(31)
+}
+
+#[test]
+fn finite_field_sqrt_33() {
+    assert_sqrt(33)
+}
+
+#[test]
+fn finite_field_sqrt_47() {
+    assert_sqrt(47)
+}
+
+#[test]
+fn finite_field_sqrt_61() {
+    assert_sqrt(61)
+}
+
+#[test]
+fn finite_field_sqrt_83() {
+    assert_sqrt(83)
+}
+
+#[test]
+fn finite_field_sqrt_101() {
+    assert_sqrt(101)
+}
+
+#[test]
+fn finite_field_sqrt_125() {
+    assert_sqrt(125)
+}
+
+#[test]
+fn finite_field_sqrt_127() {
+    assert_sqrt(127)
+}
+
+#[test]
+fn finite_field_sqrt_128() {
+    assert_sqrt(128)
+}
+
+#[test]
+fn finite_field_sqrt_129() {
+    assert_sqrt(129)
+}
+
+#[test]
+fn finite_field_sqrt_255() {
+    assert_sqrt(255)
+}
+
+#[test]
+fn finite_field_sqrt_256() {
+    assert_sqrt(256)
+}
+
+#[test]
+fn finite_field_sqrt_257() {
+    assert_sqrt(257)
+}
+
+// ---------------------------------------------
+// Tests solving x^2 + x = c
+#[test]
+fn finite_field_quadratic_5() {
+    assert_quadratic(5)
+}
+
+#[test]
+fn finite_field_quadratic_8() {
+    assert_quadratic(8)
+}
+
+#[test]
+fn finite_field_quadratic_11() {
+    assert_quadratic(11)
+}
+
+#[test]
+fn finite_field_quadratic_17() {
+    assert_quadratic(17)
+}
+
+#[test]
+fn finite_field_quadratic_25() {
+    assert_quadratic(25)
+}
+
+#[test]
+fn finite_field_quadratic_31() {
+    assert_quadratic(31)
+}
+
+#[test]
+fn finite_field_quadratic_33() {
+    assert_quadratic(33)
+}
+
+#[test]
+fn finite_field_quadratic_47() {
+    assert_quadratic(47)
+}
+
+#[test]
+fn finite_field_quadratic_61() {
+    assert_quadratic(61)
+}
+
+#[test]
+fn finite_field_quadratic_83() {
+    assert_quadratic(83)
+}
+
+#[test]
+fn finite_field_quadratic_101() {
+    assert_quadratic(101)
+}
+
+#[test]
+fn finite_field_quadratic_125() {
+    assert_quadratic(125)
+}
+
+#[test]
+fn finite_field_quadratic_127() {
+    assert_quadratic(127)
+}
+
+#[test]
+fn finite_field_quadratic_128() {
+    assert_quadratic(128)
+}
+
+#[test]
+fn finite_field_quadratic_129() {
+    assert_quadratic(129)
+}
+
+#[test]
+fn finite_field_quadratic_255() {
+    assert_quadratic(255)
+}
+
+#[test]
+fn finite_field_quadratic_256() {
+    assert_quadratic(256)
+}
+
+#[test]
+fn finite_field_quadratic_257() {
+    assert_quadratic(257)
+}
@@ -0,0 +1,202 @@
+//! Exponentiation plans. Ciphers raise every round to the same exponent, so the sequence of squarings and
+//! multiplications is computed once at construction using the
+//! [sliding window method](https://en.wikipedia.org/wiki/Exponentiation_by_squaring#Sliding-window_method) and then
+//! replayed for every input. The number of squarings and multiplications of the plan is also the metric exponents are
+//! compared on.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::utils::uint::Uint;
+
+/// Largest window tried. Table for window w holds 2^(w-1) odd powers, larger windows do not pay off below 512 bits.
+const MAX_WINDOW: u32 = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step {
+    /// Run of k squarings, i.e. x -> x^(2^k).
+    Square(u32),
+    /// Multiplication by a^(2i + 1), i.e. i-th entry of the table of odd powers.
+    Multiply(usize),
+}
+
+/// Precomputed sequence of squarings and multiplications computing a^e for a fixed e.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExponentiationPlan {
+    exponent: Uint,
+    /// Table entry the result starts with, `None` for exponent 0.
+    start: Option<usize>,
+    steps: Vec<Step>,
+    /// Number of odd powers a, a^3, ..., a^(2 * table_size - 1) computed before the steps.
+    table_size: usize,
+}
+
+impl ExponentiationPlan {
+    /// Plan for `exponent` with the window size which needs the least operations.
+    pub fn new(exponent: Uint) -> Self {
+        (1..=MAX_WINDOW)
+            .map(|window| ExponentiationPlan::with_window(exponent, window))
+            .min_by_key(|plan| plan.cost())
+            .unwrap()
+    }
+
+    /// Plan scanning the exponent from the most significant bit with windows of at most `window` bits. Every window
+    /// starts and ends with 1, so only odd powers are needed. Window 1 is the plain binary method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if window is 0.
+    pub fn with_window(exponent: Uint, window: u32) -> Self {
+        assert!(window > 0, "Window must have at least one bit");
+        let mut start = None;
+        let mut steps = Vec::new();
+        let mut table_size = 1;
+        let mut i = exponent.bits() as i64 - 1;
+        while i >= 0 {
+            if !exponent.bit(i as u32) {
+                push_squarings(&mut steps, 1);
+                i -= 1;
+                continue;
+            }
+            let mut low = (i - window as i64 + 1).max(0);
+            while !exponent.bit(low as u32) {
+                low += 1;
+            }
+            let value = ((exponent >> low as u32) & Uint::mask((i - low + 1) as u32)).low_u128() as usize;
+            let index = (value - 1) / 2;
+            table_size = table_size.max(index + 1);
+            if start.is_none() {
+                start = Some(index);
+            } else {
+                push_squarings(&mut steps, (i - low + 1) as u32);
+                steps.push(Step::Multiply(index));
+            }
+            i = low - 1;
+        }
+        ExponentiationPlan { exponent, start, steps, table_size }
+    }
+
+    pub fn exponent(&self) -> Uint {
+        self.exponent
+    }
+
+    /// Number of squarings, including a^2 needed for the table of odd powers.
+    pub fn squarings(&self) -> usize {
+        let runs: u32 = self.steps.iter().map(|step| if let Step::Square(k) = step { *k } else { 0 }).sum();
+        runs as usize + usize::from(self.table_size > 1)
+    }
+
+    /// Number of multiplications, including the ones needed for the table of odd powers.
+    pub fn multiplications(&self) -> usize {
+        self.steps.iter().filter(|step| matches!(step, Step::Multiply(_))).count() + self.table_size - 1
+    }
+
+    /// Total number of field operations, squaring costs the same as multiplication in GF(2^n) with the generic
+    /// multiplication.
+    pub fn cost(&self) -> usize {
+        self.squarings() + self.multiplications()
+    }
+
+    /// Lengths of the runs of squarings between multiplications. In GF(2^n), x -> x^(2^k) is linear, so every run can
+    /// be replaced by a precomputed map (see [`FrobeniusPlan`](crate::utils::frobenius::FrobeniusPlan)).
+    pub fn square_runs(&self) -> BTreeSet<u32> {
+        self.steps.iter().filter_map(|step| if let Step::Square(k) = step { Some(*k) } else { None }).collect()
+    }
+
+    /// Computes a^e with `multiply` as the multiplication of the field (or ring) and `one` as its identity.
+    pub fn apply<T: Copy>(&self, a: T, one: T, multiply: impl Fn(T, T) -> T) -> T {
+        self.apply_with(a, one, &multiply, |x, k| (0..k).fold(x, |x, _| multiply(x, x)))
+    }
+
+    /// Same as [`ExponentiationPlan::apply`], but every run of k squarings is computed by `square(x, k)` = x^(2^k).
+    /// Squaring needed for the table of odd powers is still done by `multiply`.
+    pub fn apply_with<T: Copy>(&self, a: T, one: T, multiply: impl Fn(T, T) -> T, square: impl Fn(T, u32) -> T) -> T {
+        let Some(start) = self.start else {
+            return one;
+        };
+        let mut table = Vec::with_capacity(self.table_size);
+        table.push(a);
+        if self.table_size > 1 {
+            let square = multiply(a, a);
+            for i in 1..self.table_size {
+                table.push(multiply(table[i - 1], square));
+            }
+        }
+        self.steps.iter().fold(table[start], |result, step| match step {
+            Step::Square(k) => square(result, *k),
+            Step::Multiply(i) => multiply(result, table[*i]),
+        })
+    }
+}
+
+/// Adds k squarings, merging them with the previous run.
+fn push_squarings(steps: &mut Vec<Step>, k: u32) {
+    match steps.last_mut() {
+        Some(Step::Square(run)) => *run += k,
+        _ => steps.push(Step::Square(k)),
+    }
+}
+
+impl fmt::Display for ExponentiationPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} squarings + {} multiplications", self.squarings(), self.multiplications())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::exponentiation::ExponentiationPlan;
+    use crate::utils::uint::Uint;
+
+    fn multiply_mod(a: u128, b: u128) -> u128 {
+        (a * b) % 1_000_000_007
+    }
+
+    fn power_mod(a: u128, e: u128) -> u128 {
+        (0..e).fold(1, |acc, _| multiply_mod(acc, a))
+    }
+
+    #[test]
+    fn small_exponents() {
+        for e in 0..300u128 {
+            for window in 1..=4 {
+                let plan = ExponentiationPlan::with_window(Uint::from(e), window);
+                assert_eq!(plan.apply(12345, 1, multiply_mod), power_mod(12345, e), "exponent {e}, window {window}");
+            }
+        }
+    }
+
+    #[test]
+    fn costs() {
+        let plan = ExponentiationPlan::new(Uint::from(3u64));
+        assert_eq!((plan.squarings(), plan.multiplications()), (1, 1));
+        let plan = ExponentiationPlan::new(Uint::ONE << 100);
+        assert_eq!((plan.squarings(), plan.multiplications()), (100, 0));
+        let plan = ExponentiationPlan::new(Uint::ZERO);
+        assert_eq!(plan.cost(), 0);
+        // Binary method needs 126 squarings and 126 multiplications
+        let plan = ExponentiationPlan::with_window(Uint::mask(127), 1);
+        assert_eq!((plan.squarings(), plan.multiplications()), (126, 126));
+        // Window of 4 bits: 8 entries of the table (1 squaring, 7 multiplications) and 32 windows
+        assert_eq!(ExponentiationPlan::new(Uint::mask(127)).cost(), 162);
+    }
+
+    #[test]
+    fn square_runs() {
+        // 0b1_0000_0101 with window 3: window 1, then 5 zeros and window 101 merged into a single run of 8 squarings
+        let plan = ExponentiationPlan::with_window(Uint::from(0b100000101u64), 3);
+        assert_eq!(plan.square_runs().into_iter().collect::<Vec<_>>(), vec![8]);
+        let doubled = plan.apply_with(3, 1, multiply_mod, |x, k| power_mod(x, 1 << k));
+        assert_eq!(doubled, power_mod(3, 0b100000101));
+    }
+
+    #[test]
+    fn decryption_exponent() {
+        // (2^128 - 1) / 3 for MiMC-127
+        let exponent = Uint::mask(128).div_rem_u64(3).0;
+        let plan = ExponentiationPlan::new(exponent);
+        assert_eq!(plan.exponent(), exponent);
+        assert!(plan.cost() < ExponentiationPlan::with_window(exponent, 1).cost());
+        assert_eq!(plan.apply(7, 1, multiply_mod), ExponentiationPlan::with_window(exponent, 1).apply(7, 1, multiply_mod));
+    }
+}
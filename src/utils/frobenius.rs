@@ -0,0 +1,157 @@
+//! Frobenius maps of GF(2^n). Squaring is GF(2)-linear, (a + b)^2 = a^2 + b^2, so x -> x^(2^k) is a linear map given
+//! by the images of x^0, ..., x^(n-1). The map is stored as tables of XORed images for every 4 bit chunk of the input,
+//! so k squarings cost n / 4 lookups instead of k multiplications. Decryption exponents are mostly runs of squarings,
+//! which makes this the faster decryption path for binary fields.
+
+use std::collections::HashMap;
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{BinaryField, FieldElement};
+use crate::utils::uint::Uint;
+
+/// Bits of the input looked up at once.
+const CHUNK_BITS: u32 = 4;
+
+/// Linear map x -> x^(2^k) of the field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrobeniusMap {
+    block_size: u32,
+    power: u32,
+    /// For chunk c, images of all 2^CHUNK_BITS combinations of x^(4c), ..., x^(4c + 3).
+    table: Vec<[Uint; 1 << CHUNK_BITS]>,
+}
+
+impl FrobeniusMap {
+    /// Map x -> x^2 of the field.
+    pub fn squaring(field: &BinaryField) -> Self {
+        let basis = (0..field.block_size()).map(|i| {
+            let x = FieldElement::from_uint(Uint::ONE << i, field.block_size());
+            field.multiply(&x, &x).value()
+        }).collect::<Vec<Uint>>();
+        FrobeniusMap::from_images(field.block_size(), 1, &basis)
+    }
+
+    /// Map x -> x^(2^k) of the field for k as `power`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if power is 0.
+    pub fn new(field: &BinaryField, power: u32) -> Self {
+        assert!(power > 0, "Frobenius map needs at least one squaring");
+        let squaring = FrobeniusMap::squaring(field);
+        let mut basis = squaring.images();
+        for _ in 1..power {
+            basis = basis.iter().map(|&b| squaring.apply_uint(b)).collect();
+        }
+        FrobeniusMap::from_images(field.block_size(), power, &basis)
+    }
+
+    /// Map given by the images of x^0, ..., x^(n-1).
+    fn from_images(block_size: u32, power: u32, basis: &[Uint]) -> Self {
+        let table = basis.chunks(CHUNK_BITS as usize).map(|images| {
+            let mut entry = [Uint::ZERO; 1 << CHUNK_BITS];
+            for bits in 1..entry.len() {
+                // Lowest set bit is added to the entry without it
+                let low = bits.trailing_zeros() as usize;
+                entry[bits] = entry[bits & (bits - 1)] ^ images.get(low).copied().unwrap_or(Uint::ZERO);
+            }
+            entry
+        }).collect();
+        FrobeniusMap { block_size, power, table }
+    }
+
+    /// Images of x^0, ..., x^(n-1).
+    fn images(&self) -> Vec<Uint> {
+        (0..self.block_size).map(|i| self.apply_uint(Uint::ONE << i)).collect()
+    }
+
+    pub fn power(&self) -> u32 {
+        self.power
+    }
+
+    fn apply_uint(&self, a: Uint) -> Uint {
+        let limbs = a.limbs();
+        let per_limb = (u64::BITS / CHUNK_BITS) as usize;
+        self.table.iter().enumerate().fold(Uint::ZERO, |result, (c, entry)| {
+            let bits = (limbs[c / per_limb] >> ((c % per_limb) as u32 * CHUNK_BITS)) as usize & (entry.len() - 1);
+            if bits == 0 { result } else { result ^ entry[bits] }
+        })
+    }
+
+    /// a^(2^k)
+    pub fn apply(&self, a: &FieldElement) -> FieldElement {
+        FieldElement::from_uint(self.apply_uint(a.value()), self.block_size)
+    }
+}
+
+/// Exponentiation plan with Frobenius map for every run of squarings, so only multiplications by the odd powers are
+/// left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrobeniusPlan {
+    field: BinaryField,
+    plan: ExponentiationPlan,
+    maps: HashMap<u32, FrobeniusMap>,
+}
+
+impl FrobeniusPlan {
+    pub fn new(field: BinaryField, plan: ExponentiationPlan) -> Self {
+        let squaring = FrobeniusMap::squaring(&field);
+        let runs = plan.square_runs();
+        let mut maps = HashMap::new();
+        // Images of the basis under x^(2^k) for increasing k
+        let mut basis = squaring.images();
+        for k in 1..=runs.last().copied().unwrap_or(0) {
+            if runs.contains(&k) {
+                maps.insert(k, FrobeniusMap::from_images(field.block_size(), k, &basis));
+            }
+            basis = basis.iter().map(|&b| squaring.apply_uint(b)).collect();
+        }
+        FrobeniusPlan { field, plan, maps }
+    }
+
+    pub fn plan(&self) -> &ExponentiationPlan {
+        &self.plan
+    }
+
+    /// a^e, where e is the exponent of the plan.
+    pub fn power(&self, a: &FieldElement) -> FieldElement {
+        self.plan.apply_with(*a, FieldElement::new(1, self.field.block_size()),
+            |x, y| self.field.multiply(&x, &y), |x, k| self.maps[&k].apply(&x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::exponentiation::ExponentiationPlan;
+    use crate::utils::frobenius::{FrobeniusMap, FrobeniusPlan};
+    use crate::utils::helpers::{BinaryField, generate_random_bits};
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn squaring() {
+        for block_size in [5, 8, 17, 127, 128, 129, 257] {
+            let field = BinaryField::new(block_size).unwrap();
+            let a = generate_random_bits(block_size);
+            assert_eq!(FrobeniusMap::squaring(&field).apply(&a), field.multiply(&a, &a));
+        }
+    }
+
+    #[test]
+    fn power_of_two() {
+        let field = BinaryField::new(61).unwrap();
+        let a = generate_random_bits(61);
+        assert_eq!(FrobeniusMap::new(&field, 10).apply(&a), field.power(&a, 1 << 10));
+        // x^(2^n) = x
+        assert_eq!(FrobeniusMap::new(&field, 61).apply(&a), a);
+    }
+
+    #[test]
+    fn plan() {
+        for block_size in [11, 127, 255] {
+            let field = BinaryField::new(block_size).unwrap();
+            let exponent = Uint::mask(block_size + 1).div_rem_u64(3).0;
+            let plan = FrobeniusPlan::new(field, ExponentiationPlan::new(exponent));
+            let a = generate_random_bits(block_size);
+            assert_eq!(plan.power(&a), field.power_wide(&a, exponent));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Second, independent implementation of GF(2^n) arithmetic generated by the [g2p](https://docs.rs/g2p) crate. It uses
+//! precomputed multiplication and inversion tables, so it is only available for the small predefined block sizes. Used
+//! to cross-validate the native arithmetic and as an alternative backend of the experiments.
+
+use g2p::{g2p, GaloisField};
+use crate::utils::helpers::{BinaryField, irreducible_polynomial};
+
+g2p!(GF32, 5, modulus: 0b100101);
+g2p!(GF256, 8, modulus: 0b100011101);
+g2p!(GF2048, 11, modulus: 0b100000000101);
+g2p!(GF131072, 17, modulus: 0b100000000000001001);
+
+/// Block sizes with a g2p field, all of them use the polynomials from [`irreducible_polynomial`].
+pub const G2P_BLOCK_SIZES: [u32; 4] = [5, 8, 11, 17];
+
+/// Whether the field has a g2p counterpart, i.e. same block size and reduction polynomial.
+pub fn is_supported(field: &BinaryField) -> bool {
+    G2P_BLOCK_SIZES.contains(&field.block_size()) && irreducible_polynomial(field.block_size()) == Ok(field.modulus())
+}
+
+/// Multiplication a * b in the g2p field of the block size.
+///
+/// # Panics
+///
+/// Panics if there is no g2p field for the block size (see [`G2P_BLOCK_SIZES`]).
+pub fn multiply(a: u128, b: u128, block_size: u32) -> u128 {
+    match block_size {
+        5 => (GF32(a as u8) * GF32(b as u8)).0 as u128,
+        8 => (GF256(a as u8) * GF256(b as u8)).0 as u128,
+        11 => (GF2048(a as u16) * GF2048(b as u16)).0 as u128,
+        17 => (GF131072(a as u32) * GF131072(b as u32)).0 as u128,
+        _ => panic!("g2p field is not available for block size {block_size}"),
+    }
+}
+
+/// a^exponent in the g2p field of the block size.
+///
+/// # Panics
+///
+/// Panics if there is no g2p field for the block size (see [`G2P_BLOCK_SIZES`]).
+pub fn power(a: u128, exponent: usize, block_size: u32) -> u128 {
+    match block_size {
+        5 => GF32(a as u8).pow(exponent).0 as u128,
+        8 => GF256(a as u8).pow(exponent).0 as u128,
+        11 => GF2048(a as u16).pow(exponent).0 as u128,
+        17 => GF131072(a as u32).pow(exponent).0 as u128,
+        _ => panic!("g2p field is not available for block size {block_size}"),
+    }
+}
+
+/// Multiplicative inverse of non-zero a in the g2p field of the block size.
+///
+/// # Panics
+///
+/// Panics if a is zero or there is no g2p field for the block size (see [`G2P_BLOCK_SIZES`]).
+pub fn invert(a: u128, block_size: u32) -> u128 {
+    match block_size {
+        5 => (GF32::ONE / GF32(a as u8)).0 as u128,
+        8 => (GF256::ONE / GF256(a as u8)).0 as u128,
+        11 => (GF2048::ONE / GF2048(a as u16)).0 as u128,
+        17 => (GF131072::ONE / GF131072(a as u32)).0 as u128,
+        _ => panic!("g2p field is not available for block size {block_size}"),
+    }
+}
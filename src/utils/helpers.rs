@@ -1,81 +1,650 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::{BitAnd, BitXor, Shl, Shr};
+use std::str::FromStr;
+use std::sync::RwLock;
 use rand::{Rng, thread_rng};
 use lazy_static::lazy_static;
+use crate::error::{Error, Result};
+use crate::mimc_general::gmimc::GMiMCVariant;
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::g2p_field;
+use crate::utils::polynomials::{degree, find_irreducible_polynomial, inverse_mod, is_irreducible, is_primitive, parse_polynomial};
+use crate::utils::prime_field::PrimeField;
+use crate::utils::uint::{LIMBS, Uint};
 
-pub type FieldElement = Vec<u8>;
+/// Largest block size for which the 2^n field arithmetic is implemented.
+pub const MAX_BLOCK_SIZE: u32 = Uint::BITS - 1;
 
+/// Element of the extension field 2^n packed into a single integer, i.e. the polynomial x^4 + x + 1 is stored as
+/// `0b10011`. Field arithmetic works directly on the packed value, bit arrays are only used at the I/O edges (see
+/// [`FieldElement::from_bits`] and [`FieldElement::to_bits`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FieldElement(Uint);
+
+impl FieldElement {
+    pub const ZERO: FieldElement = FieldElement(Uint::ZERO);
+
+    /// Creates field element from the lowest `block_size` bits of `value`, the rest is discarded.
+    pub fn new(value: u128, block_size: u32) -> Self {
+        FieldElement::from_uint(Uint::from(value), block_size)
+    }
+
+    /// Same as [`FieldElement::new`] for values which do not fit into u128.
+    pub fn from_uint(value: Uint, block_size: u32) -> Self {
+        FieldElement(value & Uint::mask(block_size))
+    }
+
+    /// Creates field element from bit array where bits are from right to left, i.e. [0, 1] -> 1 and [1, 0] -> 2.
+    pub fn from_bits(bits: &[u8]) -> Self {
+        FieldElement(bits.iter().fold(Uint::ZERO, |acc, &bit| (acc << 1) | Uint::from(bit as u64)))
+    }
+
+    /// Converts field element to bit array of size `block_size` (same ordering as in [`FieldElement::from_bits`]).
+    pub fn to_bits(self, block_size: u32) -> Vec<u8> {
+        (0..block_size).rev().map(|i| self.0.bit(i) as u8).collect()
+    }
+
+    pub fn value(self) -> Uint {
+        self.0
+    }
+
+    /// Returns a copy of the element with the `i`th lowest bit flipped.
+    pub fn flip_bit(self, i: u32) -> Self {
+        FieldElement(self.0.flip_bit(i))
+    }
+
+    /// Number of bits set in the element, i.e. Hamming weight.
+    pub fn count_ones(self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+impl BitXor for FieldElement {
+    type Output = FieldElement;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        FieldElement(self.0 ^ rhs.0)
+    }
+}
+
+impl fmt::Display for FieldElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Cipher chosen for the experiments. MiMC type ciphers take optional field with user chosen reduction polynomial,
+/// decryption method and key schedule, MiMCGe additionally takes exponent, round constants and round reduction. MiMCGePrime is MiMCGe
+/// over prime field. MiMCGeFeistel takes the field of the half-block and needs no decryption method, GMiMC additionally
+/// takes its variant and number of branches and the field of a single branch.
 pub enum CipherType<'a> {
     AES,
-    MiMC,
-    MiMCGe(u128, &'a Vec<u128>, Option<usize>),
+    MiMC(Option<BinaryField>, DecryptionMethod, KeySchedule),
+    MiMCGe(u128, &'a Vec<Uint>, Option<usize>, Option<BinaryField>, DecryptionMethod, KeySchedule),
+    MiMCGePrime(u128, &'a Vec<Uint>, Option<usize>, PrimeField),
+    MiMCGeFeistel(u128, &'a Vec<Uint>, Option<usize>, Option<BinaryField>),
+    GMiMC(GMiMCVariant, u32, u128, &'a Vec<Uint>, Option<usize>, Option<BinaryField>),
+}
+
+/// Implementation of the GF(2^n) multiplication.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Russian peasant multiplication, see [`multiply_finite_field`].
+    #[default]
+    Native,
+    /// Tables generated by the g2p crate, only for the block sizes in [`g2p_field::G2P_BLOCK_SIZES`].
+    G2p,
+}
+
+/// How ciphers over GF(2^n) raise the state to the decryption exponent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecryptionMethod {
+    /// Square and multiply by [`ExponentiationPlan`].
+    SquareMultiply,
+    /// Runs of squarings are done by precomputed linear maps, see [`FrobeniusPlan`](crate::utils::frobenius::FrobeniusPlan).
+    #[default]
+    Frobenius,
+}
+
+/// How MiMC and MiMCGe derive the round keys k_0, ..., k_r from the key, k_i is added before round i and k_r after the
+/// last round. The 2K key is split into n-bit words from the least significant one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeySchedule {
+    /// The n-bit key in every round, as in the original MiMC.
+    #[default]
+    Single,
+    /// k_0 is the n-bit key and k_1, ..., k_r are pseudorandom words of SplitMix64 seeded by the key, i.e. the round keys
+    /// are unrelated to each other as if they were independent. The seed has only 64 bits, so this is meant for the
+    /// experiments and not as a key derivation.
+    Independent,
+    /// k_0 is the n-bit key and k_(i+1) = (k_i + c_i)^e, i.e. the round keys are the states of the cipher encrypting the
+    /// key with zero round keys.
+    Iterated,
+    /// Two n-bit keys alternating (2K), k_0 in even rounds and k_1 in odd ones, the key has 2n bits.
+    TwoKey,
+}
+
+impl KeySchedule {
+    /// Key size in bits of a cipher with the block size.
+    ///
+    /// Returns an error if the key does not fit into [`Uint::BITS`].
+    pub fn key_size(self, block_size: u32) -> Result<u32> {
+        let words = match self {
+            KeySchedule::Single | KeySchedule::Independent | KeySchedule::Iterated => 1,
+            KeySchedule::TwoKey => 2,
+        };
+        let key_size = words * block_size as usize;
+        if key_size > Uint::BITS as usize {
+            return Err(Error::KeyTooLarge(key_size));
+        }
+        Ok(key_size as u32)
+    }
+
+    /// Round keys k_0, ..., k_r of the key, derived once for all the blocks encrypted with it. `round` is the ith round
+    /// function (x + c_i)^e of the cipher, which is only used by [`KeySchedule::Iterated`].
+    pub fn round_keys(self, key: &FieldElement, block_size: u32, rounds: usize, round: impl Fn(&FieldElement, usize) -> FieldElement) -> RoundKeys {
+        let word = |i: usize| FieldElement::from_uint(key.value() >> (i as u32 * block_size), block_size);
+        match self {
+            KeySchedule::Single => RoundKeys::Alternating([word(0), word(0)]),
+            KeySchedule::Independent => {
+                let seed = key.value().limbs().iter().fold(KEY_SEED, |seed, limb| split_mix(seed ^ limb)());
+                RoundKeys::Derived([word(0)].into_iter().chain(split_mix_elements(seed, block_size).take(rounds)).collect())
+            }
+            KeySchedule::Iterated => {
+                let mut keys = Vec::with_capacity(rounds + 1);
+                keys.push(word(0));
+                for i in 0..rounds {
+                    keys.push(round(&keys[i], i));
+                }
+                RoundKeys::Derived(keys)
+            }
+            KeySchedule::TwoKey => RoundKeys::Alternating([word(0), word(1)]),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeySchedule::Single => "single",
+            KeySchedule::Independent => "independent",
+            KeySchedule::Iterated => "iterated",
+            KeySchedule::TwoKey => "2K",
+        }
+    }
+}
+
+/// Round keys k_0, ..., k_r given by [`KeySchedule::round_keys`]. Schedules which repeat one or two keys do not
+/// allocate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RoundKeys {
+    /// k_i is the first key for even i and the second one for odd i.
+    Alternating([FieldElement; 2]),
+    /// k_i of every round and the final addition.
+    Derived(Vec<FieldElement>),
+}
+
+impl RoundKeys {
+    /// Key k_i added before round i, k_r is added after the last round.
+    pub fn get(&self, i: usize) -> FieldElement {
+        match self {
+            RoundKeys::Alternating(keys) => keys[i % 2],
+            RoundKeys::Derived(keys) => keys[i],
+        }
+    }
+
+    /// Round keys with the tweak reduced to `block_size` bits added to k_i of odd i.
+    pub fn with_tweak(mut self, tweak: &FieldElement, block_size: u32) -> Self {
+        let tweak = &FieldElement::from_uint(tweak.value(), block_size);
+        match &mut self {
+            RoundKeys::Alternating(keys) => keys[1] = add_finite_field(&keys[1], tweak),
+            RoundKeys::Derived(keys) => {
+                for key in keys.iter_mut().skip(1).step_by(2) {
+                    *key = add_finite_field(key, tweak);
+                }
+            }
+        }
+        self
+    }
 }
 
 lazy_static! {
-    static ref IRREDUCIBLE_POLYNOMIALS: HashMap<u32, (u128, u128)> = HashMap::from([
-        (5, (0x10, 0x25)),                                                              // x^4,  x^5 + x^2 + 1
-        (8, (0x80, 0x11D)),                                                             // x^7,  x^8 + x^4 + x^3 + x^2 + 1
-        (11, (0x400, 0x805)),                                                           // x^10, x^11 + x^2 + 1
-        (17, (0x10000, 0x20009)),                                                       // x^16, x^17 + x^3 + 1
-        (25, (0x1000000, 0x2000145)),                                                   // x^24, x^25 + x^8 + x^6 + x^2 + 1
-        (31, (0x40000000, 0x80000009)),                                                 // x^30, x^31 + x^3 + 1
-        (33, (0x100000000, 0x200003D49)),                                               // x^32, x^33 + x^13 + x^12 + x^11 + x^10 + x^8 + x^6 + x^3 + 1
-        (47, (0x400000000000, 0x800000000021)),                                         // x^46, x^47 + x^5 + 1
-        (61, (0x1000000000000000, 0x2000000000000027)),                                 // x^60, x^61 + x^5 + x^2 + x + 1
-        (83, (0x400000000000000000000, 0x800000000000000000095)),                       // x^82, x^83 + x^7 + x^4 + x^2 + 1
-        (101, (0x10000000000000000000000000, 0x200000000000000000000000C3)),            // x^100, x^101 + x^7 + x^6 + x + 1
-        (125, (0x10000000000000000000000000000000, 0x200000000000000000000000004BBE69)),// x^124, x^125 + x^22 + x^19 + x^17 + x^16 + x^15 + x^13 + x^12 + x^11 + x^10 + x^9 + x^6 + x^5 + x^3 + 1
-        (127, (0x40000000000000000000000000000000, 0x80000000000000000000000000000003)) // x^126, x^127 + x + 1
+    static ref IRREDUCIBLE_POLYNOMIALS: HashMap<u32, Uint> = HashMap::from([
+        (5, Uint::from_u128(0x25)),                                                     // x^5 + x^2 + 1
+        (8, Uint::from_u128(0x11D)),                                                    // x^8 + x^4 + x^3 + x^2 + 1
+        (11, Uint::from_u128(0x805)),                                                   // x^11 + x^2 + 1
+        (17, Uint::from_u128(0x20009)),                                                 // x^17 + x^3 + 1
+        (25, Uint::from_u128(0x2000145)),                                               // x^25 + x^8 + x^6 + x^2 + 1
+        (31, Uint::from_u128(0x80000009)),                                              // x^31 + x^3 + 1
+        (33, Uint::from_u128(0x200003D49)),                                             // x^33 + x^13 + x^12 + x^11 + x^10 + x^8 + x^6 + x^3 + 1
+        (47, Uint::from_u128(0x800000000021)),                                          // x^47 + x^5 + 1
+        (61, Uint::from_u128(0x2000000000000027)),                                      // x^61 + x^5 + x^2 + x + 1
+        (83, Uint::from_u128(0x800000000000000000095)),                                 // x^83 + x^7 + x^4 + x^2 + 1
+        (101, Uint::from_u128(0x200000000000000000000000C3)),                           // x^101 + x^7 + x^6 + x + 1
+        (125, Uint::from_u128(0x200000000000000000000000004BBE69)),                     // x^125 + x^22 + x^19 + x^17 + x^16 + x^15 + x^13 + x^12 + x^11 + x^10 + x^9 + x^6 + x^5 + x^3 + 1
+        (127, Uint::from_u128(0x80000000000000000000000000000003)),                     // x^127 + x + 1
+        (128, Uint::from_u128(0x87) ^ (Uint::ONE << 128)),                              // x^128 + x^7 + x^2 + x + 1 (GCM)
+        (129, Uint::from_u128(0x21) ^ (Uint::ONE << 129)),                              // x^129 + x^5 + 1
+        (255, Uint::from_u128(0x10000000000001) ^ (Uint::ONE << 255)),                  // x^255 + x^52 + 1
+        (256, Uint::from_u128(0x425) ^ (Uint::ONE << 256)),                             // x^256 + x^10 + x^5 + x^2 + 1
+        (257, Uint::from_u128(0x1001) ^ (Uint::ONE << 257)),                            // x^257 + x^12 + 1
     ]);
+
+    /// Polynomials found for block sizes which are not in `IRREDUCIBLE_POLYNOMIALS`, so the search is done only once.
+    static ref GENERATED_POLYNOMIALS: RwLock<HashMap<u32, Uint>> = RwLock::new(HashMap::new());
+}
+
+/// Returns irreducible polynomial for the field 2^n with n as `block_size`. Predefined polynomials from
+/// `IRREDUCIBLE_POLYNOMIALS` are used if they exist, otherwise, the lowest weight polynomial is found with
+/// [`find_irreducible_polynomial`] and cached.
+///
+/// Returns an error if `block_size` is not in range 2 <= n <= [`MAX_BLOCK_SIZE`].
+pub fn irreducible_polynomial(block_size: u32) -> Result<Uint> {
+    if !(2..=MAX_BLOCK_SIZE).contains(&block_size) {
+        return Err(Error::UnsupportedBlockSize(block_size));
+    }
+    if let Some(&poly) = IRREDUCIBLE_POLYNOMIALS.get(&block_size) {
+        return Ok(poly);
+    }
+    if let Some(&poly) = GENERATED_POLYNOMIALS.read().unwrap().get(&block_size) {
+        return Ok(poly);
+    }
+    let poly = find_irreducible_polynomial(block_size);
+    GENERATED_POLYNOMIALS.write().unwrap().insert(block_size, poly);
+    Ok(poly)
 }
 
+/// Extension field 2^n given by its irreducible reduction polynomial. Block size n is the degree of the polynomial.
+///
+/// Fields up to 2^128 are computed on u128 (the x^128 term of the polynomial is dropped by the shift, so it does not
+/// need to be stored), larger ones on [`Uint`].
+///
+/// In constant-time mode (see [`BinaryField::with_constant_time`]), multiplication always does n iterations without
+/// branches on the operands, so it can be used with secret keys and states. Multiplication can also be delegated to
+/// the g2p crate for the block sizes it is generated for (see [`BinaryField::with_backend`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BinaryField {
+    block_size: u32,
+    modulus: Uint,
+    constant_time: bool,
+    backend: Backend,
+}
+
+impl BinaryField {
+    /// Field 2^n with n as `block_size` using the default polynomial (see [`irreducible_polynomial`]).
+    ///
+    /// Returns an error if `block_size` is not in range 2 <= n <= [`MAX_BLOCK_SIZE`].
+    pub fn new(block_size: u32) -> Result<Self> {
+        Ok(BinaryField { block_size, modulus: irreducible_polynomial(block_size)?, constant_time: false, backend: Backend::Native })
+    }
+
+    /// Field given by the reduction polynomial `modulus`, e.g. `0x80000000000000000000000000000003` for x^127 + x + 1.
+    ///
+    /// Returns an error if polynomial is reducible (then it is not a field) or its degree is not in range 2 <= n <=
+    /// [`MAX_BLOCK_SIZE`].
+    pub fn with_modulus(modulus: Uint) -> Result<Self> {
+        let block_size = degree(modulus);
+        if !(2..=MAX_BLOCK_SIZE).contains(&block_size) {
+            return Err(Error::UnsupportedBlockSize(block_size));
+        }
+        if !is_irreducible(modulus) {
+            return Err(Error::ReducibleModulus(modulus));
+        }
+        Ok(BinaryField { block_size, modulus, constant_time: false, backend: Backend::Native })
+    }
+
+    /// Same field with constant-time arithmetic switched on or off. Ciphers over constant-time fields do not use lookup
+    /// tables either, as these leak the index through the cache.
+    ///
+    /// Returns an error if constant time is switched on for the g2p backend.
+    pub fn with_constant_time(self, constant_time: bool) -> Result<Self> {
+        if constant_time && self.backend == Backend::G2p {
+            return Err(Error::BackendNotConstantTime);
+        }
+        Ok(BinaryField { constant_time, ..self })
+    }
+
+    pub fn is_constant_time(&self) -> bool {
+        self.constant_time
+    }
+
+    /// Same field with multiplication done by the `backend`.
+    ///
+    /// Returns an error if g2p backend is chosen for a field it is not generated for (see [`g2p_field::is_supported`]) or
+    /// a constant-time field.
+    pub fn with_backend(self, backend: Backend) -> Result<Self> {
+        if backend == Backend::G2p && !g2p_field::is_supported(&self) {
+            return Err(Error::BackendUnavailable(self.block_size));
+        }
+        if backend == Backend::G2p && self.constant_time {
+            return Err(Error::BackendNotConstantTime);
+        }
+        Ok(BinaryField { backend, ..self })
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    pub fn modulus(&self) -> Uint {
+        self.modulus
+    }
+
+    /// Whether x generates the multiplicative group of the field (see [`is_primitive`]), `None` if this is not known.
+    pub fn is_primitive(&self) -> Option<bool> {
+        is_primitive(self.modulus)
+    }
+
+    /// Whether the elements fit into u128, so the faster arithmetic can be used.
+    fn is_narrow(&self) -> bool {
+        self.block_size <= u128::BITS
+    }
+
+    /// Polynomial in the form needed for the u128 arithmetic: x^(n-1) and the lowest 128 bits of the polynomial.
+    fn narrow_polynomial(&self) -> (u128, u128) {
+        (1 << (self.block_size - 1), self.modulus.low_u128())
+    }
+
+    /// Polynomial in the form needed for the wide arithmetic: x^(n-1) and the polynomial itself.
+    fn wide_polynomial(&self) -> (Uint, Uint) {
+        (Uint::ONE << (self.block_size - 1), self.modulus)
+    }
+
+    fn multiply_narrow(&self, a: u128, b: u128) -> u128 {
+        if self.backend == Backend::G2p {
+            g2p_field::multiply(a, b, self.block_size)
+        } else if self.constant_time {
+            _multiply_constant_time(a, b, self.narrow_polynomial(), self.block_size)
+        } else {
+            _multiply_finite_field(a, b, self.narrow_polynomial())
+        }
+    }
+
+    fn multiply_wide(&self, a: Uint, b: Uint) -> Uint {
+        if self.constant_time {
+            _multiply_constant_time(a, b, self.wide_polynomial(), self.block_size)
+        } else {
+            _multiply_finite_field(a, b, self.wide_polynomial())
+        }
+    }
+
+    /// Multiplication in the field, see [`multiply_finite_field`].
+    pub fn multiply(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        if self.is_narrow() {
+            FieldElement(Uint::from(self.multiply_narrow(a.0.low_u128(), b.0.low_u128())))
+        } else {
+            FieldElement(self.multiply_wide(a.0, b.0))
+        }
+    }
+
+    /// Exponentiation in the field, see [`square_multiply`].
+    pub fn power(&self, a: &FieldElement, exponent: u128) -> FieldElement {
+        self.power_wide(a, Uint::from(exponent))
+    }
+
+    /// Exponentiation with exponent which does not fit into u128, e.g. inverse of x^e for fields larger than 2^127.
+    pub fn power_wide(&self, a: &FieldElement, exponent: Uint) -> FieldElement {
+        self.power_with(a, &ExponentiationPlan::new(exponent))
+    }
+
+    /// Exponentiation by precomputed plan, so the same exponent does not need to be scanned for every element. The
+    /// plan depends only on the exponent, so the exponentiation is constant-time if the multiplication is.
+    pub fn power_with(&self, a: &FieldElement, plan: &ExponentiationPlan) -> FieldElement {
+        if self.is_narrow() {
+            FieldElement(Uint::from(plan.apply(a.0.low_u128(), 1, |x, y| self.multiply_narrow(x, y))))
+        } else {
+            FieldElement(plan.apply(a.0, Uint::ONE, |x, y| self.multiply_wide(x, y)))
+        }
+    }
+
+    /// Multiplicative inverse in the field, see [`invert_finite_field`].
+    pub fn invert(&self, a: &FieldElement) -> Result<FieldElement> {
+        inverse_mod(a.0, self.modulus).map(FieldElement).ok_or(Error::ZeroInverse)
+    }
+
+    /// Division a / b in the field, see [`divide_finite_field`].
+    pub fn divide(&self, a: &FieldElement, b: &FieldElement) -> Result<FieldElement> {
+        Ok(self.multiply(a, &self.invert(b)?))
+    }
+
+    /// a^2
+    pub fn square(&self, a: &FieldElement) -> FieldElement {
+        self.multiply(a, a)
+    }
+
+    /// Absolute trace, see [`trace_finite_field`].
+    pub fn trace(&self, a: &FieldElement) -> bool {
+        let mut conjugate = *a;
+        let mut sum = *a;
+        for _ in 1..self.block_size {
+            conjugate = self.square(&conjugate);
+            sum = sum ^ conjugate;
+        }
+        debug_assert!(sum.0 <= Uint::ONE, "Trace must be in GF(2)");
+        sum.0 == Uint::ONE
+    }
+
+    /// Half-trace, see [`half_trace_finite_field`].
+    ///
+    /// Returns an error if the block size is even.
+    pub fn half_trace(&self, a: &FieldElement) -> Result<FieldElement> {
+        if self.block_size.is_multiple_of(2) {
+            return Err(Error::EvenBlockSize(self.block_size));
+        }
+        let mut conjugate = *a;
+        let mut sum = *a;
+        for _ in 0..(self.block_size - 1) / 2 {
+            conjugate = self.square(&self.square(&conjugate));
+            sum = sum ^ conjugate;
+        }
+        Ok(sum)
+    }
+
+    /// Square root, see [`sqrt_finite_field`].
+    pub fn sqrt(&self, a: &FieldElement) -> FieldElement {
+        (1..self.block_size).fold(*a, |x, _| self.square(&x))
+    }
+
+    /// Solution of x^2 + x = c, see [`solve_quadratic_finite_field`].
+    pub fn solve_quadratic(&self, c: &FieldElement) -> Option<FieldElement> {
+        if self.trace(c) {
+            return None;
+        }
+        if let Ok(solution) = self.half_trace(c) {
+            return Some(solution);
+        }
+        // For even n, x = sum_(i=0)^(n-2) (sum_(j=i+1)^(n-1) d^(2^j)) * c^(2^i), where d is any element with Tr(d) = 1.
+        // Half of the elements have trace 1, while the basis x^i often has only a few of them, so d is chosen randomly.
+        let n = self.block_size as usize;
+        let delta = std::iter::repeat_with(|| generate_random_bits(self.block_size))
+            .find(|d| self.trace(d))
+            .unwrap();
+        let conjugates = |a: FieldElement| (1..n).fold(vec![a], |mut powers, _| {
+            powers.push(self.square(powers.last().unwrap()));
+            powers
+        });
+        let (c_powers, delta_powers) = (conjugates(*c), conjugates(delta));
+        let mut delta_sum = FieldElement::ZERO;
+        let mut x = FieldElement::ZERO;
+        for i in (0..n - 1).rev() {
+            delta_sum = delta_sum ^ delta_powers[i + 1];
+            x = x ^ self.multiply(&delta_sum, &c_powers[i]);
+        }
+        Some(x)
+    }
+}
+
+impl FromStr for BinaryField {
+    type Err = Error;
+
+    /// Parses reduction polynomial (see [`parse_polynomial`]) and checks that it defines a field.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BinaryField::with_modulus(parse_polynomial(s)?)
+    }
+}
+
+/// Field the cipher works over, either 2^n given by its reduction polynomial or prime field GF(p).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Binary(BinaryField),
+    Prime(PrimeField),
+}
+
+impl FieldType {
+    /// Number of bits needed to store an element.
+    pub fn block_size(&self) -> u32 {
+        match self {
+            FieldType::Binary(field) => field.block_size(),
+            FieldType::Prime(field) => field.block_size(),
+        }
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = Error;
+
+    /// Parses `binary:<polynomial>` (see [`BinaryField`]), `prime:<p>` (see [`PrimeField`]) or name of the prime field
+    /// preset (see [`PrimeField::preset`]).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("binary", polynomial)) => Ok(FieldType::Binary(polynomial.parse()?)),
+            Some(("prime", prime)) => Ok(FieldType::Prime(prime.parse()?)),
+            _ => PrimeField::preset(s).map(FieldType::Prime)
+                .ok_or_else(|| Error::Parse(format!("unknown field '{s}', expected binary:<polynomial>, prime:<p>, bn254 or bls12-381"))),
+        }
+    }
+}
+
+/// Block cipher over field elements. Besides encryption and decryption, it describes itself, so that generic code
+/// (experiments, modes of operation) does not need the block size and the parameters passed alongside it.
 pub trait Cipher {
     fn encrypt(&self, plaintext: &FieldElement, key: &FieldElement) -> FieldElement;
     fn decrypt(&self, ciphertext: &FieldElement, key: &FieldElement) -> FieldElement;
+
+    /// Number of bits of a block.
+    fn block_size(&self) -> u32;
+
+    /// Number of bits of the key, the block size by default. Feistel ciphers take the key of a branch and the 2K key
+    /// schedule of MiMC and MiMCGe two blocks.
+    fn key_size(&self) -> u32 {
+        self.block_size()
+    }
+
+    fn rounds(&self) -> usize;
+
+    /// Name of the cipher, e.g. `MiMCGe`.
+    fn name(&self) -> &'static str;
+
+    /// Parameters which distinguish ciphers of the same name, e.g. `e = 3, n = 17, 11 rounds, modulus 0x20009`.
+    fn parameters(&self) -> String;
+
+    /// Replaces the plaintext `block` with its ciphertext.
+    fn encrypt_in_place(&self, block: &mut FieldElement, key: &FieldElement) {
+        *block = self.encrypt(block, key);
+    }
+
+    /// Replaces the ciphertext `block` with its plaintext.
+    fn decrypt_in_place(&self, block: &mut FieldElement, key: &FieldElement) {
+        *block = self.decrypt(block, key);
+    }
+
+    /// Encrypts every block in place with the same key (as in ECB mode).
+    fn encrypt_many(&self, blocks: &mut [FieldElement], key: &FieldElement) {
+        for block in blocks {
+            self.encrypt_in_place(block, key);
+        }
+    }
+
+    /// Decrypts every block in place with the same key.
+    fn decrypt_many(&self, blocks: &mut [FieldElement], key: &FieldElement) {
+        for block in blocks {
+            self.decrypt_in_place(block, key);
+        }
+    }
+}
+
+/// Block cipher with a public tweak, which selects one of the permutations given by the key.
+pub trait TweakableCipher: Cipher {
+    /// Number of bits of the tweak, same as the block size by default.
+    fn tweak_size(&self) -> u32 {
+        self.block_size()
+    }
+
+    fn encrypt_tweaked(&self, plaintext: &FieldElement, key: &FieldElement, tweak: &FieldElement) -> FieldElement;
+    fn decrypt_tweaked(&self, ciphertext: &FieldElement, key: &FieldElement, tweak: &FieldElement) -> FieldElement;
 }
 
 pub fn gcd(a: u128, b: u128) -> u128 {
-    return if b == 0 { a } else { gcd(b, a % b ) }
+    if b == 0 { a } else { gcd(b, a % b ) }
 }
 
-/// Generates random bit array of size `block_size`. Bits are from right to left, i.e. [0, 1] -> 1
-/// and [1, 0] -> 2
+/// Generates random field element of size `block_size` bits.
 /// # Example
 /// ```
+/// # use mimcge::{generate_random_bits, Uint};
 /// let bits = generate_random_bits(4);
 ///
-/// assert_eq!(bits, vec![0, 1, 1, 1]);
+/// assert!(bits.value() < Uint::from(16u64));
 /// ```
 pub fn generate_random_bits(block_size: u32) -> FieldElement {
-    let mut result: Vec<u8> = Vec::with_capacity(block_size as usize);
-    for _ in 0..block_size {
-        result.push(thread_rng().gen_range(0..=1));
-    }
-    result
+    FieldElement::from_uint(Uint::from_limbs(thread_rng().gen()), block_size)
 }
 
 /// Generates random round constants for MiMC type cipher, where the first constant is 0.
 pub fn generate_round_constants(size: usize, block_size: u32) -> Vec<FieldElement> {
     let mut result: Vec<FieldElement> = Vec::with_capacity(size);
-    result.push(vec![0; block_size as usize]); // c_0 must be 0
+    result.push(FieldElement::ZERO); // c_0 must be 0
     for _ in 1..size {
         result.push(generate_random_bits(block_size));
     }
     result
 }
 
+/// Seed of the round keys of [`KeySchedule::Independent`], mixed with the key.
+const KEY_SEED: u64 = 0x4B65_7953_6368; // "KeySch"
+
+/// SplitMix64 generator starting from the state.
+fn split_mix(mut state: u64) -> impl FnMut() -> u64 {
+    move || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Elements of `block_size` bits made of the SplitMix64 outputs from the seed, [`LIMBS`] outputs each.
+fn split_mix_elements(seed: u64, block_size: u32) -> impl Iterator<Item = FieldElement> {
+    let mut next = split_mix(seed);
+    std::iter::repeat_with(move || FieldElement::from_uint(Uint::from_limbs(std::array::from_fn(|_| next())), block_size))
+}
+
+/// Round constants which are the same in every run, for constructions which must be reproducible such as hash
+/// functions. Same as [`generate_round_constants`], but the constants are taken from SplitMix64 generator with a fixed
+/// seed instead of a random one.
+pub fn fixed_round_constants(size: usize, block_size: u32) -> Vec<FieldElement> {
+    let mut result: Vec<FieldElement> = Vec::with_capacity(size);
+    result.push(FieldElement::ZERO); // c_0 must be 0
+    result.extend(split_mix_elements(0x4D69_4D43_4765, block_size).take(size.saturating_sub(1))); // "MiMCGe"
+    result
+}
+
 /// Converts bit array to decimal expression
 pub fn to_decimal(bits: &[u8]) -> u128 {
     let mut result: u128 = 0;
-    let mut multiple = 1;
-    for &bit in bits.iter().rev() {
-        result += (bit as u128) * multiple;
-        multiple *= 2;
+    for &bit in bits.iter() {
+        result = (result << 1) | bit as u128;
     }
     result
 }
 
 /// Converts number to bit array expression
-pub fn to_binary(number: u128, block_size: u32) -> FieldElement {
+pub fn to_binary(number: u128, block_size: u32) -> Vec<u8> {
     let b = block_size as usize;
     let mut result = vec![0u8; b];
     let mut state = number;
@@ -89,53 +658,118 @@ pub fn to_binary(number: u128, block_size: u32) -> FieldElement {
 
 /// Adds elements over 2^n field for any n
 pub fn add_finite_field(a: &FieldElement, b: &FieldElement) -> FieldElement {
-    let mut result = a.to_vec();
-    for i in 0..result.len() {
-        result[i] = a[i] ^ b[i];
+    *a ^ *b
+}
+
+/// Integer types the field arithmetic can run on, i.e. u128 and [`Uint`].
+pub(crate) trait Bits: Copy + Default + PartialEq + From<u64> + BitAnd<Output = Self> + BitXor<Output = Self>
+    + Shl<u32, Output = Self> + Shr<u32, Output = Self> {
+    /// All ones if the lowest bit is set, zero otherwise, computed without branching.
+    fn spread_low_bit(self) -> Self;
+}
+
+impl Bits for u128 {
+    fn spread_low_bit(self) -> Self {
+        (self & 1).wrapping_neg()
+    }
+}
+
+impl Bits for Uint {
+    fn spread_low_bit(self) -> Self {
+        Uint::from_limbs([(self.limbs()[0] & 1).wrapping_neg(); LIMBS])
     }
-    result
 }
 
-fn _multiply_finite_field(mut a: u128, mut b: u128, block_size: u32) -> u128 {
-    let poly = IRREDUCIBLE_POLYNOMIALS[&block_size];
-    let mut p = 0u128;
-    while a != 0 && b != 0 {
-        if (b & 1) >= 1 {
-            p ^= a;
+pub(crate) fn _multiply_finite_field<T: Bits>(mut a: T, mut b: T, poly: (T, T)) -> T {
+    let (zero, one) = (T::default(), T::from(1));
+    let mut p = zero;
+    while a != zero && b != zero {
+        if b & one != zero {
+            p = p ^ a;
         }
 
-        if (a & poly.0) >= 1 {
+        if a & poly.0 != zero {
             a = (a << 1) ^ poly.1;
         } else {
-            a <<= 1;
+            a = a << 1;
         }
-        b >>= 1;
+        b = b >> 1;
     }
     p
 }
 
-/// Multiplication in extension field 2^n for n as `block_size`. For every `block_size`, multiplication must be
-/// implemented separately as this is f(x) * g(x) mod h(x) where h(x) is the irreducible polynomial (equivalent to prime
-/// number in rings) which is different for every field.
+/// Same as [`_multiply_finite_field`], but the number of iterations and the operations done in each of them do not
+/// depend on the operands: every bit of `b` is processed and the conditional additions of `a` and of the reduction
+/// polynomial are done by masks.
+pub(crate) fn _multiply_constant_time<T: Bits>(mut a: T, b: T, poly: (T, T), block_size: u32) -> T {
+    let mut p = T::default();
+    for i in 0..block_size {
+        p = p ^ (a & (b >> i).spread_low_bit());
+        let carry = ((a & poly.0) >> (block_size - 1)).spread_low_bit();
+        a = (a << 1) ^ (poly.1 & carry);
+    }
+    p
+}
+
+/// Multiplication in extension field 2^n for n as `block_size`. For every `block_size`, multiplication is different
+/// as this is f(x) * g(x) mod h(x) where h(x) is the irreducible polynomial (equivalent to prime number in rings) which
+/// is different for every field (see [`irreducible_polynomial`]).
 ///
 /// This specific implementation uses [Russian peasant multiplication
 /// algorithm](https://en.wikipedia.org/wiki/Finite_field_arithmetic#C_programming_example). This can be further
 /// optimized using [precomputed tables](https://en.wikipedia.org/wiki/Finite_field_arithmetic#Generator_based_tables),
 /// [hardware specific instructions](https://en.wikipedia.org/wiki/Carry-less_product) or any other method. However, for
 /// small enough fields, this method is rather fast enough.
-pub fn multiply_finite_field(a: &FieldElement, b: &FieldElement, block_size: u32) -> FieldElement {
-    assert!(IRREDUCIBLE_POLYNOMIALS.contains_key(&block_size), "Multiplication for this block size is not implemented");
-    to_binary(_multiply_finite_field(to_decimal(&a), to_decimal(&b), block_size), block_size)
+///
+/// Like all the functions taking `block_size`, returns an error if there is no field for it (see [`BinaryField::new`]).
+pub fn multiply_finite_field(a: &FieldElement, b: &FieldElement, block_size: u32) -> Result<FieldElement> {
+    Ok(BinaryField::new(block_size)?.multiply(a, b))
+}
+
+/// Fast exponentiation implementation using [square and multiply](https://en.wikipedia.org/wiki/Exponentiation_by_squaring)
+/// algorithm with sliding window (see [`ExponentiationPlan`]).
+pub fn square_multiply(a: &FieldElement, exponent: u128, block_size: u32) -> Result<FieldElement> {
+    Ok(BinaryField::new(block_size)?.power(a, exponent))
+}
+
+/// Multiplicative inverse in extension field 2^n for n as `block_size`, i.e. a^-1 such that a * a^-1 = 1, computed by
+/// the [extended Euclidean algorithm](https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm#Simple_algebraic_field_extensions)
+/// over GF(2)\[x\]. This is equal to a^(2^n - 2), but it does not need n squarings and multiplications.
+///
+/// Returns an error for zero, which has no inverse.
+pub fn invert_finite_field(a: &FieldElement, block_size: u32) -> Result<FieldElement> {
+    BinaryField::new(block_size)?.invert(a)
+}
+
+/// Division a / b = a * b^-1 in extension field 2^n for n as `block_size`, see [`invert_finite_field`].
+///
+/// Returns an error if `b` is zero.
+pub fn divide_finite_field(a: &FieldElement, b: &FieldElement, block_size: u32) -> Result<FieldElement> {
+    BinaryField::new(block_size)?.divide(a, b)
+}
+
+/// Absolute trace Tr(a) = a + a^2 + a^4 + ... + a^(2^(n-1)) in extension field 2^n for n as `block_size`. Trace is a
+/// linear map onto GF(2), thus the result is a single bit.
+pub fn trace_finite_field(a: &FieldElement, block_size: u32) -> Result<bool> {
+    Ok(BinaryField::new(block_size)?.trace(a))
+}
+
+/// Half-trace H(a) = a + a^4 + a^16 + ... + a^(4^((n-1)/2)) in extension field 2^n for odd n as `block_size`. For c with
+/// Tr(c) = 0, H(c) is a solution of x^2 + x = c.
+///
+/// Returns an error if `block_size` is even.
+pub fn half_trace_finite_field(a: &FieldElement, block_size: u32) -> Result<FieldElement> {
+    BinaryField::new(block_size)?.half_trace(a)
 }
 
-fn _square_multiply(y: u128, x: u128, exponent: u128, block_size: u32) -> u128 {
-    return if exponent == 0 { y }
-    else if exponent % 2 == 0 { _square_multiply(y, _multiply_finite_field(x, x, block_size), exponent / 2, block_size) }
-    else { _square_multiply(_multiply_finite_field(x, y, block_size), _multiply_finite_field(x, x, block_size), (exponent - 1) / 2, block_size) }
+/// Square root a^(2^(n-1)) in extension field 2^n for n as `block_size`. Squaring is a permutation of the field, so
+/// every element has exactly one square root.
+pub fn sqrt_finite_field(a: &FieldElement, block_size: u32) -> Result<FieldElement> {
+    Ok(BinaryField::new(block_size)?.sqrt(a))
 }
 
-/// Fast exponentiation implementation using [square and multiply](https://en.wikipedia.org/wiki/Exponentiation_by_squaring) algorithm.
-pub fn square_multiply(a: &FieldElement, exponent: u128, block_size: u32) -> FieldElement {
-    assert!(IRREDUCIBLE_POLYNOMIALS.contains_key(&block_size), "Multiplication for this block size is not implemented");
-    to_binary(_square_multiply(1, to_decimal(&a), exponent, block_size), block_size)
+/// One of the solutions of x^2 + x = c in extension field 2^n for n as `block_size`, the other one is x + 1. Solution
+/// exists if and only if Tr(c) = 0, otherwise `None` is returned.
+pub fn solve_quadratic_finite_field(c: &FieldElement, block_size: u32) -> Result<Option<FieldElement>> {
+    Ok(BinaryField::new(block_size)?.solve_quadratic(c))
 }
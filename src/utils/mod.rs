@@ -1 +1,10 @@
+pub mod exponentiation;
+pub mod frobenius;
+pub mod g2p_field;
 pub mod helpers;
+pub mod number_theory;
+pub mod polynomials;
+pub mod power_map;
+pub mod prime_field;
+pub mod sbox;
+pub mod uint;
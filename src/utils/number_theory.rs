@@ -0,0 +1,257 @@
+//! Integer arithmetic modulo `m` on u128 without overflow, primality testing and factorization. Needed to check
+//! whether reduction polynomial is primitive, i.e. factors of 2^n - 1 must be known.
+
+use crate::utils::helpers::gcd;
+
+/// (a + b) mod m for a, b < m.
+pub fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= m { sum.wrapping_sub(m) } else { sum }
+}
+
+/// (a * b) mod m for a, b < m using double and add, so the product never overflows.
+pub fn mul_mod(mut a: u128, mut b: u128, m: u128) -> u128 {
+    if let (Ok(x), Ok(y)) = (u64::try_from(a), u64::try_from(b)) {
+        return (x as u128 * y as u128) % m;
+    }
+    let mut result = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// a^e mod m using square and multiply.
+pub fn pow_mod(mut a: u128, mut e: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    while e != 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// Deterministic [Miller-Rabin](https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test) test. First 13
+/// primes as witnesses are enough for every n < 3.3 * 10^24, for larger n the probability of error is negligible.
+pub fn is_prime(n: u128) -> bool {
+    const WITNESSES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for a in WITNESSES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Full 256 bit product of a * b as (high, low) parts.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & u64::MAX as u128);
+    let (b1, b0) = (b >> 64, b & u64::MAX as u128);
+    let (low, middle, high) = (a0 * b0, a1 * b0, a1 * b1);
+    let (middle, carry) = middle.overflowing_add(a0 * b1);
+    let (low, carry_low) = low.overflowing_add(middle << 64);
+    (high + (middle >> 64) + ((carry as u128) << 64) + carry_low as u128, low)
+}
+
+/// [Montgomery multiplication](https://en.wikipedia.org/wiki/Montgomery_modular_multiplication) modulo odd `m` with
+/// R = 2^128. Numbers in Montgomery form are x * R mod m.
+struct Montgomery {
+    m: u128,
+    /// -m^(-1) mod R
+    m_neg_inv: u128,
+}
+
+impl Montgomery {
+    fn new(m: u128) -> Self {
+        // Newton's iteration doubles the correct bits of m^(-1) mod 2^k every step
+        let mut inv: u128 = 1;
+        for _ in 0..7 {
+            inv = inv.wrapping_mul(2u128.wrapping_sub(m.wrapping_mul(inv)));
+        }
+        Montgomery { m, m_neg_inv: inv.wrapping_neg() }
+    }
+
+    /// a * b * R^(-1) mod m
+    fn multiply(&self, a: u128, b: u128) -> u128 {
+        let (high, low) = widening_mul(a, b);
+        let (carry_high, carry_low) = widening_mul(low.wrapping_mul(self.m_neg_inv), self.m);
+        // low + carry_low is 0 mod R, only the carry is left
+        let carry = low.overflowing_add(carry_low).1 as u128;
+        let (result, overflow) = high.overflowing_add(carry_high);
+        let (result, overflow_carry) = result.overflowing_add(carry);
+        if overflow || overflow_carry || result >= self.m { result.wrapping_sub(self.m) } else { result }
+    }
+}
+
+/// Finds non-trivial divisor of odd composite `n` using [Pollard's
+/// rho](https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm) with Brent's cycle detection. Differences are
+/// multiplied together, so gcd is computed only once for a batch of steps.
+fn pollard_rho(n: u128) -> u128 {
+    const BATCH: usize = 128;
+    let montgomery = Montgomery::new(n);
+    for c in 1.. {
+        // Polynomial x^2 + c in Montgomery form, the divisor is the same as gcd(xR - yR, n) = gcd(x - y, n)
+        let f = |x: u128| add_mod(montgomery.multiply(x, x), c, n);
+        let (mut x, mut y, mut saved) = (2u128, 2u128, 2u128);
+        let mut product = 1u128;
+        let mut length = 1;
+        let mut d = 1;
+        while d == 1 {
+            x = y;
+            for _ in 0..length {
+                y = f(y);
+            }
+            let mut step = 0;
+            while step < length && d == 1 {
+                saved = y;
+                for _ in 0..BATCH.min(length - step) {
+                    y = f(y);
+                    product = montgomery.multiply(product, x.abs_diff(y));
+                }
+                d = gcd(product, n);
+                step += BATCH;
+            }
+            length *= 2;
+        }
+        if d == n {
+            // Batch skipped over the divisor, repeat the last batch one step at a time
+            d = 1;
+            while d == 1 {
+                saved = f(saved);
+                d = gcd(x.abs_diff(saved), n);
+            }
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+/// Distinct prime factors of `n` in ascending order.
+pub fn prime_factors(n: u128) -> Vec<u128> {
+    let mut result = Vec::new();
+    let mut remaining = vec![n];
+    while let Some(mut m) = remaining.pop() {
+        // Small factors are removed by trial division, as Pollard's rho is slow for them
+        for p in 2..1000 {
+            while m.is_multiple_of(p) {
+                result.push(p);
+                m /= p;
+            }
+        }
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            result.push(m);
+        } else {
+            let d = pollard_rho(m);
+            remaining.push(d);
+            remaining.push(m / d);
+        }
+    }
+    result.sort();
+    result.dedup();
+    result
+}
+
+/// Distinct prime factors of 2^n - 1. The number is split first using its algebraic factors 2^d - 1 for every d dividing
+/// n, so Pollard's rho only needs to factor much smaller parts (e.g. 2^122 - 1 = 3 * (2^61 - 1) * (2^61 + 1) / 3 would
+/// take minutes otherwise).
+pub fn mersenne_prime_factors(n: u32) -> Vec<u128> {
+    let mut parts = vec![(1u128 << n) - 1];
+    for d in (1..n).filter(|d| n.is_multiple_of(*d)) {
+        let divisor = (1u128 << d) - 1;
+        parts = parts.into_iter().flat_map(|x| {
+            let g = gcd(x, divisor);
+            if g == 1 || g == x { vec![x] } else { vec![g, x / g] }
+        }).collect();
+    }
+    let mut result: Vec<u128> = parts.into_iter().flat_map(prime_factors).collect();
+    result.sort();
+    result.dedup();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::number_theory::{is_prime, mersenne_prime_factors, Montgomery, mul_mod, pow_mod, prime_factors, widening_mul};
+
+    #[test]
+    fn multiply_without_overflow() {
+        let m = (1u128 << 127) - 1;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1); // (-1) * (-1) = 1
+        assert_eq!(mul_mod(1 << 100, 1 << 100, m), 1 << 73); // 2^200 = 2^73 mod 2^127 - 1
+    }
+
+    #[test]
+    fn full_product() {
+        assert_eq!(widening_mul(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(widening_mul(1 << 100, 1 << 100), (1 << 72, 0));
+    }
+
+    #[test]
+    fn montgomery_multiplication() {
+        let m = (1u128 << 127) - 1;
+        let montgomery = Montgomery::new(m);
+        // R = 2^128 = 2 mod m, so the result is a * b * 2^(-1)
+        assert_eq!(montgomery.multiply(6, 5), 15);
+        assert_eq!(montgomery.multiply(m - 1, 2), m - 1);
+    }
+
+    #[test]
+    fn fermat_little_theorem() {
+        let p = (1u128 << 127) - 1;
+        assert_eq!(pow_mod(3, p - 1, p), 1);
+    }
+
+    #[test]
+    fn primes() {
+        assert!(is_prime(2));
+        assert!(is_prime(131071)); // 2^17 - 1
+        assert!(is_prime((1 << 127) - 1));
+        assert!(!is_prime(1));
+        assert!(!is_prime(341)); // Fermat pseudoprime to base 2
+        assert!(!is_prime(3215031751)); // Strong pseudoprime to bases 2, 3, 5, 7
+    }
+
+    #[test]
+    fn factors() {
+        assert_eq!(prime_factors(255), vec![3, 5, 17]);
+        assert_eq!(prime_factors((1 << 64) - 1), vec![3, 5, 17, 257, 641, 65537, 6700417]);
+        assert_eq!(prime_factors((1 << 101) - 1), vec![7432339208719, 341117531003194129]);
+    }
+
+    #[test]
+    fn mersenne_factors() {
+        assert_eq!(mersenne_prime_factors(8), vec![3, 5, 17]);
+        assert_eq!(mersenne_prime_factors(122), vec![3, 768614336404564651, 2305843009213693951]);
+        assert_eq!(mersenne_prime_factors(127), vec![(1 << 127) - 1]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Polynomials over GF(2) packed into integers, i.e. x^4 + x + 1 is `0b10011`. Used to find irreducible polynomials
+//! (reduction polynomials of 2^n fields) for block sizes which are not in the predefined table and to verify user
+//! supplied ones.
+
+use crate::utils::number_theory::mersenne_prime_factors;
+use crate::utils::uint::Uint;
+
+/// x as a polynomial.
+const X: Uint = Uint::from_u128(0b10);
+
+/// Degree of the polynomial. Zero polynomial is treated as degree 0.
+pub fn degree(a: Uint) -> u32 {
+    a.bits().saturating_sub(1)
+}
+
+/// Remainder of polynomial division a(x) mod b(x).
+fn remainder(mut a: Uint, b: Uint) -> Uint {
+    let db = degree(b);
+    while !a.is_zero() && degree(a) >= db {
+        a = a ^ (b << (degree(a) - db));
+    }
+    a
+}
+
+fn gcd(a: Uint, b: Uint) -> Uint {
+    if b.is_zero() { a } else { gcd(b, remainder(a, b)) }
+}
+
+/// Multiplication a(x) * b(x) mod f(x), where a(x) and b(x) are already reduced, i.e. have degree lower than f(x).
+fn multiply_mod(mut a: Uint, mut b: Uint, f: Uint) -> Uint {
+    let top = degree(f) - 1;
+    let mut p = Uint::ZERO;
+    while !a.is_zero() && !b.is_zero() {
+        if b.bit(0) {
+            p = p ^ a;
+        }
+        a = if a.bit(top) { (a << 1) ^ f } else { a << 1 };
+        b = b >> 1;
+    }
+    p
+}
+
+/// Inverse of a(x) modulo irreducible f(x) using the extended Euclidean algorithm, i.e. b(x) such that
+/// a(x) * b(x) = 1 mod f(x). Only the coefficient of a(x) is tracked, as the one of f(x) is not needed. Returns `None`
+/// for zero polynomial.
+pub fn inverse_mod(a: Uint, f: Uint) -> Option<Uint> {
+    if a.is_zero() {
+        return None;
+    }
+    // Invariant: u = g1 * a and v = g2 * a mod f(x)
+    let (mut u, mut v) = (remainder(a, f), f);
+    let (mut g1, mut g2) = (Uint::ONE, Uint::ZERO);
+    while u != Uint::ONE {
+        if u.is_zero() {
+            return None; // a(x) and f(x) have a common factor, only possible for reducible f(x)
+        }
+        if degree(u) < degree(v) {
+            std::mem::swap(&mut u, &mut v);
+            std::mem::swap(&mut g1, &mut g2);
+        }
+        let shift = degree(u) - degree(v);
+        u = u ^ (v << shift);
+        g1 = g1 ^ (g2 << shift);
+    }
+    Some(g1)
+}
+
+/// a(x)^e mod f(x) using square and multiply.
+fn power_mod(mut a: Uint, mut e: u128, f: Uint) -> Uint {
+    let mut result = Uint::ONE;
+    while e != 0 {
+        if e & 1 == 1 {
+            result = multiply_mod(result, a, f);
+        }
+        a = multiply_mod(a, a, f);
+        e >>= 1;
+    }
+    result
+}
+
+/// Checks if polynomial f(x) of degree n is irreducible over GF(2) using Ben-Or's test: f(x) is irreducible if and only
+/// if gcd(x^(2^i) - x, f(x)) = 1 for every i <= n / 2, as x^(2^i) - x is the product of all irreducible polynomials
+/// with degree dividing i. Reducible polynomials usually have a small factor, so they are rejected after a few steps.
+pub fn is_irreducible(f: Uint) -> bool {
+    let n = degree(f);
+    if n == 0 {
+        return false;
+    }
+    // x^(2^i) mod f(x) by repeated squaring of x
+    let mut frobenius = X;
+    for _ in 0..n / 2 {
+        frobenius = multiply_mod(frobenius, frobenius, f);
+        if gcd(f, frobenius ^ X) != Uint::ONE {
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks if irreducible polynomial f(x) of degree n is primitive, i.e. x generates the whole multiplicative group of
+/// the field, so x^((2^n - 1) / q) != 1 mod f(x) for every prime q dividing 2^n - 1. Non-primitive polynomials still
+/// define the same field, only the representation differs.
+///
+/// Returns `None` for degrees above 127, as 2^n - 1 is not factored then.
+pub fn is_primitive(f: Uint) -> Option<bool> {
+    let n = degree(f);
+    if n >= u128::BITS {
+        return None;
+    }
+    let order = (1u128 << n) - 1;
+    Some(is_irreducible(f) && mersenne_prime_factors(n).iter().all(|q| power_mod(X, order / q, f) != Uint::ONE))
+}
+
+/// Parses polynomial either from hex (`0x80000000000000000000000000000003`) or from the list of exponents of its terms
+/// (`127,1,0`), both being x^127 + x + 1.
+pub fn parse_polynomial(s: &str) -> Result<Uint, String> {
+    if s.starts_with("0x") || s.starts_with("0X") {
+        return s.parse().map_err(|e| format!("invalid hex polynomial: {e}"));
+    }
+    let mut result = Uint::ZERO;
+    for term in s.split(',') {
+        let exponent: u32 = term.trim().parse().map_err(|e| format!("invalid exponent '{}': {e}", term.trim()))?;
+        if exponent >= Uint::BITS {
+            return Err(format!("exponent {exponent} is too large, maximum degree is {}", Uint::BITS - 1));
+        }
+        result = result | (Uint::ONE << exponent);
+    }
+    Ok(result)
+}
+
+/// Finds irreducible polynomial of degree `n` with the least amount of terms. First, trinomials x^n + x^k + 1 are
+/// checked, then pentanomials x^n + x^a + x^b + x^c + 1 (lowest middle terms first). For every n > 1 up to 511 at least
+/// one of these exists.
+///
+/// # Panics
+///
+/// Panics if degree is not in range 2 <= n <= 511.
+pub fn find_irreducible_polynomial(n: u32) -> Uint {
+    assert!((2..Uint::BITS).contains(&n), "Irreducible polynomial search is implemented for degrees 2 to 511");
+    let term = |k: u32| Uint::ONE << k;
+    let base = term(n) | Uint::ONE;
+    (1..n).map(|k| base | term(k))
+        .chain((3..n).flat_map(|a| (2..a).flat_map(move |b| (1..b).map(move |c| base | term(a) | term(b) | term(c)))))
+        .find(|&f| is_irreducible(f))
+        .expect("Irreducible trinomial or pentanomial must exist")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::polynomials::{find_irreducible_polynomial, inverse_mod, is_irreducible, is_primitive, multiply_mod, parse_polynomial};
+    use crate::utils::uint::Uint;
+
+    fn poly(value: u128) -> Uint {
+        Uint::from(value)
+    }
+
+    #[test]
+    fn irreducible_known() {
+        assert!(is_irreducible(poly(0x25))); // x^5 + x^2 + 1
+        assert!(is_irreducible(poly(0x11D))); // x^8 + x^4 + x^3 + x^2 + 1
+        assert!(is_irreducible(poly(0x80000000000000000000000000000003))); // x^127 + x + 1
+        assert!(is_irreducible(parse_polynomial("128,7,2,1,0").unwrap())); // GCM polynomial
+        assert!(is_irreducible(parse_polynomial("255,52,0").unwrap()));
+    }
+
+    #[test]
+    fn reducible_known() {
+        assert!(!is_irreducible(poly(0b101))); // x^2 + 1 = (x + 1)^2
+        assert!(!is_irreducible(poly(0b10101))); // x^4 + x^2 + 1 = (x^2 + x + 1)^2
+        assert!(!is_irreducible(poly(0b100011))); // x^5 + x + 1 = (x^2 + x + 1)(x^3 + x^2 + 1)
+        assert!(!is_irreducible(parse_polynomial("256,1,0").unwrap())); // Trinomials of degree 8k are reducible
+    }
+
+    #[test]
+    fn find_trinomial() {
+        assert_eq!(find_irreducible_polynomial(17), poly(0x20009)); // x^17 + x^3 + 1
+        assert_eq!(find_irreducible_polynomial(127), poly(0x80000000000000000000000000000003)); // x^127 + x + 1
+        assert_eq!(find_irreducible_polynomial(257), parse_polynomial("257,12,0").unwrap());
+    }
+
+    #[test]
+    fn find_pentanomial() {
+        // There are no irreducible trinomials of degree 8
+        assert_eq!(find_irreducible_polynomial(8), poly(0x11B)); // x^8 + x^4 + x^3 + x + 1
+        // Neither of degree 13
+        assert_eq!(find_irreducible_polynomial(13), poly(0x201B)); // x^13 + x^4 + x^3 + x + 1
+        // Nor 128, the lowest one is the GCM polynomial
+        assert_eq!(find_irreducible_polynomial(128), parse_polynomial("128,7,2,1,0").unwrap());
+    }
+
+    #[test]
+    fn inverse() {
+        // AES S-box: inverse of 0x53 is 0xCA
+        assert_eq!(inverse_mod(poly(0x53), poly(0x11B)), Some(poly(0xCA)));
+        assert_eq!(inverse_mod(Uint::ONE, poly(0x11B)), Some(Uint::ONE));
+        assert_eq!(inverse_mod(Uint::ZERO, poly(0x11B)), None);
+        let f = parse_polynomial("257,12,0").unwrap();
+        let a = Uint::mask(256);
+        assert_eq!(multiply_mod(a, inverse_mod(a, f).unwrap(), f), Uint::ONE);
+    }
+
+    #[test]
+    fn primitive() {
+        assert_eq!(is_primitive(poly(0x11D)), Some(true)); // x^8 + x^4 + x^3 + x^2 + 1
+        assert_eq!(is_primitive(poly(0x11B)), Some(false)); // AES polynomial is irreducible, but x has order 51
+        assert_eq!(is_primitive(poly(0x80000000000000000000000000000003)), Some(true)); // 2^127 - 1 is prime
+        assert_eq!(is_primitive(poly(0b101)), Some(false)); // Reducible
+        assert_eq!(is_primitive(parse_polynomial("128,7,2,1,0").unwrap()), None);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_polynomial("0x80000000000000000000000000000003"), Ok(poly(0x80000000000000000000000000000003)));
+        assert_eq!(parse_polynomial("127,1,0"), Ok(poly(0x80000000000000000000000000000003)));
+        assert_eq!(parse_polynomial("17, 3, 0"), Ok(poly(0x20009)));
+        assert_eq!(parse_polynomial("511,0"), Ok((Uint::ONE << 511) | Uint::ONE));
+        assert!(parse_polynomial("0xZZ").is_err());
+        assert!(parse_polynomial("512,0").is_err());
+        assert!(parse_polynomial("5,x").is_err());
+    }
+}
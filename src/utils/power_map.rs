@@ -0,0 +1,108 @@
+//! Power map x -> x^e of GF(2^n) and its inverse x -> x^s, the non-linear layer of MiMC type ciphers. Small fields use
+//! lookup tables, larger ones precomputed exponentiation plans.
+
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::frobenius::FrobeniusPlan;
+use crate::utils::helpers::{Backend, BinaryField, DecryptionMethod, FieldElement};
+use crate::utils::sbox::{MAX_SBOX_BLOCK_SIZE, SBox};
+use crate::utils::uint::Uint;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PowerMap {
+    field: BinaryField,
+    encryption_plan: ExponentiationPlan,
+    decryption_plan: ExponentiationPlan,
+    /// Decryption plan with Frobenius maps, `None` if decryption uses square and multiply or tables.
+    frobenius_plan: Option<FrobeniusPlan>,
+    /// Tables for block sizes up to [`MAX_SBOX_BLOCK_SIZE`].
+    sbox: Option<SBox>,
+}
+
+impl PowerMap {
+    /// Power map x^`exponent` with inverse x^`decryption_exponent` over the `field`. Decryption method is only used
+    /// when the field is too large for the tables. Constant-time fields use neither tables nor Frobenius maps, as their
+    /// lookups are indexed by the secret state. Neither do fields with g2p backend, so the rounds run on the backend.
+    pub fn new(field: BinaryField, exponent: u128, decryption_exponent: Uint, decryption_method: DecryptionMethod) -> Self {
+        let encryption_plan = ExponentiationPlan::new(Uint::from(exponent));
+        let decryption_plan = ExponentiationPlan::new(decryption_exponent);
+        let use_tables = !field.is_constant_time() && field.backend() == Backend::Native;
+        let sbox = (use_tables && field.block_size() <= MAX_SBOX_BLOCK_SIZE).then(|| SBox::new(&field, &encryption_plan));
+        let frobenius_plan = match decryption_method {
+            DecryptionMethod::Frobenius if use_tables && sbox.is_none() => Some(FrobeniusPlan::new(field, decryption_plan.clone())),
+            _ => None,
+        };
+        PowerMap { field, encryption_plan, decryption_plan, frobenius_plan, sbox }
+    }
+
+    pub fn encryption_plan(&self) -> &ExponentiationPlan {
+        &self.encryption_plan
+    }
+
+    pub fn decryption_plan(&self) -> &ExponentiationPlan {
+        &self.decryption_plan
+    }
+
+    /// Whether the map is computed by lookup tables.
+    pub fn is_table(&self) -> bool {
+        self.sbox.is_some()
+    }
+
+    /// a^e
+    pub fn forward(&self, a: &FieldElement) -> FieldElement {
+        match &self.sbox {
+            Some(sbox) => sbox.forward(a),
+            None => self.field.power_with(a, &self.encryption_plan),
+        }
+    }
+
+    /// a^s, where s is the decryption exponent.
+    pub fn inverse(&self, a: &FieldElement) -> FieldElement {
+        match (&self.sbox, &self.frobenius_plan) {
+            (Some(sbox), _) => sbox.inverse(a),
+            (None, Some(plan)) => plan.power(a),
+            (None, None) => self.field.power_with(a, &self.decryption_plan),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mimc_general::mimc_general::{decryption_exponent, find_t};
+    use crate::utils::helpers::{BinaryField, DecryptionMethod, generate_random_bits};
+    use crate::utils::power_map::PowerMap;
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn table_matches_exponentiation() {
+        let field = BinaryField::new(11).unwrap();
+        let s = decryption_exponent(5, find_t(5, Uint::mask(11)).unwrap(), Uint::mask(11));
+        let table = PowerMap::new(field, 5, s, DecryptionMethod::Frobenius);
+        assert!(table.is_table());
+        for _ in 0..100 {
+            let a = generate_random_bits(11);
+            assert_eq!(table.forward(&a), field.power(&a, 5));
+            assert_eq!(table.inverse(&a), field.power_wide(&a, s));
+        }
+    }
+
+    #[test]
+    fn constant_time() {
+        let field = BinaryField::new(11).unwrap().with_constant_time(true).unwrap();
+        let s = decryption_exponent(5, find_t(5, Uint::mask(11)).unwrap(), Uint::mask(11));
+        let map = PowerMap::new(field, 5, s, DecryptionMethod::Frobenius);
+        assert!(!map.is_table());
+        let a = generate_random_bits(11);
+        assert_eq!(map.forward(&a), BinaryField::new(11).unwrap().power(&a, 5));
+        assert_eq!(map.inverse(&map.forward(&a)), a);
+    }
+
+    #[test]
+    fn large_field() {
+        let field = BinaryField::new(19).unwrap();
+        let s = decryption_exponent(3, find_t(3, Uint::mask(19)).unwrap(), Uint::mask(19));
+        let map = PowerMap::new(field, 3, s, DecryptionMethod::SquareMultiply);
+        assert!(!map.is_table());
+        let a = generate_random_bits(19);
+        assert_eq!(map.inverse(&map.forward(&a)), a);
+    }
+}
@@ -0,0 +1,325 @@
+//! Prime field GF(p) for primes up to 256 bits. Elements are stored in [`FieldElement`] as integers from 0 to p - 1, so
+//! the same ciphers and experiments can work with both binary and prime fields. Primes larger than 64 bits use
+//! [Montgomery multiplication](https://en.wikipedia.org/wiki/Montgomery_modular_multiplication), so there is no
+//! division in the arithmetic.
+
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+use crate::error::{Error, Result};
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::FieldElement;
+use crate::utils::number_theory::is_prime;
+use crate::utils::uint::{LIMBS, Uint};
+
+/// Largest size of the prime in bits, so the product of two elements fits into [`Uint`].
+pub const MAX_PRIME_BITS: u32 = Uint::BITS / 2;
+
+/// Number of 64 bit limbs used by Montgomery arithmetic.
+const MONTGOMERY_LIMBS: usize = MAX_PRIME_BITS as usize / 64;
+
+/// Scalar field of BN254 (alt_bn128) curve used by Circom and gnark.
+pub const BN254_SCALAR: &str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+/// Scalar field of BLS12-381 curve.
+pub const BLS12_381_SCALAR: &str = "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+/// Montgomery arithmetic modulo odd `m` < 2^256 with R = 2^256. Numbers in Montgomery form are x * R mod m.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Montgomery {
+    m: [u64; MONTGOMERY_LIMBS],
+    /// -m^(-1) mod 2^64
+    m_neg_inv: u64,
+    /// R^2 mod m, used for the conversion to Montgomery form
+    r2: Uint,
+}
+
+impl Montgomery {
+    fn new(m: Uint) -> Self {
+        let low = m.limbs()[0];
+        // Newton's iteration doubles the correct bits of m^(-1) mod 2^k every step
+        let mut inv: u64 = 1;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(low.wrapping_mul(inv)));
+        }
+        let r = (Uint::ONE << MAX_PRIME_BITS).div_rem(m).1;
+        Montgomery {
+            m: m.limbs()[..MONTGOMERY_LIMBS].try_into().unwrap(),
+            m_neg_inv: inv.wrapping_neg(),
+            r2: (r * r).div_rem(m).1,
+        }
+    }
+
+    /// a * b * R^(-1) mod m using coarsely integrated operand scanning (CIOS), i.e. reduction is done after
+    /// multiplication by every limb of `b`.
+    fn multiply(&self, a: Uint, b: Uint) -> Uint {
+        const N: usize = MONTGOMERY_LIMBS;
+        let (a, b) = (a.limbs(), b.limbs());
+        let mut t = [0u64; N + 2];
+        for &b_i in &b[..N] {
+            let mut carry = 0u128;
+            for j in 0..N {
+                let current = t[j] as u128 + a[j] as u128 * b_i as u128 + carry;
+                t[j] = current as u64;
+                carry = current >> 64;
+            }
+            let current = t[N] as u128 + carry;
+            t[N] = current as u64;
+            t[N + 1] = (current >> 64) as u64;
+
+            // Adding multiple of m, so the lowest limb becomes 0 and can be shifted out
+            let factor = t[0].wrapping_mul(self.m_neg_inv);
+            let mut carry = (t[0] as u128 + factor as u128 * self.m[0] as u128) >> 64;
+            for j in 1..N {
+                let current = t[j] as u128 + factor as u128 * self.m[j] as u128 + carry;
+                t[j - 1] = current as u64;
+                carry = current >> 64;
+            }
+            let current = t[N] as u128 + carry;
+            t[N - 1] = current as u64;
+            t[N] = t[N + 1] + (current >> 64) as u64;
+        }
+        let mut limbs = [0u64; LIMBS];
+        limbs[..=N].copy_from_slice(&t[..=N]);
+        let result = Uint::from_limbs(limbs);
+        let m = self.modulus();
+        if result >= m { result - m } else { result }
+    }
+
+    fn modulus(&self) -> Uint {
+        let mut limbs = [0u64; LIMBS];
+        limbs[..MONTGOMERY_LIMBS].copy_from_slice(&self.m);
+        Uint::from_limbs(limbs)
+    }
+
+    /// Converts to Montgomery form, a * R mod m.
+    fn convert(&self, a: Uint) -> Uint {
+        self.multiply(a, self.r2)
+    }
+
+    /// Converts from Montgomery form, a * R^(-1) mod m.
+    fn convert_back(&self, a: Uint) -> Uint {
+        self.multiply(a, Uint::ONE)
+    }
+}
+
+/// Prime field GF(p), arithmetic is done modulo `p`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrimeField {
+    modulus: Uint,
+    /// Only for primes which do not fit into u64
+    montgomery: Option<Montgomery>,
+}
+
+impl PrimeField {
+    /// Field of integers modulo prime `modulus`.
+    ///
+    /// Returns an error if `modulus` is not prime, is 2 or has more than [`MAX_PRIME_BITS`] bits.
+    pub fn new(modulus: Uint) -> Result<Self> {
+        if modulus.bits() > MAX_PRIME_BITS {
+            return Err(Error::PrimeTooLarge(modulus.bits()));
+        }
+        let field = PrimeField::new_unchecked(modulus);
+        if modulus <= Uint::from(2u64) || !field.is_prime() {
+            return Err(Error::NotPrime(modulus));
+        }
+        Ok(field)
+    }
+
+    /// Field by its name: `bn254` or `bls12-381` (scalar fields of these curves).
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "bn254" => Some(PrimeField::new_unchecked(BN254_SCALAR.parse().unwrap())),
+            "bls12-381" => Some(PrimeField::new_unchecked(BLS12_381_SCALAR.parse().unwrap())),
+            _ => None,
+        }
+    }
+
+    /// Field without checking that the modulus is prime.
+    fn new_unchecked(modulus: Uint) -> Self {
+        let montgomery = if modulus.bits() > u64::BITS { Some(Montgomery::new(modulus)) } else { None };
+        PrimeField { modulus, montgomery }
+    }
+
+    pub fn modulus(&self) -> Uint {
+        self.modulus
+    }
+
+    /// Number of bits needed to store an element.
+    pub fn block_size(&self) -> u32 {
+        self.modulus.bits()
+    }
+
+    /// log2(p), used for the number of rounds.
+    pub fn log2_order(&self) -> f64 {
+        let shift = self.block_size().saturating_sub(64);
+        ((self.modulus >> shift).low_u128() as f64).log2() + shift as f64
+    }
+
+    /// Reduces any value to the element of the field, i.e. a mod p.
+    pub fn reduce(&self, a: &FieldElement) -> FieldElement {
+        FieldElement::from_uint(a.value().div_rem(self.modulus).1, self.block_size())
+    }
+
+    /// (a + b) mod p
+    pub fn add(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        let sum = a.value() + b.value();
+        let sum = if sum >= self.modulus { sum - self.modulus } else { sum };
+        FieldElement::from_uint(sum, self.block_size())
+    }
+
+    /// (a - b) mod p
+    pub fn subtract(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        let difference = if a.value() >= b.value() { a.value() - b.value() } else { a.value() + self.modulus - b.value() };
+        FieldElement::from_uint(difference, self.block_size())
+    }
+
+    /// (a * b) mod p
+    pub fn multiply(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        let product = match &self.montgomery {
+            // a * b * R^(-1) * R^2 * R^(-1) = a * b
+            Some(montgomery) => montgomery.multiply(montgomery.multiply(a.value(), b.value()), montgomery.r2),
+            None => Uint::from((a.value().low_u128() * b.value().low_u128()) % self.modulus.low_u128()),
+        };
+        FieldElement::from_uint(product, self.block_size())
+    }
+
+    /// a^exponent mod p using square and multiply.
+    pub fn power(&self, a: &FieldElement, exponent: u128) -> FieldElement {
+        self.power_wide(a, Uint::from(exponent))
+    }
+
+    /// Same as [`PrimeField::power`] for exponents which do not fit into u128.
+    pub fn power_wide(&self, a: &FieldElement, exponent: Uint) -> FieldElement {
+        self.power_with(a, &ExponentiationPlan::new(exponent))
+    }
+
+    /// Exponentiation by precomputed plan, so the same exponent does not need to be scanned for every element.
+    pub fn power_with(&self, a: &FieldElement, plan: &ExponentiationPlan) -> FieldElement {
+        if let Some(montgomery) = &self.montgomery {
+            // Whole exponentiation is done in Montgomery form, so only two conversions are needed
+            let result = plan.apply(montgomery.convert(a.value()), montgomery.convert(Uint::ONE), |x, y| montgomery.multiply(x, y));
+            return FieldElement::from_uint(montgomery.convert_back(result), self.block_size());
+        }
+        plan.apply(*a, FieldElement::new(1, self.block_size()), |x, y| self.multiply(&x, &y))
+    }
+
+    /// Deterministic [Miller-Rabin](https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test) test of the
+    /// modulus, same as [`is_prime`], but with the field arithmetic for primes larger than u128.
+    fn is_prime(&self) -> bool {
+        const WITNESSES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+        if self.modulus.bits() <= u128::BITS {
+            return is_prime(self.modulus.low_u128());
+        }
+        let block_size = self.block_size();
+        let minus_one = FieldElement::from_uint(self.modulus - Uint::ONE, block_size);
+        let s = (0..).find(|&i| minus_one.value().bit(i)).unwrap();
+        let d = minus_one.value() >> s;
+        'witness: for a in WITNESSES {
+            let mut x = self.power_wide(&FieldElement::new(a, block_size), d);
+            if x == FieldElement::new(1, block_size) || x == minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = self.multiply(&x, &x);
+                if x == minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+}
+
+impl FromStr for PrimeField {
+    type Err = Error;
+
+    /// Parses prime either in decimal or hex and checks that it defines a field. Names of presets (see
+    /// [`PrimeField::preset`]) are accepted as well.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(field) = PrimeField::preset(s) {
+            return Ok(field);
+        }
+        PrimeField::new(s.parse::<Uint>()?)
+    }
+}
+
+impl fmt::Display for PrimeField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "GF({})", self.modulus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::utils::helpers::FieldElement;
+    use crate::utils::prime_field::{BLS12_381_SCALAR, BN254_SCALAR, Montgomery, PrimeField};
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn montgomery_multiplication() {
+        let m: Uint = BN254_SCALAR.parse().unwrap();
+        let montgomery = Montgomery::new(m);
+        let (a, b) = (Uint::ONE << 200, (Uint::ONE << 253) + Uint::from(12345u64));
+        let expected = (a * b).div_rem(m).1;
+        let result = montgomery.convert_back(montgomery.multiply(montgomery.convert(a), montgomery.convert(b)));
+        assert_eq!(result, expected);
+        assert_eq!(montgomery.convert_back(montgomery.convert(m - Uint::ONE)), m - Uint::ONE);
+    }
+
+    #[test]
+    fn presets() {
+        let bn254 = PrimeField::preset("bn254").unwrap();
+        assert_eq!(bn254.block_size(), 254);
+        assert_eq!(format!("{:#x}", bn254.modulus()), "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+        let bls = "BLS12-381".parse::<PrimeField>().unwrap();
+        assert_eq!(bls.modulus(), BLS12_381_SCALAR.parse().unwrap());
+        assert_eq!(format!("{:#x}", bls.modulus()), "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+        assert_eq!(PrimeField::preset("bn128"), None);
+    }
+
+    #[test]
+    fn arithmetic_bn254() {
+        let field = PrimeField::preset("bn254").unwrap();
+        let p = field.modulus();
+        let minus_one = FieldElement::from_uint(p - Uint::ONE, 254);
+        assert_eq!(field.multiply(&minus_one, &minus_one), FieldElement::new(1, 254));
+        // 5 is the multiplicative generator, so it is not a square: 5^((p - 1) / 2) = -1
+        assert_eq!(field.power_wide(&FieldElement::new(5, 254), (p - Uint::ONE) >> 1), minus_one);
+    }
+
+    #[test]
+    fn arithmetic_small() {
+        let field = PrimeField::new(Uint::from(101u64)).unwrap();
+        let (a, b) = (FieldElement::new(70, 7), FieldElement::new(50, 7));
+        assert_eq!(field.add(&a, &b), FieldElement::new(19, 7));
+        assert_eq!(field.subtract(&b, &a), FieldElement::new(81, 7));
+        assert_eq!(field.multiply(&a, &b), FieldElement::new(66, 7)); // 3500 = 34 * 101 + 66
+        assert_eq!(field.power(&a, 100), FieldElement::new(1, 7)); // Fermat's little theorem
+        assert_eq!(field.reduce(&FieldElement::new(127, 7)), FieldElement::new(26, 7));
+    }
+
+    #[test]
+    fn arithmetic_255() {
+        // 2^255 - 19
+        let p = (Uint::ONE << 255) - Uint::from(19u64);
+        let field = PrimeField::new(p).unwrap();
+        let minus_one = FieldElement::from_uint(p - Uint::ONE, 255);
+        assert_eq!(field.multiply(&minus_one, &minus_one), FieldElement::new(1, 255));
+        assert_eq!(field.add(&minus_one, &FieldElement::new(5, 255)), FieldElement::new(4, 255));
+        let a = FieldElement::from_uint(Uint::ONE << 200, 255);
+        assert_eq!(field.power_wide(&a, p - Uint::ONE), FieldElement::new(1, 255));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("101".parse::<PrimeField>(), Ok(PrimeField::new(Uint::from(101u64)).unwrap()));
+        assert!("57896044618658097711785492504343953926634992332820282019728792003956564819949".parse::<PrimeField>().is_ok()); // 2^255 - 19
+        assert_eq!("100".parse::<PrimeField>(), Err(Error::NotPrime(Uint::from(100u64))));
+        assert_eq!("2".parse::<PrimeField>(), Err(Error::NotPrime(Uint::from(2u64))));
+        assert!(format!("0x7{}", "f".repeat(63)).parse::<PrimeField>().is_err()); // 2^255 - 1 is divisible by 7
+        assert!(format!("0x1{}", "0".repeat(64)).parse::<PrimeField>().is_err()); // Too large
+    }
+}
@@ -0,0 +1,103 @@
+//! Lookup-table S-boxes for small fields. For n up to [`MAX_SBOX_BLOCK_SIZE`], the whole power map x -> x^e and its
+//! inverse fit into memory (2^17 entries each), so every round is a single lookup instead of an exponentiation.
+
+use crate::utils::exponentiation::ExponentiationPlan;
+use crate::utils::helpers::{BinaryField, FieldElement};
+use crate::utils::uint::Uint;
+
+/// Largest block size for which the tables are built, the two tables take 1 MiB there.
+pub const MAX_SBOX_BLOCK_SIZE: u32 = 17;
+
+/// Power map x -> x^e of GF(2^n) and its inverse as tables indexed by the packed element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SBox {
+    block_size: u32,
+    forward: Vec<u32>,
+    inverse: Vec<u32>,
+}
+
+impl SBox {
+    /// Tables of x -> x^e with e given by the `plan`. Inverse table is the inverse permutation of the forward one, so
+    /// the decryption exponent is not needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block size is larger than [`MAX_SBOX_BLOCK_SIZE`] or x^e is not a permutation.
+    pub fn new(field: &BinaryField, plan: &ExponentiationPlan) -> Self {
+        let block_size = field.block_size();
+        assert!(block_size <= MAX_SBOX_BLOCK_SIZE, "S-box tables are only built for block sizes up to {MAX_SBOX_BLOCK_SIZE}");
+        let forward: Vec<u32> = (0..1u32 << block_size)
+            .map(|x| field.power_with(&FieldElement::new(x as u128, block_size), plan).value().low_u128() as u32)
+            .collect();
+        let mut inverse = vec![u32::MAX; forward.len()];
+        for (x, &y) in forward.iter().enumerate() {
+            assert_eq!(inverse[y as usize], u32::MAX, "This is not a permutation polynomial");
+            inverse[y as usize] = x as u32;
+        }
+        SBox { block_size, forward, inverse }
+    }
+
+    /// a^e, bits of `a` above the block size are ignored.
+    pub fn forward(&self, a: &FieldElement) -> FieldElement {
+        FieldElement::new(self.forward[self.index(a)] as u128, self.block_size)
+    }
+
+    /// a^(1/e), i.e. b such that b^e = a. Bits of `a` above the block size are ignored.
+    pub fn inverse(&self, a: &FieldElement) -> FieldElement {
+        FieldElement::new(self.inverse[self.index(a)] as u128, self.block_size)
+    }
+
+    fn index(&self, a: &FieldElement) -> usize {
+        (a.value() & Uint::mask(self.block_size)).low_u128() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::exponentiation::ExponentiationPlan;
+    use crate::utils::helpers::{BinaryField, FieldElement, generate_random_bits};
+    use crate::utils::sbox::SBox;
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn forward_5() {
+        let field = BinaryField::new(5).unwrap();
+        let sbox = SBox::new(&field, &ExponentiationPlan::new(Uint::from(3u64)));
+        for x in 0..32 {
+            let a = FieldElement::new(x, 5);
+            assert_eq!(sbox.forward(&a), field.power(&a, 3));
+        }
+    }
+
+    #[test]
+    fn inverse_17() {
+        let field = BinaryField::new(17).unwrap();
+        let sbox = SBox::new(&field, &ExponentiationPlan::new(Uint::from(7u64)));
+        for _ in 0..100 {
+            let a = generate_random_bits(17);
+            assert_eq!(sbox.inverse(&sbox.forward(&a)), a);
+            assert_eq!(field.power(&sbox.inverse(&a), 7), a);
+        }
+    }
+
+    #[test]
+    fn wide_element() {
+        let sbox = SBox::new(&BinaryField::new(5).unwrap(), &ExponentiationPlan::new(Uint::from(3u64)));
+        let (wide, a) = (FieldElement::new((1 << 20) | 13, 32), FieldElement::new(13, 5));
+        assert_eq!(sbox.forward(&wide), sbox.forward(&a));
+        assert_eq!(sbox.inverse(&wide), sbox.inverse(&a));
+    }
+
+    #[test]
+    #[should_panic(expected = "This is not a permutation polynomial")]
+    fn not_permutation() {
+        // 2^8 - 1 is divisible by 3
+        SBox::new(&BinaryField::new(8).unwrap(), &ExponentiationPlan::new(Uint::from(3u64)));
+    }
+
+    #[test]
+    #[should_panic(expected = "S-box tables are only built for block sizes up to 17")]
+    fn too_large() {
+        SBox::new(&BinaryField::new(19).unwrap(), &ExponentiationPlan::new(Uint::from(3u64)));
+    }
+}
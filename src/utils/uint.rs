@@ -0,0 +1,377 @@
+//! Fixed width unsigned integer made of 64 bit limbs. Used as storage for field elements and GF(2) polynomials which
+//! do not fit into u128.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Shl, Shr, Sub};
+use std::str::FromStr;
+
+/// Number of 64 bit limbs in [`Uint`].
+pub const LIMBS: usize = 8;
+
+/// Unsigned integer of `64 * LIMBS` bits. Limbs are stored from the least significant one. Arithmetic operations wrap
+/// around on overflow.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Uint([u64; LIMBS]);
+
+impl Uint {
+    pub const BITS: u32 = 64 * LIMBS as u32;
+    pub const ZERO: Uint = Uint([0; LIMBS]);
+    pub const ONE: Uint = Uint::from_u128(1);
+
+    pub const fn from_u128(value: u128) -> Self {
+        let mut limbs = [0; LIMBS];
+        limbs[0] = value as u64;
+        limbs[1] = (value >> 64) as u64;
+        Uint(limbs)
+    }
+
+    /// Number from limbs given from the least significant one.
+    pub const fn from_limbs(limbs: [u64; LIMBS]) -> Self {
+        Uint(limbs)
+    }
+
+    /// Limbs from the least significant one.
+    pub fn limbs(self) -> [u64; LIMBS] {
+        self.0
+    }
+
+    /// Lowest 128 bits, the rest is discarded.
+    pub fn low_u128(self) -> u128 {
+        self.0[0] as u128 | (self.0[1] as u128) << 64
+    }
+
+    /// Number with the lowest `bits` bits set.
+    pub fn mask(bits: u32) -> Self {
+        let mut result = Uint::ZERO;
+        for (i, limb) in result.0.iter_mut().enumerate() {
+            let low = 64 * i as u32;
+            if bits >= low + 64 {
+                *limb = u64::MAX;
+            } else if bits > low {
+                *limb = (1 << (bits - low)) - 1;
+            }
+        }
+        result
+    }
+
+    pub fn bit(self, i: u32) -> bool {
+        (self.0[(i / 64) as usize] >> (i % 64)) & 1 == 1
+    }
+
+    pub fn flip_bit(self, i: u32) -> Self {
+        let mut result = self;
+        result.0[(i / 64) as usize] ^= 1 << (i % 64);
+        result
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == Uint::ZERO
+    }
+
+    /// Number of bits needed to represent the number, i.e. position of the highest set bit plus one.
+    pub fn bits(self) -> u32 {
+        for i in (0..LIMBS).rev() {
+            if self.0[i] != 0 {
+                return 64 * i as u32 + 64 - self.0[i].leading_zeros();
+            }
+        }
+        0
+    }
+
+    pub fn count_ones(self) -> u32 {
+        self.0.iter().map(|x| x.count_ones()).sum()
+    }
+
+    /// Divides by a small number returning quotient and remainder.
+    pub fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+        let mut quotient = Uint::ZERO;
+        let mut remainder = 0u128;
+        for i in (0..LIMBS).rev() {
+            let current = (remainder << 64) | self.0[i] as u128;
+            quotient.0[i] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (quotient, remainder as u64)
+    }
+
+    /// self * multiplier + addend
+    pub fn mul_add_u64(self, multiplier: u64, addend: u64) -> Self {
+        let mut result = Uint::ZERO;
+        let mut carry = addend as u128;
+        for i in 0..LIMBS {
+            let current = self.0[i] as u128 * multiplier as u128 + carry;
+            result.0[i] = current as u64;
+            carry = current >> 64;
+        }
+        result
+    }
+
+    /// Quotient and remainder of the division, using binary long division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(self, divisor: Uint) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "Division by zero");
+        let mut quotient = Uint::ZERO;
+        let mut remainder = Uint::ZERO;
+        for i in (0..self.bits()).rev() {
+            remainder = remainder << 1;
+            if self.bit(i) {
+                remainder.0[0] |= 1;
+            }
+            if remainder >= divisor {
+                remainder = remainder - divisor;
+                quotient.0[(i / 64) as usize] |= 1 << (i % 64);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    /// Parses number in the given radix (up to 16).
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, String> {
+        if s.is_empty() {
+            return Err("cannot parse number from empty string".to_string());
+        }
+        let mut result = Uint::ZERO;
+        for c in s.chars() {
+            let digit = c.to_digit(radix).ok_or_else(|| format!("invalid digit '{c}' in number '{s}'"))?;
+            let next = result.mul_add_u64(radix as u64, digit as u64);
+            if next.div_rem_u64(radix as u64) != (result, digit as u64) {
+                return Err(format!("number '{s}' does not fit into {} bits", Uint::BITS));
+            }
+            result = next;
+        }
+        Ok(result)
+    }
+}
+
+impl From<u64> for Uint {
+    fn from(value: u64) -> Self {
+        Uint::from_u128(value as u128)
+    }
+}
+
+impl From<u128> for Uint {
+    fn from(value: u128) -> Self {
+        Uint::from_u128(value)
+    }
+}
+
+impl Add for Uint {
+    type Output = Uint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = Uint::ZERO;
+        let mut carry = false;
+        for i in 0..LIMBS {
+            let (sum, overflow) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, overflow_carry) = sum.overflowing_add(carry as u64);
+            result.0[i] = sum;
+            carry = overflow || overflow_carry;
+        }
+        result
+    }
+}
+
+impl Sub for Uint {
+    type Output = Uint;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = Uint::ZERO;
+        let mut borrow = false;
+        for i in 0..LIMBS {
+            let (difference, overflow) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (difference, overflow_borrow) = difference.overflowing_sub(borrow as u64);
+            result.0[i] = difference;
+            borrow = overflow || overflow_borrow;
+        }
+        result
+    }
+}
+
+impl Mul for Uint {
+    type Output = Uint;
+
+    /// Schoolbook multiplication, limbs of the product above `LIMBS` are discarded.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut result = Uint::ZERO;
+        for i in 0..LIMBS {
+            let mut carry = 0u128;
+            for j in 0..LIMBS - i {
+                let current = self.0[i] as u128 * rhs.0[j] as u128 + result.0[i + j] as u128 + carry;
+                result.0[i + j] = current as u64;
+                carry = current >> 64;
+            }
+        }
+        result
+    }
+}
+
+impl BitXor for Uint {
+    type Output = Uint;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Uint(std::array::from_fn(|i| self.0[i] ^ rhs.0[i]))
+    }
+}
+
+impl BitAnd for Uint {
+    type Output = Uint;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Uint(std::array::from_fn(|i| self.0[i] & rhs.0[i]))
+    }
+}
+
+impl BitOr for Uint {
+    type Output = Uint;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Uint(std::array::from_fn(|i| self.0[i] | rhs.0[i]))
+    }
+}
+
+impl Shl<u32> for Uint {
+    type Output = Uint;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        let (limbs, bits) = ((rhs / 64) as usize, rhs % 64);
+        let mut result = Uint::ZERO;
+        for i in limbs..LIMBS {
+            result.0[i] = self.0[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                result.0[i] |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        result
+    }
+}
+
+impl Shr<u32> for Uint {
+    type Output = Uint;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        let (limbs, bits) = ((rhs / 64) as usize, rhs % 64);
+        let mut result = Uint::ZERO;
+        for i in 0..LIMBS.saturating_sub(limbs) {
+            result.0[i] = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < LIMBS {
+                result.0[i] |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        result
+    }
+}
+
+impl Ord for Uint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for Uint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Uint {
+    type Err = String;
+
+    /// Parses either decimal number or hex number starting with `0x`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => Uint::from_str_radix(hex, 16),
+            None => Uint::from_str_radix(s, 10),
+        }
+    }
+}
+
+impl fmt::Display for Uint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Dividing by 10^19, the largest power of 10 fitting into u64
+        const BASE: u64 = 10_000_000_000_000_000_000;
+        let mut parts = Vec::new();
+        let mut rest = *self;
+        loop {
+            let (quotient, remainder) = rest.div_rem_u64(BASE);
+            parts.push(remainder);
+            rest = quotient;
+            if rest.is_zero() {
+                break;
+            }
+        }
+        let mut result = parts.pop().unwrap().to_string();
+        for part in parts.iter().rev() {
+            result.push_str(&format!("{part:019}"));
+        }
+        f.pad_integral(true, "", &result)
+    }
+}
+
+impl fmt::Debug for Uint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for Uint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let highest = (0..LIMBS).rev().find(|&i| self.0[i] != 0).unwrap_or(0);
+        let mut result = format!("{:x}", self.0[highest]);
+        for i in (0..highest).rev() {
+            result.push_str(&format!("{:016x}", self.0[i]));
+        }
+        f.pad_integral(true, "0x", &result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::uint::Uint;
+
+    #[test]
+    fn parse_and_print() {
+        let a: Uint = "231584178474632390847141970017375815706539969331281128078915168015826259279871".parse().unwrap();
+        assert_eq!(a, Uint::mask(257)); // 2^257 - 1
+        assert_eq!(a.to_string(), "231584178474632390847141970017375815706539969331281128078915168015826259279871");
+        assert_eq!("0x1ffff".parse::<Uint>(), Ok(Uint::from(131071u64)));
+        assert_eq!(format!("{:#x}", Uint::ONE << 200), format!("0x1{}", "0".repeat(50)));
+        assert_eq!(Uint::ZERO.to_string(), "0");
+        assert!("12a".parse::<Uint>().is_err());
+        assert!(format!("0x1{}", "0".repeat(128)).parse::<Uint>().is_err()); // 2^512 does not fit
+    }
+
+    #[test]
+    fn shifts() {
+        let a = Uint::from(0b1011u64);
+        assert_eq!((a << 190) >> 190, a);
+        assert_eq!((a << 64).low_u128(), 0b1011 << 64);
+        assert_eq!((a << 511) >> 511, Uint::ONE);
+        assert_eq!((a << 300) >> 301, Uint::from(0b101u64));
+        assert_eq!((a << 300).bits(), 304);
+        assert_eq!(Uint::mask(130).count_ones(), 130);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Uint::mask(300);
+        assert_eq!(a + Uint::ONE, Uint::ONE << 300);
+        assert_eq!((a + Uint::ONE) - Uint::ONE, a);
+        assert_eq!(Uint::ZERO - Uint::ONE, Uint::mask(Uint::BITS)); // Wraps around
+        assert_eq!(Uint::from(u128::MAX) * Uint::from(u128::MAX), (Uint::ONE << 256) - (Uint::ONE << 129) + Uint::ONE);
+        let (quotient, remainder) = a.div_rem(Uint::from(1000u64));
+        assert_eq!(quotient * Uint::from(1000u64) + remainder, a);
+        assert!(remainder < Uint::from(1000u64));
+        assert_eq!(Uint::mask(256).div_rem(Uint::mask(128)), (Uint::ONE << 128 | Uint::ONE, Uint::ZERO));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Uint::ONE << 128 > Uint::from(u128::MAX));
+        assert!(Uint::from(5u64) < Uint::from(6u64));
+    }
+}
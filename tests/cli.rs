@@ -1,16 +1,16 @@
 use std::process::Command;
 use assert_cmd::prelude::{CommandCargoExt, OutputAssertExt};
-use predicates::prelude::predicate;
+use predicates::prelude::{predicate, PredicateBooleanExt};
 
 #[test]
 fn cipher_test_happy_path_11() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("mimcge")?;
 
     cmd.arg("cipher-test").arg("mimcge").arg("11")
-        .args(&["-p", "1868"])
-        .args(&["-k", "1362"])
-        .args(&["-e", "7"])
-        .args(&["-R", "0", "773", "996", "1417"]);
+        .args(["-p", "1868"])
+        .args(["-k", "1362"])
+        .args(["-e", "7"])
+        .args(["-R", "0", "773", "996", "1417"]);
 
     cmd.assert()
         .success()
@@ -24,10 +24,10 @@ fn cipher_test_happy_path_33() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("mimcge")?;
 
     cmd.arg("cipher-test").arg("mimcge").arg("33")
-        .args(&["-p", "2121644265"])
-        .args(&["-k", "2704582896"])
-        .args(&["-e", "11"])
-        .args(&["-R", "0", "4167564917", "2955227280", "6014621339", "7070376341", "3894014214", "5647237025", "681616375", "6711229718", "2588429073"]);
+        .args(["-p", "2121644265"])
+        .args(["-k", "2704582896"])
+        .args(["-e", "11"])
+        .args(["-R", "0", "4167564917", "2955227280", "6014621339", "7070376341", "3894014214", "5647237025", "681616375", "6711229718", "2588429073"]);
 
     cmd.assert()
         .success()
@@ -36,6 +36,152 @@ fn cipher_test_happy_path_33() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn cipher_test_generated_polynomial_13() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("13")
+        .args(["-p", "1234"])
+        .args(["-k", "4321"])
+        .args(["-e", "5"])
+        .args(["-R", "0", "11", "22", "33"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Plaintext:  1234 [0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 0, 1, 0]\nCiphertext: 4724 [1, 0, 0, 1, 0, 0, 1, 1, 1, 0, 1, 0, 0]\nDecrypted:  1234 [0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 0, 1, 0]"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_decrypt_127() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("127")
+        .args(["-p", "12345"])
+        .args(["-k", "67890"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Decrypted:  12345 "));
+
+    Ok(())
+}
+
+#[test]
+fn enc_time_257_with_modulus() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("enc-time").arg("mimcge").arg("257")
+        .args(["-e", "3"])
+        .args(["-m", "257,12,0"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Total time to encrypt"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_prime_field_101() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("7")
+        .args(["-p", "33"])
+        .args(["-k", "71"])
+        .args(["-e", "3"])
+        .args(["-R", "0", "5", "17", "42", "88"])
+        .args(["--field", "prime:101"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Plaintext:  33 [0, 1, 0, 0, 0, 0, 1]\nCiphertext: 66 [1, 0, 0, 0, 0, 1, 0]\nDecrypted:  33 [0, 1, 0, 0, 0, 0, 1]"));
+
+    Ok(())
+}
+
+#[test]
+fn diffusion_bn254() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("diffusion").arg("mimcge").arg("254")
+        .args(["-e", "7"])
+        .args(["-t", "2"])
+        .args(["--field", "bn254"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Diffusion tested with 2 plaintexts"));
+
+    Ok(())
+}
+
+#[test]
+fn dec_time_bls12_381() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("dec-time").arg("mimcge").arg("255")
+        .args(["-e", "5"])
+        .args(["--field", "prime:bls12-381"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Total time to decrypt 1x1 ciphertexts"));
+
+    Ok(())
+}
+
+#[test]
+fn dec_time_square_multiply_127() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("dec-time").arg("mimc").arg("127")
+        .args(["-d", "square-multiply"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Total time to decrypt 1x1 ciphertexts"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_constant_time_11() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("11")
+        .args(["-p", "1868"])
+        .args(["-k", "1362"])
+        .args(["-e", "7"])
+        .args(["-R", "0", "773", "996", "1417"])
+        .arg("--constant-time");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Plaintext:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]\nCiphertext: 1962 [1, 1, 1, 1, 0, 1, 0, 1, 0, 1, 0]\nDecrypted:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_g2p_backend_11() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("11")
+        .args(["-p", "1868"])
+        .args(["-k", "1362"])
+        .args(["-e", "7"])
+        .args(["-R", "0", "773", "996", "1417"])
+        .args(["--backend", "g2p"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Plaintext:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]\nCiphertext: 1962 [1, 1, 1, 1, 0, 1, 0, 1, 0, 1, 0]\nDecrypted:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]"));
+
+    Ok(())
+}
+
 #[test]
 fn error_wrong_cipher() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("mimcge")?;
@@ -53,7 +199,7 @@ fn error_wrong_cipher() -> Result<(), Box<dyn std::error::Error>> {
 fn error_wrong_block_size() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("mimcge")?;
 
-    cmd.arg("enc-time").arg("mimcge").arg("64");
+    cmd.arg("enc-time").arg("mimcge").arg("512");
 
     cmd.assert()
         .failure()
@@ -61,3 +207,490 @@ fn error_wrong_block_size() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn error_reducible_modulus() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("8").args(["-e", "2"]).args(["-m", "8,4,0"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("polynomial 0x111 is reducible"));
+
+    Ok(())
+}
+
+#[test]
+fn error_modulus_block_size_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("9").args(["-e", "2"]).args(["-m", "0x11B"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("modulus of degree 8 does not match block size 9"));
+
+    Ok(())
+}
+
+#[test]
+fn warning_non_primitive_modulus() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("8").args(["-e", "2"]).args(["-m", "0x11B"]);
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: modulus 0x11b is not primitive"));
+
+    Ok(())
+}
+
+#[test]
+fn error_prime_field_not_mimcge() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimc").arg("7").args(["--field", "prime:101"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("prime fields are only supported by mimcge cipher"));
+
+    Ok(())
+}
+
+#[test]
+fn error_prime_field_not_prime() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("7").args(["--field", "prime:100"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("100 is not an odd prime"));
+
+    Ok(())
+}
+
+#[test]
+fn error_g2p_backend_block_size() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("diffusion").arg("mimcge").arg("13").args(["--backend", "g2p"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("g2p backend is only available for block sizes [5, 8, 11, 17]"));
+
+    Ok(())
+}
+
+#[test]
+fn error_not_permutation() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("diffusion").arg("mimcge").arg("8").args(["-e", "3"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("error: x^3 is not a permutation polynomial of the field"))
+        .stderr(predicate::str::contains("panicked").not());
+
+    Ok(())
+}
+
+#[test]
+fn error_round_reduction() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("enc-time").arg("mimcge").arg("17").args(["-r", "11"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("round reduction 11 leaves no rounds, the cipher has 11"));
+
+    Ok(())
+}
+
+#[test]
+fn error_mimc_even_block_size() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimc").arg("16");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("block size must be odd, got 16"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_mimcge_feistel() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge-feistel").arg("16").args(["-e", "5", "-k", "0x34", "-p", "0xabcd"]).args(["-R", "0", "5", "22", "16"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Cipher:     MiMCGeFeistel (e = 5, n = 2x8, 4 rounds, modulus 0x11d)"))
+        .stdout(predicate::str::contains("Plaintext:  43981 [1, 0, 1, 0, 1, 0, 1, 1, 1, 1, 0, 0, 1, 1, 0, 1]\nCiphertext: 10957 [0, 0, 1, 0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 1, 0, 1]\nDecrypted:  43981 [1, 0, 1, 0, 1, 0, 1, 1, 1, 1, 0, 0, 1, 1, 0, 1]"));
+
+    Ok(())
+}
+
+#[test]
+fn error_mimcge_feistel_odd_block_size() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("enc-time").arg("mimcge-feistel").arg("33");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("block size of a Feistel cipher must be even, got 33"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_gmimc_mrf() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("gmimc").arg("32").args(["--variant", "mrf", "--branches", "4", "-e", "5", "-k", "0x2a", "-p", "0xdeadbeef"])
+        .args(["-R", "1", "2", "3", "4", "5", "6"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Cipher:     GMiMC (MRF, e = 5, n = 4x8, 6 rounds, modulus 0x11d)"))
+        .stdout(predicate::str::contains("Ciphertext: 1911481991 "))
+        .stdout(predicate::str::contains("Decrypted:  3735928559 "));
+
+    Ok(())
+}
+
+#[test]
+fn error_gmimc_branches() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("diffusion").arg("gmimc").arg("33").args(["--branches", "4"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("block size 33 cannot be split into 4 branches of at least 2 bits"));
+
+    Ok(())
+}
+
+#[test]
+fn hash_sponge_stdin() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("mimcge")?;
+
+    cmd.arg("hash").arg("mimcge-feistel").arg("64").args(["-e", "5", "--rate", "32"]).write_stdin("abc");
+
+    cmd.assert()
+        .success()
+        .stdout("1700ea6dfe088e76  -\n")
+        .stderr(predicate::str::contains("Sponge (rate 32, capacity 32, 64-bit digest, multi-rate padding, MiMCGeFeistel"));
+
+    Ok(())
+}
+
+#[test]
+fn hash_miyaguchi_preneel_file() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join("mimcge_hash_miyaguchi_preneel_file");
+    std::fs::write(&path, "abc")?;
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("hash").arg("mimcge").arg("33").args(["-e", "5", "--construction", "miyaguchi-preneel"]).arg("-i").arg(&path);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("f685944c00  "));
+
+    Ok(())
+}
+
+#[test]
+fn error_hash_missing_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("hash").arg("mimcge").arg("33").args(["-i", "no/such/file"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("error: no/such/file: "));
+
+    Ok(())
+}
+
+#[test]
+fn encrypt_decrypt_file_cbc_33() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = std::env::temp_dir();
+    let (plaintext, ciphertext) = (directory.join("mimcge_cbc_33_plaintext"), directory.join("mimcge_cbc_33_ciphertext"));
+    std::fs::write(&plaintext, "Not a multiple of 33 bits")?;
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("encrypt-file").arg("mimcge").arg("33").args(["-e", "5", "-k", "0x1abcdef01", "--mode", "cbc"])
+        .arg("-i").arg(&plaintext).arg("-o").arg(&ciphertext);
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Encrypted 25 bytes with MiMCGe (e = 5, n = 33, 15 rounds, modulus 0x200003d49) in CBC mode"));
+    // 5 bytes of IV and 8 blocks of 33 bits
+    assert_eq!(std::fs::read(&ciphertext)?.len(), 5 + 33);
+
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("decrypt-file").arg("mimcge").arg("33").args(["-e", "5", "-k", "0x1abcdef01", "--mode", "cbc"]).arg("-i").arg(&ciphertext);
+
+    cmd.assert()
+        .success()
+        .stdout("Not a multiple of 33 bits");
+
+    Ok(())
+}
+
+#[test]
+fn error_encrypt_file_without_key() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("encrypt-file").arg("mimcge").arg("17");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("encrypt-file and decrypt-file need --key"));
+
+    Ok(())
+}
+
+#[test]
+fn fpe_encrypt_decrypt() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("fpe").arg("mimcge").args(["--domain", "1000000", "-p", "123456", "-k", "42", "-e", "5"]);
+
+    cmd.assert()
+        .success()
+        .stdout("670975\n")
+        .stderr(predicate::str::contains("Cipher:     MiMCGe (e = 5, n = 21, 10 rounds, modulus 0x200005), domain [0, 1000000)"));
+
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("fpe").arg("mimcge").args(["--domain", "1000000", "-p", "670975", "-k", "42", "-e", "5", "--decrypt"]);
+
+    cmd.assert()
+        .success()
+        .stdout("123456\n");
+
+    Ok(())
+}
+
+#[test]
+fn error_fpe_out_of_domain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("fpe").arg("mimcge").args(["--domain", "1000", "-p", "1000", "-k", "1"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("value 1000 is not in the domain [0, 1000)"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_tweak_11() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("11")
+        .args(["-p", "1868"])
+        .args(["-k", "1362"])
+        .args(["-e", "7"])
+        .args(["-R", "0", "773", "996", "1417"])
+        .args(["--tweak", "5"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Tweak:      5 [0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1]"))
+        .stdout(predicate::str::contains("Plaintext:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]\nCiphertext: 537 [0, 1, 0, 0, 0, 0, 1, 1, 0, 0, 1]\nDecrypted:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_zero_tweak_11() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    // Same ciphertext as without the tweak
+    cmd.arg("cipher-test").arg("mimcge").arg("11").args(["-p", "1868", "-k", "1362", "-e", "7", "--tweak", "0"]).args(["-R", "0", "773", "996", "1417"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Ciphertext: 1962 [1, 1, 1, 1, 0, 1, 0, 1, 0, 1, 0]"));
+
+    Ok(())
+}
+
+#[test]
+fn tweak_diffusion_33() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("tweak-diffusion").arg("mimcge").arg("33").args(["-e", "5", "-t", "10"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Tweak diffusion tested with 10 plaintexts"))
+        .stdout(predicate::str::contains("Expected mean: 544.5"));
+
+    Ok(())
+}
+
+#[test]
+fn error_tweak_not_mimcge() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge-feistel").arg("16").args(["--tweak", "1"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("tweak is only supported by MiMCGe over 2^n fields"));
+
+    Ok(())
+}
+
+#[test]
+fn cipher_test_independent_key_schedule_11() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("mimcge").arg("11")
+        .args(["-p", "1868"])
+        .args(["-k", "0x4bc"])
+        .args(["-e", "7"])
+        .args(["-R", "0", "773", "996", "1417"])
+        .args(["--key-schedule", "independent"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Cipher:     MiMCGe (e = 7, n = 11, 4 rounds, modulus 0x805, independent key schedule)"))
+        .stdout(predicate::str::contains("Plaintext:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]\nCiphertext: 689 [0, 1, 0, 1, 0, 1, 1, 0, 0, 0, 1]\nDecrypted:  1868 [1, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0]"));
+
+    Ok(())
+}
+
+#[test]
+fn confusion_two_key_schedule_33() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("confusion").arg("mimcge").arg("33").args(["-e", "5", "-t", "10", "--key-schedule", "2k"]);
+
+    // All 66 key bits are flipped
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("expected mean: 1089 "));
+
+    Ok(())
+}
+
+#[test]
+fn error_key_schedule_not_mimc() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("cipher-test").arg("gmimc").arg("32").args(["--key-schedule", "iterated"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("key schedules are only supported by mimc and mimcge ciphers over 2^n fields"));
+
+    Ok(())
+}
+
+#[test]
+fn error_two_key_too_large() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("confusion").arg("mimcge").arg("257").args(["--key-schedule", "2k"]);
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("key of 514 bits does not fit into 512 bits"));
+
+    Ok(())
+}
+
+#[test]
+fn mac_cmac_aes_empty() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("mimcge")?;
+
+    // Test vector of RFC 4493
+    cmd.arg("mac").arg("aes").arg("128").args(["-k", "0x2b7e151628aed2a6abf7158809cf4f3c"]).write_stdin("");
+
+    cmd.assert()
+        .success()
+        .stdout("bb1d6929e95937287fa37d129b756746  -\n")
+        .stderr(predicate::str::contains("CMAC (128-bit tag, subkeys modulus 0x100000000000000000000000000000087, AES"));
+
+    Ok(())
+}
+
+#[test]
+fn mac_verify_cbc_mac_33() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("mimcge")?;
+
+    cmd.arg("mac").arg("mimcge").arg("33").args(["-e", "5", "-k", "0x1abcdef01", "--mac", "cbc-mac"]).write_stdin("abc");
+
+    cmd.assert()
+        .success()
+        .stdout("3bc942b880  -\n");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("mimcge")?;
+
+    cmd.arg("verify").arg("mimcge").arg("33").args(["-e", "5", "-k", "0x1abcdef01", "--mac", "cbc-mac", "--tag", "3bc942b880"]).write_stdin("abc");
+
+    cmd.assert()
+        .success()
+        .stdout("-: OK\n");
+
+    Ok(())
+}
+
+#[test]
+fn error_verify_modified_message() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("mimcge")?;
+
+    cmd.arg("verify").arg("mimcge").arg("33").args(["-e", "5", "-k", "0x1abcdef01", "--mac", "cbc-mac", "--tag", "3bc942b880"]).write_stdin("abd");
+
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("tag does not authenticate the message"));
+
+    Ok(())
+}
+
+#[test]
+fn mac_sponge_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("mac").arg("mimcge-feistel").arg("64").args(["-e", "5", "-k", "0x1234", "--mac", "sponge", "--rate", "32", "--digest-size", "128"])
+        .args(["--input", "Cargo.toml"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match("^[0-9a-f]{32}  Cargo.toml\n$")?)
+        .stderr(predicate::str::contains("SpongeMAC (64-bit key, rate 32, capacity 32, 128-bit digest"));
+
+    Ok(())
+}
+
+#[test]
+fn error_mac_without_key() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("mimcge")?;
+
+    cmd.arg("mac").arg("mimcge").arg("33");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("mac and verify need --key"));
+
+    Ok(())
+}